@@ -0,0 +1,21 @@
+/*
+    ===================================================
+    Migration Version : 003
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Key/value settings used to
+                        configure grading computations
+    ===================================================
+*/
+
+CREATE TABLE settings (
+/*
+    The configuration entry "key" holding the
+    textual value "value".
+*/
+    key         VARCHAR(60)  NOT NULL PRIMARY KEY,
+    value       VARCHAR(255) NOT NULL
+);
+
+-- Missing notes are left out of weighted averages by default
+INSERT INTO settings (key, value) VALUES ('grading.missing_notes', 'exclude');
@@ -0,0 +1,54 @@
+/*
+    ===================================================
+    Migration Version : 004
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Configurable mentions and
+                        end-of-year decision rules
+    ===================================================
+*/
+
+CREATE TABLE mention_rules (
+/*
+    The mention "label" granted to averages
+    greater than or equal to "min_average".
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    label       VARCHAR(30)  NOT NULL UNIQUE,
+    min_average REAL         NOT NULL
+);
+
+CREATE TABLE decision_rules (
+/*
+    Single row holding the thresholds of the
+    end-of-year decision (admis, redouble, exclu).
+*/
+    id                      INTEGER PRIMARY KEY CHECK (id = 1),
+    admission_average       REAL    NOT NULL DEFAULT 10,
+    exclusion_average       REAL    NOT NULL DEFAULT 7,
+    failing_subject_average REAL    NOT NULL DEFAULT 10,
+    max_failed_subjects     INTEGER NOT NULL DEFAULT 3
+);
+
+CREATE TABLE key_subject_rules (
+/*
+    Minimum average "min_average" required in the
+    subjects whose code starts with "subject_prefix".
+*/
+    subject_prefix VARCHAR(60) NOT NULL PRIMARY KEY,
+    min_average    REAL        NOT NULL
+);
+
+INSERT INTO mention_rules (label, min_average) VALUES
+    ('Excellent', 18),
+    ('Très Bien', 16),
+    ('Bien', 14),
+    ('Assez Bien', 12),
+    ('Passable', 10),
+    ('Insuffisant', 0);
+
+INSERT INTO decision_rules (id) VALUES (1);
+
+INSERT INTO key_subject_rules (subject_prefix, min_average) VALUES
+    ('MATH', 8),
+    ('FR', 8);
@@ -0,0 +1,63 @@
+/*
+    ===================================================
+    Migration Version : 005
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Academic years divided into
+                        terms, notes attached to a term
+    ===================================================
+*/
+
+CREATE TABLE academic_years (
+/*
+    The school year "label" (e.g. 2025-2026) running
+    from "start_date" to "end_date" and divided into
+    terms of kind "period_kind" (trimestre or semestre).
+    Only one year is the current one.
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    label       VARCHAR(20) NOT NULL UNIQUE,
+    start_date  DATE        NOT NULL CHECK (date(start_date) = start_date),
+    end_date    DATE        NOT NULL CHECK (date(end_date) = end_date),
+    period_kind VARCHAR(10) NOT NULL CHECK (period_kind IN ('trimestre', 'semestre')),
+    is_current  BOOLEAN     NOT NULL DEFAULT 0,
+
+    CHECK (start_date < end_date)
+);
+
+CREATE TABLE periods (
+/*
+    The term number "number" of the academic year
+    "academic_year_id", from "start_date" to "end_date".
+*/
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    academic_year_id INTEGER NOT NULL,
+    number           INTEGER NOT NULL CHECK (number > 0),
+    start_date       DATE    NOT NULL CHECK (date(start_date) = start_date),
+    end_date         DATE    NOT NULL CHECK (date(end_date) = end_date),
+
+    CHECK (start_date < end_date),
+    UNIQUE (academic_year_id, number),
+    FOREIGN KEY (academic_year_id) REFERENCES academic_years(id) ON DELETE CASCADE
+);
+
+ALTER TABLE notes ADD COLUMN period_id INTEGER REFERENCES periods(id);
+
+CREATE INDEX idx_notes_period ON notes(period_id);
+
+-- School year running when the migration is applied (from August on,
+-- the one starting in September), divided into three terms
+INSERT INTO academic_years (label, start_date, end_date, period_kind, is_current)
+SELECT year || '-' || (year + 1), year || '-09-01', (year + 1) || '-07-03', 'trimestre', 1
+FROM (SELECT CAST(strftime('%Y', 'now') AS INTEGER) - (CAST(strftime('%m', 'now') AS INTEGER) < 8) AS year);
+
+INSERT INTO periods (academic_year_id, number, start_date, end_date)
+SELECT id, 1, start_date, substr(start_date, 1, 4) || '-12-19' FROM academic_years
+UNION ALL
+SELECT id, 2, substr(end_date, 1, 4) || '-01-05', substr(end_date, 1, 4) || '-03-27' FROM academic_years
+UNION ALL
+SELECT id, 3, substr(end_date, 1, 4) || '-04-13', end_date FROM academic_years;
+
+-- Notes recorded before terms existed keep no period: nothing tells
+-- which term they belong to. The notes integrity check lists them so
+-- that they can be attached to the right term.
@@ -0,0 +1,56 @@
+/*
+    ===================================================
+    Migration Version : 006
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Weighted evaluation types and
+                        evaluations that notes belong to
+    ===================================================
+*/
+
+CREATE TABLE evaluation_types (
+/*
+    The kind of assessment "label" identified by
+    "code". Within a subject, the average of each
+    type counts for "weight" relative to the other
+    types evaluated. Notes recorded outside any
+    evaluation belong to the default type.
+*/
+    code        VARCHAR(20) NOT NULL PRIMARY KEY,
+    label       VARCHAR(60) NOT NULL,
+    weight      REAL        NOT NULL CHECK (weight > 0),
+    is_default  BOOLEAN     NOT NULL DEFAULT 0
+);
+
+CREATE TABLE evaluations (
+/*
+    The assessment "title" of type "type_code" given
+    in the subject "subject_code" on "date", during
+    the period "period_id". Scores are out of
+    "max_score" and the evaluation counts for
+    "weight" among the evaluations of its type.
+*/
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject_code VARCHAR(60)  NOT NULL,
+    period_id    INTEGER      NOT NULL,
+    type_code    VARCHAR(20)  NOT NULL,
+    title        VARCHAR(100) NOT NULL,
+    date         DATE         NOT NULL CHECK (date(date) = date),
+    max_score    REAL         NOT NULL DEFAULT 20 CHECK (max_score > 0),
+    weight       REAL         NOT NULL DEFAULT 1 CHECK (weight > 0),
+
+    FOREIGN KEY (subject_code) REFERENCES subjects(code),
+    FOREIGN KEY (period_id) REFERENCES periods(id),
+    FOREIGN KEY (type_code) REFERENCES evaluation_types(code)
+);
+
+ALTER TABLE notes ADD COLUMN evaluation_id INTEGER REFERENCES evaluations(id);
+
+CREATE INDEX idx_notes_evaluation ON notes(evaluation_id);
+
+-- 40 % continuous assessment, 60 % composition
+INSERT INTO evaluation_types (code, label, weight, is_default) VALUES
+    ('devoir', 'Devoir', 20, 1),
+    ('interrogation', 'Interrogation', 20, 0),
+    ('composition', 'Composition', 60, 0),
+    ('examen', 'Examen', 60, 0);
@@ -0,0 +1,77 @@
+/*
+    ===================================================
+    Migration Version : 007
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Classes table referenced by
+                        students and subjects
+    ===================================================
+*/
+
+CREATE TABLE classes (
+/*
+    The class identified by "code" (e.g. 6ème A) of
+    the level "level" (e.g. 6ème) and section "section"
+    (e.g. A), opened for the academic year
+    "academic_year_id", followed by the head teacher
+    "head_teacher" and holding at most "capacity"
+    students.
+*/
+    code             VARCHAR(20)  NOT NULL PRIMARY KEY,
+    level            VARCHAR(20)  NOT NULL,
+    section          VARCHAR(10),
+    academic_year_id INTEGER,
+    head_teacher     VARCHAR(120),
+    capacity         INTEGER      CHECK (capacity IS NULL OR capacity > 0),
+
+    FOREIGN KEY (academic_year_id) REFERENCES academic_years(id) ON DELETE SET NULL
+);
+
+-- Every class already used by a student or a subject, in the current year
+INSERT INTO classes (code, level, academic_year_id)
+SELECT class, class, (SELECT id FROM academic_years WHERE is_current = 1)
+FROM (SELECT class FROM students UNION SELECT class FROM subjects);
+
+-- Rebuild students and subjects to reference their class
+CREATE TABLE students_new (
+/*
+    The student "name" with surname "surname"
+    enrolled in the class "class" and identified
+    by the unique matricule "matricule".
+*/
+    matricule   VARCHAR(255) NOT NULL PRIMARY KEY,
+    name        VARCHAR(60)  NOT NULL,
+    surname     VARCHAR(60)  NOT NULL,
+    class       VARCHAR(20)  NOT NULL,
+
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE
+);
+
+INSERT INTO students_new (matricule, name, surname, class)
+SELECT matricule, name, surname, class FROM students;
+
+DROP TABLE students;
+ALTER TABLE students_new RENAME TO students;
+
+CREATE TABLE subjects_new (
+/*
+    The subject "name" identified by the code "code"
+    taught in the class "class" and weighted by
+    the coefficient "coefficient".
+*/
+    code        VARCHAR(60)  NOT NULL PRIMARY KEY,
+    name        VARCHAR(60)  NOT NULL,
+    class       VARCHAR(20)  NOT NULL,
+    coefficient INTEGER      NOT NULL DEFAULT 1,
+
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE
+);
+
+INSERT INTO subjects_new (code, name, class, coefficient)
+SELECT code, name, class, coefficient FROM subjects;
+
+DROP TABLE subjects;
+ALTER TABLE subjects_new RENAME TO subjects;
+
+CREATE INDEX idx_students_class ON students(class);
+CREATE INDEX idx_subjects_class ON subjects(class);
@@ -0,0 +1,26 @@
+/*
+    ===================================================
+    Migration Version : 008
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Reject notes in a subject that
+                        is not taught in the student's
+                        class
+    ===================================================
+*/
+
+CREATE TRIGGER notes_class_check_insert
+BEFORE INSERT ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> (SELECT class FROM students WHERE matricule = NEW.matricule)
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
+
+CREATE TRIGGER notes_class_check_update
+BEFORE UPDATE OF matricule, subject_code ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> (SELECT class FROM students WHERE matricule = NEW.matricule)
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
@@ -0,0 +1,93 @@
+/*
+    ===================================================
+    Migration Version : 009
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : CHECK constraints on note values
+                        and subject coefficients, and
+                        configurable grading scale
+    ===================================================
+*/
+
+-- Notes are stored out of 20. The grading scale can narrow the bounds
+-- and require half or quarter points.
+INSERT INTO settings (key, value) VALUES
+    ('grading.scale_min', '0'),
+    ('grading.scale_max', '20'),
+    ('grading.scale_step', 'any');
+
+-- The class check of the notes refers to subjects, which is rebuilt
+DROP TRIGGER notes_class_check_insert;
+DROP TRIGGER notes_class_check_update;
+
+-- Legacy values breaking the new constraints are kept as they are, to be
+-- reported by the scan of the settings menu and corrected by hand
+PRAGMA ignore_check_constraints = ON;
+
+CREATE TABLE subjects_new (
+/*
+    The subject "name" identified by the code "code"
+    taught in the class "class" and weighted by
+    the coefficient "coefficient" (1 to 255).
+*/
+    code        VARCHAR(60)  NOT NULL PRIMARY KEY,
+    name        VARCHAR(60)  NOT NULL,
+    class       VARCHAR(20)  NOT NULL,
+    coefficient INTEGER      NOT NULL DEFAULT 1 CHECK (coefficient BETWEEN 1 AND 255),
+
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE
+);
+
+INSERT INTO subjects_new (code, name, class, coefficient)
+SELECT code, name, class, coefficient FROM subjects;
+
+DROP TABLE subjects;
+ALTER TABLE subjects_new RENAME TO subjects;
+
+CREATE INDEX idx_subjects_class ON subjects(class);
+
+CREATE TABLE notes_new (
+/*
+    The grade "value" out of 20 obtained by the
+    student identified by "matricule" for the
+    subject identified by "subject_code".
+*/
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    matricule    VARCHAR(255) NOT NULL,
+    subject_code VARCHAR(60)  NOT NULL,
+    value        REAL         NOT NULL CHECK (typeof(value) IN ('real', 'integer') AND value BETWEEN 0 AND 20),
+    period_id    INTEGER,
+    evaluation_id INTEGER,
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule),
+    FOREIGN KEY (subject_code) REFERENCES subjects(code),
+    FOREIGN KEY (period_id) REFERENCES periods(id),
+    FOREIGN KEY (evaluation_id) REFERENCES evaluations(id)
+);
+
+INSERT INTO notes_new (id, matricule, subject_code, value, period_id, evaluation_id)
+SELECT id, matricule, subject_code, value, period_id, evaluation_id FROM notes;
+
+DROP TABLE notes;
+ALTER TABLE notes_new RENAME TO notes;
+
+PRAGMA ignore_check_constraints = OFF;
+
+CREATE INDEX idx_notes_period ON notes(period_id);
+CREATE INDEX idx_notes_evaluation ON notes(evaluation_id);
+
+CREATE TRIGGER notes_class_check_insert
+BEFORE INSERT ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> (SELECT class FROM students WHERE matricule = NEW.matricule)
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
+
+CREATE TRIGGER notes_class_check_update
+BEFORE UPDATE OF matricule, subject_code ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> (SELECT class FROM students WHERE matricule = NEW.matricule)
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
@@ -0,0 +1,37 @@
+/*
+    ===================================================
+    Migration Version : 010
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Teachers and the subjects they
+                        teach
+    ===================================================
+*/
+
+CREATE TABLE teachers (
+/*
+    The teacher "name" with surname "surname",
+    reachable at "email" and "phone".
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    name        VARCHAR(60)  NOT NULL,
+    surname     VARCHAR(60)  NOT NULL,
+    email       VARCHAR(120),
+    phone       VARCHAR(30)
+);
+
+CREATE TABLE teaching_assignments (
+/*
+    The subject "subject_code" is taught by the
+    teacher "teacher_id". Subjects belong to a
+    class, so this also assigns the teacher to
+    the class.
+*/
+    subject_code VARCHAR(60) NOT NULL PRIMARY KEY,
+    teacher_id   INTEGER     NOT NULL,
+
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON DELETE CASCADE ON UPDATE CASCADE,
+    FOREIGN KEY (teacher_id) REFERENCES teachers(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_teaching_assignments_teacher ON teaching_assignments(teacher_id);
@@ -0,0 +1,115 @@
+/*
+    ===================================================
+    Migration Version : 011
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Enrolment of the students in a
+                        class for each academic year,
+                        notes attached to an enrolment
+    ===================================================
+*/
+
+CREATE TABLE enrolments (
+/*
+    The student "matricule" enrolled in the class
+    "class" for the academic year "academic_year_id".
+    "status" tells whether the student is still
+    enrolled, was transferred to another class, left
+    the school or graduated. "decision" is the
+    end-of-year decision taken on the enrolment.
+*/
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    matricule        VARCHAR(255) NOT NULL,
+    class            VARCHAR(20)  NOT NULL,
+    academic_year_id INTEGER      NOT NULL,
+    status           VARCHAR(12)  NOT NULL DEFAULT 'enrolled'
+                     CHECK (status IN ('enrolled', 'transferred', 'left', 'graduated')),
+    decision         VARCHAR(10)  CHECK (decision IN ('admis', 'redouble', 'exclu')),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE,
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE,
+    FOREIGN KEY (academic_year_id) REFERENCES academic_years(id)
+);
+
+CREATE INDEX idx_enrolments_class_year ON enrolments(class, academic_year_id);
+CREATE UNIQUE INDEX idx_enrolments_active ON enrolments(matricule, academic_year_id) WHERE status = 'enrolled';
+
+-- Current students are enrolled in their class for the current year and
+-- for every year they have notes in
+INSERT INTO enrolments (matricule, class, academic_year_id)
+SELECT matricule, class, id FROM students, academic_years WHERE is_current = 1
+UNION
+SELECT DISTINCT n.matricule, st.class, p.academic_year_id
+FROM notes n
+JOIN students st ON st.matricule = n.matricule
+JOIN periods p ON p.id = n.period_id;
+
+CREATE TRIGGER enrolments_student_insert
+AFTER INSERT ON students
+BEGIN
+    INSERT INTO enrolments (matricule, class, academic_year_id)
+    SELECT NEW.matricule, NEW.class, id FROM academic_years WHERE is_current = 1;
+END;
+
+ALTER TABLE notes ADD COLUMN enrolment_id INTEGER REFERENCES enrolments(id);
+
+CREATE INDEX idx_notes_enrolment ON notes(enrolment_id);
+
+-- Notes belong to the enrolment of the student in the year of their
+-- period, the current year for notes without a period
+UPDATE notes SET enrolment_id = (
+    SELECT e.id FROM enrolments e
+    WHERE e.matricule = notes.matricule
+      AND e.academic_year_id = COALESCE(
+          (SELECT academic_year_id FROM periods WHERE id = notes.period_id),
+          (SELECT id FROM academic_years WHERE is_current = 1))
+    ORDER BY e.status = 'enrolled' DESC, e.id DESC
+    LIMIT 1
+);
+
+CREATE TRIGGER notes_enrolment_insert
+AFTER INSERT ON notes
+WHEN NEW.enrolment_id IS NULL
+BEGIN
+    UPDATE notes SET enrolment_id = (
+        SELECT e.id FROM enrolments e
+        WHERE e.matricule = NEW.matricule
+          AND e.academic_year_id = COALESCE(
+              (SELECT academic_year_id FROM periods WHERE id = NEW.period_id),
+              (SELECT id FROM academic_years WHERE is_current = 1))
+        ORDER BY e.status = 'enrolled' DESC, e.id DESC
+        LIMIT 1
+    )
+    WHERE id = NEW.id;
+END;
+
+-- A note is checked against the class of the student in the year of the
+-- note, which differs from their current class after a promotion
+DROP TRIGGER notes_class_check_insert;
+DROP TRIGGER notes_class_check_update;
+
+CREATE TRIGGER notes_class_check_insert
+BEFORE INSERT ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> COALESCE(
+      (SELECT e.class FROM enrolments e
+       WHERE e.matricule = NEW.matricule
+         AND e.academic_year_id = COALESCE(
+             (SELECT academic_year_id FROM periods WHERE id = NEW.period_id),
+             (SELECT id FROM academic_years WHERE is_current = 1))
+       ORDER BY e.status = 'enrolled' DESC, e.id DESC
+       LIMIT 1),
+      (SELECT class FROM students WHERE matricule = NEW.matricule))
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
+
+CREATE TRIGGER notes_class_check_update
+BEFORE UPDATE OF matricule, subject_code ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> COALESCE(
+      (SELECT class FROM enrolments WHERE id = NEW.enrolment_id AND matricule = NEW.matricule),
+      (SELECT class FROM students WHERE matricule = NEW.matricule))
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
@@ -0,0 +1,37 @@
+/*
+    ===================================================
+    Migration Version : 012
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Absences and lateness of the
+                        students, alert thresholds
+    ===================================================
+*/
+
+CREATE TABLE absences (
+/*
+    The absence or lateness ("kind") of the student
+    "matricule" on "date", optionally during a class
+    hour of the subject "subject_code". "duration"
+    is in hours for an absence and in minutes for a
+    lateness. "justified" tells whether a reason
+    "reason" was accepted.
+*/
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    matricule    VARCHAR(255) NOT NULL,
+    date         DATE         NOT NULL CHECK (date(date) IS date),
+    kind         VARCHAR(10)  NOT NULL CHECK (kind IN ('absence', 'retard')),
+    subject_code VARCHAR(60),
+    duration     INTEGER      NOT NULL DEFAULT 1 CHECK (duration > 0),
+    justified    BOOLEAN      NOT NULL DEFAULT 0,
+    reason       VARCHAR(255),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE,
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON UPDATE CASCADE
+);
+
+CREATE INDEX idx_absences_student_date ON absences(matricule, date);
+
+INSERT INTO settings (key, value) VALUES
+    ('attendance.max_unjustified_hours', '10'),
+    ('attendance.max_lateness', '5');
@@ -0,0 +1,45 @@
+/*
+    ===================================================
+    Migration Version : 013
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Extended student profiles and
+                        the guardians of the students
+    ===================================================
+*/
+
+CREATE TABLE student_profiles (
+/*
+    Civil status and contact details of the student
+    "matricule": born on "birth_date" in
+    "birth_place", gender "gender" ('M' or 'F'),
+    living at "address". "photo_path" points to an
+    identity photo on disk.
+*/
+    matricule   VARCHAR(255) NOT NULL PRIMARY KEY,
+    birth_date  DATE         CHECK (birth_date IS NULL OR date(birth_date) IS birth_date),
+    birth_place VARCHAR(120),
+    gender      CHAR(1)      CHECK (gender IN ('M', 'F')),
+    photo_path  VARCHAR(255),
+    address     VARCHAR(255),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE guardians (
+/*
+    The guardian "name" of the student "matricule"
+    ("relation": père, mère, tuteur...), reachable
+    at "phone" and "email".
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    matricule   VARCHAR(255) NOT NULL,
+    name        VARCHAR(120) NOT NULL,
+    relation    VARCHAR(30)  NOT NULL,
+    phone       VARCHAR(30),
+    email       VARCHAR(120),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE INDEX idx_guardians_student ON guardians(matricule);
@@ -0,0 +1,22 @@
+/*
+    ===================================================
+    Migration Version : 014
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Sequences of the generated
+                        matricules and their pattern
+    ===================================================
+*/
+
+CREATE TABLE matricule_sequences (
+/*
+    Last sequence number "last_value" handed out for
+    the matricules sharing the prefix "scope" (the
+    pattern with its year and level filled in).
+*/
+    scope       VARCHAR(255) NOT NULL PRIMARY KEY,
+    last_value  INTEGER      NOT NULL DEFAULT 0
+);
+
+INSERT INTO settings (key, value) VALUES
+    ('students.matricule_pattern', 'STU-{LEVEL}-{SEQ:3}');
@@ -0,0 +1,14 @@
+/*
+    ===================================================
+    Migration Version : 015
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Thresholds of the early-warning
+                        detection of struggling students
+    ===================================================
+*/
+
+INSERT INTO settings (key, value) VALUES
+    ('warnings.max_drop', '2'),
+    ('warnings.core_failures', '2'),
+    ('warnings.outlier_z_score', '1.5');
@@ -0,0 +1,52 @@
+/*
+    ===================================================
+    Migration Version : 016
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Moderations of the notes of an
+                        evaluation or a subject, with
+                        the original values to revert
+    ===================================================
+*/
+
+CREATE TABLE moderations (
+/*
+    The transformation "description" (bonus, linear
+    rescaling or curve) applied by "applied_by" at
+    "applied_at" to the notes of the subject
+    "subject_code", restricted to the evaluation
+    "evaluation_id" or to the period "period_id".
+    "reverted_at" is set once the original values
+    were restored.
+*/
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject_code  VARCHAR(60)  NOT NULL,
+    evaluation_id INTEGER,
+    period_id     INTEGER,
+    description   VARCHAR(120) NOT NULL,
+    applied_by    VARCHAR(25),
+    applied_at    DATETIME     NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    reverted_at   DATETIME,
+
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON UPDATE CASCADE,
+    FOREIGN KEY (evaluation_id) REFERENCES evaluations(id),
+    FOREIGN KEY (period_id) REFERENCES periods(id)
+);
+
+CREATE TABLE moderated_notes (
+/*
+    The note "note_id" changed by the moderation
+    "moderation_id" from "original_value" to
+    "moderated_value".
+*/
+    moderation_id   INTEGER NOT NULL,
+    note_id         INTEGER NOT NULL,
+    original_value  REAL    NOT NULL,
+    moderated_value REAL    NOT NULL,
+
+    PRIMARY KEY (moderation_id, note_id),
+    FOREIGN KEY (moderation_id) REFERENCES moderations(id) ON DELETE CASCADE,
+    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_moderations_subject ON moderations(subject_code);
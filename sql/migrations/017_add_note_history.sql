@@ -0,0 +1,55 @@
+/*
+    ===================================================
+    Migration Version : 017
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Versioned history of the notes,
+                        with who changed them and why
+    ===================================================
+*/
+
+CREATE TABLE note_history (
+/*
+    The version "version" of the note "note_id",
+    whose value went from "previous_value" (NULL when
+    the note was recorded) to "new_value", changed by
+    "changed_by" at "changed_at" (NULL when unknown) for
+    "reason". The history outlives the note: note ids are
+    never reused, so rows are kept once a note is purged.
+*/
+    id             INTEGER PRIMARY KEY AUTOINCREMENT,
+    note_id        INTEGER      NOT NULL,
+    version        INTEGER      NOT NULL CHECK (version > 0),
+    previous_value REAL,
+    new_value      REAL         NOT NULL,
+    changed_by     VARCHAR(25),
+    changed_at     DATETIME     DEFAULT CURRENT_TIMESTAMP,
+    reason         VARCHAR(255),
+
+    UNIQUE (note_id, version)
+);
+
+-- Notes recorded so far start their history here, on an unknown date
+INSERT INTO note_history (note_id, version, new_value, changed_at)
+SELECT id, 1, value, NULL FROM notes;
+
+CREATE TRIGGER note_history_insert
+AFTER INSERT ON notes
+BEGIN
+    INSERT INTO note_history (note_id, version, new_value) VALUES (NEW.id, 1, NEW.value);
+END;
+
+CREATE TRIGGER note_history_update
+AFTER UPDATE OF value ON notes
+WHEN NEW.value IS NOT OLD.value
+BEGIN
+    INSERT INTO note_history (note_id, version, previous_value, new_value)
+    VALUES (
+        NEW.id,
+        (SELECT COALESCE(MAX(version), 0) + 1 FROM note_history WHERE note_id = NEW.id),
+        OLD.value,
+        NEW.value
+    );
+END;
+
+INSERT INTO settings (key, value) VALUES ('notes.free_edit_days', '7');
@@ -0,0 +1,89 @@
+/*
+    ===================================================
+    Migration Version : 018
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Super admins, locks of the notes
+                        of a closed period and audit log
+    ===================================================
+*/
+
+ALTER TABLE admins ADD COLUMN is_super_admin BOOLEAN NOT NULL DEFAULT 0;
+
+UPDATE admins SET is_super_admin = 1 WHERE login = 'admin';
+
+CREATE TABLE period_locks (
+/*
+    The notes of the period "period_id" locked by
+    "locked_by" at "locked_at": all of them, those of
+    the class "class" or those of the subject
+    "subject_code".
+*/
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    period_id    INTEGER     NOT NULL,
+    class        VARCHAR(20),
+    subject_code VARCHAR(60),
+    locked_by    VARCHAR(25),
+    locked_at    DATETIME    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (class IS NULL OR subject_code IS NULL),
+    FOREIGN KEY (period_id) REFERENCES periods(id) ON DELETE CASCADE,
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE,
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON UPDATE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_period_locks_scope
+    ON period_locks(period_id, COALESCE(class, ''), COALESCE(subject_code, ''));
+
+CREATE TABLE audit_log (
+/*
+    The action "action" (lock or reopen) performed
+    by "performed_by" at "performed_at" on "details",
+    with its "justification".
+*/
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    action        VARCHAR(10)  NOT NULL CHECK (action IN ('lock', 'reopen')),
+    details       VARCHAR(255) NOT NULL,
+    justification VARCHAR(255),
+    performed_by  VARCHAR(25),
+    performed_at  DATETIME     NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER notes_lock_insert
+BEFORE INSERT ON notes
+WHEN EXISTS (
+    SELECT 1 FROM period_locks l
+    WHERE l.period_id = NEW.period_id
+      AND (l.subject_code IS NULL OR l.subject_code = NEW.subject_code)
+      AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = NEW.subject_code))
+)
+BEGIN
+    SELECT RAISE(ABORT, 'the period is locked');
+END;
+
+CREATE TRIGGER notes_lock_update
+BEFORE UPDATE OF value, matricule, subject_code, period_id, evaluation_id ON notes
+WHEN EXISTS (
+    SELECT 1 FROM period_locks l
+    WHERE (l.period_id = OLD.period_id
+           AND (l.subject_code IS NULL OR l.subject_code = OLD.subject_code)
+           AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = OLD.subject_code)))
+       OR (l.period_id = NEW.period_id
+           AND (l.subject_code IS NULL OR l.subject_code = NEW.subject_code)
+           AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = NEW.subject_code)))
+)
+BEGIN
+    SELECT RAISE(ABORT, 'the period is locked');
+END;
+
+CREATE TRIGGER notes_lock_delete
+BEFORE DELETE ON notes
+WHEN EXISTS (
+    SELECT 1 FROM period_locks l
+    WHERE l.period_id = OLD.period_id
+      AND (l.subject_code IS NULL OR l.subject_code = OLD.subject_code)
+      AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = OLD.subject_code))
+)
+BEGIN
+    SELECT RAISE(ABORT, 'the period is locked');
+END;
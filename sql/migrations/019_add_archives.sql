@@ -0,0 +1,19 @@
+/*
+    ===================================================
+    Migration Version : 019
+    Created On        : 2026-10-19
+    Author            : Strife-Cyber
+    Description       : Archiving of students, subjects
+                        and notes instead of deleting
+                        them, purged after a retention
+                        period
+    ===================================================
+*/
+
+-- "deleted_at" is set when the row is archived, NULL while it is active
+ALTER TABLE students ADD COLUMN deleted_at DATETIME;
+ALTER TABLE subjects ADD COLUMN deleted_at DATETIME;
+ALTER TABLE notes ADD COLUMN deleted_at DATETIME;
+
+-- Days an archived record is kept before it can be purged
+INSERT INTO settings (key, value) VALUES ('archives.retention_days', '365');
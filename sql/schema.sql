@@ -8,47 +8,582 @@
     ===================================================
 */
 
+CREATE TABLE academic_years (
+/*
+    The school year "label" (e.g. 2025-2026) running
+    from "start_date" to "end_date" and divided into
+    terms of kind "period_kind" (trimestre or semestre).
+    Only one year is the current one.
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    label       VARCHAR(20) NOT NULL UNIQUE,
+    start_date  DATE        NOT NULL CHECK (date(start_date) = start_date),
+    end_date    DATE        NOT NULL CHECK (date(end_date) = end_date),
+    period_kind VARCHAR(10) NOT NULL CHECK (period_kind IN ('trimestre', 'semestre')),
+    is_current  BOOLEAN     NOT NULL DEFAULT 0,
+
+    CHECK (start_date < end_date)
+);
+
+CREATE TABLE periods (
+/*
+    The term number "number" of the academic year
+    "academic_year_id", from "start_date" to "end_date".
+*/
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    academic_year_id INTEGER NOT NULL,
+    number           INTEGER NOT NULL CHECK (number > 0),
+    start_date       DATE    NOT NULL CHECK (date(start_date) = start_date),
+    end_date         DATE    NOT NULL CHECK (date(end_date) = end_date),
+
+    CHECK (start_date < end_date),
+    UNIQUE (academic_year_id, number),
+    FOREIGN KEY (academic_year_id) REFERENCES academic_years(id) ON DELETE CASCADE
+);
+
+CREATE TABLE classes (
+/*
+    The class identified by "code" (e.g. 6ème A) of
+    the level "level" (e.g. 6ème) and section "section"
+    (e.g. A), opened for the academic year
+    "academic_year_id", followed by the head teacher
+    "head_teacher" and holding at most "capacity"
+    students.
+*/
+    code             VARCHAR(20)  NOT NULL PRIMARY KEY,
+    level            VARCHAR(20)  NOT NULL,
+    section          VARCHAR(10),
+    academic_year_id INTEGER,
+    head_teacher     VARCHAR(120),
+    capacity         INTEGER      CHECK (capacity IS NULL OR capacity > 0),
+
+    FOREIGN KEY (academic_year_id) REFERENCES academic_years(id) ON DELETE SET NULL
+);
+
 CREATE TABLE students (
 /*
     The student "name" with surname "surname"
     enrolled in the class "class" and identified
-    by the unique matricule "matricule".
+    by the unique matricule "matricule", archived
+    at "deleted_at".
 */
     matricule   VARCHAR(255) NOT NULL PRIMARY KEY,
     name        VARCHAR(60)  NOT NULL,
     surname     VARCHAR(60)  NOT NULL,
-    class       VARCHAR(20)  NOT NULL
+    class       VARCHAR(20)  NOT NULL,
+    deleted_at  DATETIME,
+
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE
 );
 
 CREATE TABLE subjects (
 /*
     The subject "name" identified by the code "code"
     taught in the class "class" and weighted by
-    the coefficient "coefficient".
+    the coefficient "coefficient" (1 to 255),
+    archived at "deleted_at".
 */
     code        VARCHAR(60)  NOT NULL PRIMARY KEY,
     name        VARCHAR(60)  NOT NULL,
     class       VARCHAR(20)  NOT NULL,
-    coefficient INTEGER      NOT NULL DEFAULT 1
+    coefficient INTEGER      NOT NULL DEFAULT 1 CHECK (coefficient BETWEEN 1 AND 255),
+    deleted_at  DATETIME,
+
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE
+);
+
+CREATE INDEX idx_students_class ON students(class);
+CREATE INDEX idx_subjects_class ON subjects(class);
+
+CREATE TABLE enrolments (
+/*
+    The student "matricule" enrolled in the class
+    "class" for the academic year "academic_year_id".
+    "status" tells whether the student is still
+    enrolled, was transferred to another class, left
+    the school or graduated. "decision" is the
+    end-of-year decision taken on the enrolment.
+*/
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    matricule        VARCHAR(255) NOT NULL,
+    class            VARCHAR(20)  NOT NULL,
+    academic_year_id INTEGER      NOT NULL,
+    status           VARCHAR(12)  NOT NULL DEFAULT 'enrolled'
+                     CHECK (status IN ('enrolled', 'transferred', 'left', 'graduated')),
+    decision         VARCHAR(10)  CHECK (decision IN ('admis', 'redouble', 'exclu')),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE,
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE,
+    FOREIGN KEY (academic_year_id) REFERENCES academic_years(id)
+);
+
+CREATE INDEX idx_enrolments_class_year ON enrolments(class, academic_year_id);
+CREATE UNIQUE INDEX idx_enrolments_active ON enrolments(matricule, academic_year_id) WHERE status = 'enrolled';
+
+CREATE TRIGGER enrolments_student_insert
+AFTER INSERT ON students
+BEGIN
+    INSERT INTO enrolments (matricule, class, academic_year_id)
+    SELECT NEW.matricule, NEW.class, id FROM academic_years WHERE is_current = 1;
+END;
+
+CREATE TABLE teachers (
+/*
+    The teacher "name" with surname "surname",
+    reachable at "email" and "phone".
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    name        VARCHAR(60)  NOT NULL,
+    surname     VARCHAR(60)  NOT NULL,
+    email       VARCHAR(120),
+    phone       VARCHAR(30)
+);
+
+CREATE TABLE teaching_assignments (
+/*
+    The subject "subject_code" is taught by the
+    teacher "teacher_id". Subjects belong to a
+    class, so this also assigns the teacher to
+    the class.
+*/
+    subject_code VARCHAR(60) NOT NULL PRIMARY KEY,
+    teacher_id   INTEGER     NOT NULL,
+
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON DELETE CASCADE ON UPDATE CASCADE,
+    FOREIGN KEY (teacher_id) REFERENCES teachers(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_teaching_assignments_teacher ON teaching_assignments(teacher_id);
+
+CREATE TABLE evaluation_types (
+/*
+    The kind of assessment "label" identified by
+    "code". Within a subject, the average of each
+    type counts for "weight" relative to the other
+    types evaluated. Notes recorded outside any
+    evaluation belong to the default type.
+*/
+    code        VARCHAR(20) NOT NULL PRIMARY KEY,
+    label       VARCHAR(60) NOT NULL,
+    weight      REAL        NOT NULL CHECK (weight > 0),
+    is_default  BOOLEAN     NOT NULL DEFAULT 0
+);
+
+CREATE TABLE evaluations (
+/*
+    The assessment "title" of type "type_code" given
+    in the subject "subject_code" on "date", during
+    the period "period_id". Scores are out of
+    "max_score" and the evaluation counts for
+    "weight" among the evaluations of its type.
+*/
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject_code VARCHAR(60)  NOT NULL,
+    period_id    INTEGER      NOT NULL,
+    type_code    VARCHAR(20)  NOT NULL,
+    title        VARCHAR(100) NOT NULL,
+    date         DATE         NOT NULL CHECK (date(date) = date),
+    max_score    REAL         NOT NULL DEFAULT 20 CHECK (max_score > 0),
+    weight       REAL         NOT NULL DEFAULT 1 CHECK (weight > 0),
+
+    FOREIGN KEY (subject_code) REFERENCES subjects(code),
+    FOREIGN KEY (period_id) REFERENCES periods(id),
+    FOREIGN KEY (type_code) REFERENCES evaluation_types(code)
 );
 
 CREATE TABLE notes (
 /*
-    The grade "value" obtained by the student
-    identified by "matricule" for the subject
-    identified by "subject_code".
+    The grade "value" out of 20 obtained by the
+    student identified by "matricule" for the
+    subject identified by "subject_code", during
+    the enrolment "enrolment_id", archived at
+    "deleted_at".
 */
     id           INTEGER PRIMARY KEY AUTOINCREMENT,
     matricule    VARCHAR(255) NOT NULL,
     subject_code VARCHAR(60)  NOT NULL,
-    value        REAL         NOT NULL,
-    
+    value        REAL         NOT NULL CHECK (typeof(value) IN ('real', 'integer') AND value BETWEEN 0 AND 20),
+    period_id    INTEGER,
+    evaluation_id INTEGER,
+    enrolment_id INTEGER REFERENCES enrolments(id),
+    deleted_at   DATETIME,
+
     FOREIGN KEY (matricule) REFERENCES students(matricule),
-    FOREIGN KEY (subject_code) REFERENCES subjects(code)
+    FOREIGN KEY (subject_code) REFERENCES subjects(code),
+    FOREIGN KEY (period_id) REFERENCES periods(id),
+    FOREIGN KEY (evaluation_id) REFERENCES evaluations(id)
+);
+
+CREATE INDEX idx_notes_period ON notes(period_id);
+CREATE INDEX idx_notes_evaluation ON notes(evaluation_id);
+CREATE INDEX idx_notes_enrolment ON notes(enrolment_id);
+
+CREATE TRIGGER notes_enrolment_insert
+AFTER INSERT ON notes
+WHEN NEW.enrolment_id IS NULL
+BEGIN
+    UPDATE notes SET enrolment_id = (
+        SELECT e.id FROM enrolments e
+        WHERE e.matricule = NEW.matricule
+          AND e.academic_year_id = COALESCE(
+              (SELECT academic_year_id FROM periods WHERE id = NEW.period_id),
+              (SELECT id FROM academic_years WHERE is_current = 1))
+        ORDER BY e.status = 'enrolled' DESC, e.id DESC
+        LIMIT 1
+    )
+    WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER notes_class_check_insert
+BEFORE INSERT ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> COALESCE(
+      (SELECT e.class FROM enrolments e
+       WHERE e.matricule = NEW.matricule
+         AND e.academic_year_id = COALESCE(
+             (SELECT academic_year_id FROM periods WHERE id = NEW.period_id),
+             (SELECT id FROM academic_years WHERE is_current = 1))
+       ORDER BY e.status = 'enrolled' DESC, e.id DESC
+       LIMIT 1),
+      (SELECT class FROM students WHERE matricule = NEW.matricule))
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
+
+CREATE TRIGGER notes_class_check_update
+BEFORE UPDATE OF matricule, subject_code ON notes
+WHEN (SELECT class FROM subjects WHERE code = NEW.subject_code)
+  <> COALESCE(
+      (SELECT class FROM enrolments WHERE id = NEW.enrolment_id AND matricule = NEW.matricule),
+      (SELECT class FROM students WHERE matricule = NEW.matricule))
+BEGIN
+    SELECT RAISE(ABORT, 'subject is not taught in the student''s class');
+END;
+
+CREATE TABLE note_history (
+/*
+    The version "version" of the note "note_id",
+    whose value went from "previous_value" (NULL when
+    the note was recorded) to "new_value", changed by
+    "changed_by" at "changed_at" (NULL when unknown) for
+    "reason". The history outlives the note: note ids are
+    never reused, so rows are kept once a note is purged.
+*/
+    id             INTEGER PRIMARY KEY AUTOINCREMENT,
+    note_id        INTEGER      NOT NULL,
+    version        INTEGER      NOT NULL CHECK (version > 0),
+    previous_value REAL,
+    new_value      REAL         NOT NULL,
+    changed_by     VARCHAR(25),
+    changed_at     DATETIME     DEFAULT CURRENT_TIMESTAMP,
+    reason         VARCHAR(255),
+
+    UNIQUE (note_id, version)
+);
+
+CREATE TRIGGER note_history_insert
+AFTER INSERT ON notes
+BEGIN
+    INSERT INTO note_history (note_id, version, new_value) VALUES (NEW.id, 1, NEW.value);
+END;
+
+CREATE TRIGGER note_history_update
+AFTER UPDATE OF value ON notes
+WHEN NEW.value IS NOT OLD.value
+BEGIN
+    INSERT INTO note_history (note_id, version, previous_value, new_value)
+    VALUES (
+        NEW.id,
+        (SELECT COALESCE(MAX(version), 0) + 1 FROM note_history WHERE note_id = NEW.id),
+        OLD.value,
+        NEW.value
+    );
+END;
+
+CREATE TABLE moderations (
+/*
+    The transformation "description" (bonus, linear
+    rescaling or curve) applied by "applied_by" at
+    "applied_at" to the notes of the subject
+    "subject_code", restricted to the evaluation
+    "evaluation_id" or to the period "period_id".
+    "reverted_at" is set once the original values
+    were restored.
+*/
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject_code  VARCHAR(60)  NOT NULL,
+    evaluation_id INTEGER,
+    period_id     INTEGER,
+    description   VARCHAR(120) NOT NULL,
+    applied_by    VARCHAR(25),
+    applied_at    DATETIME     NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    reverted_at   DATETIME,
+
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON UPDATE CASCADE,
+    FOREIGN KEY (evaluation_id) REFERENCES evaluations(id),
+    FOREIGN KEY (period_id) REFERENCES periods(id)
+);
+
+CREATE TABLE moderated_notes (
+/*
+    The note "note_id" changed by the moderation
+    "moderation_id" from "original_value" to
+    "moderated_value".
+*/
+    moderation_id   INTEGER NOT NULL,
+    note_id         INTEGER NOT NULL,
+    original_value  REAL    NOT NULL,
+    moderated_value REAL    NOT NULL,
+
+    PRIMARY KEY (moderation_id, note_id),
+    FOREIGN KEY (moderation_id) REFERENCES moderations(id) ON DELETE CASCADE,
+    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_moderations_subject ON moderations(subject_code);
+
+CREATE TABLE period_locks (
+/*
+    The notes of the period "period_id" locked by
+    "locked_by" at "locked_at": all of them, those of
+    the class "class" or those of the subject
+    "subject_code".
+*/
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    period_id    INTEGER     NOT NULL,
+    class        VARCHAR(20),
+    subject_code VARCHAR(60),
+    locked_by    VARCHAR(25),
+    locked_at    DATETIME    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (class IS NULL OR subject_code IS NULL),
+    FOREIGN KEY (period_id) REFERENCES periods(id) ON DELETE CASCADE,
+    FOREIGN KEY (class) REFERENCES classes(code) ON UPDATE CASCADE,
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON UPDATE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_period_locks_scope
+    ON period_locks(period_id, COALESCE(class, ''), COALESCE(subject_code, ''));
+
+CREATE TABLE audit_log (
+/*
+    The action "action" (lock or reopen) performed
+    by "performed_by" at "performed_at" on "details",
+    with its "justification".
+*/
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    action        VARCHAR(10)  NOT NULL CHECK (action IN ('lock', 'reopen')),
+    details       VARCHAR(255) NOT NULL,
+    justification VARCHAR(255),
+    performed_by  VARCHAR(25),
+    performed_at  DATETIME     NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER notes_lock_insert
+BEFORE INSERT ON notes
+WHEN EXISTS (
+    SELECT 1 FROM period_locks l
+    WHERE l.period_id = NEW.period_id
+      AND (l.subject_code IS NULL OR l.subject_code = NEW.subject_code)
+      AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = NEW.subject_code))
+)
+BEGIN
+    SELECT RAISE(ABORT, 'the period is locked');
+END;
+
+CREATE TRIGGER notes_lock_update
+BEFORE UPDATE OF value, matricule, subject_code, period_id, evaluation_id ON notes
+WHEN EXISTS (
+    SELECT 1 FROM period_locks l
+    WHERE (l.period_id = OLD.period_id
+           AND (l.subject_code IS NULL OR l.subject_code = OLD.subject_code)
+           AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = OLD.subject_code)))
+       OR (l.period_id = NEW.period_id
+           AND (l.subject_code IS NULL OR l.subject_code = NEW.subject_code)
+           AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = NEW.subject_code)))
+)
+BEGIN
+    SELECT RAISE(ABORT, 'the period is locked');
+END;
+
+CREATE TRIGGER notes_lock_delete
+BEFORE DELETE ON notes
+WHEN EXISTS (
+    SELECT 1 FROM period_locks l
+    WHERE l.period_id = OLD.period_id
+      AND (l.subject_code IS NULL OR l.subject_code = OLD.subject_code)
+      AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = OLD.subject_code))
+)
+BEGIN
+    SELECT RAISE(ABORT, 'the period is locked');
+END;
+
+CREATE TABLE absences (
+/*
+    The absence or lateness ("kind") of the student
+    "matricule" on "date", optionally during a class
+    hour of the subject "subject_code". "duration"
+    is in hours for an absence and in minutes for a
+    lateness. "justified" tells whether a reason
+    "reason" was accepted.
+*/
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    matricule    VARCHAR(255) NOT NULL,
+    date         DATE         NOT NULL CHECK (date(date) IS date),
+    kind         VARCHAR(10)  NOT NULL CHECK (kind IN ('absence', 'retard')),
+    subject_code VARCHAR(60),
+    duration     INTEGER      NOT NULL DEFAULT 1 CHECK (duration > 0),
+    justified    BOOLEAN      NOT NULL DEFAULT 0,
+    reason       VARCHAR(255),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE,
+    FOREIGN KEY (subject_code) REFERENCES subjects(code) ON UPDATE CASCADE
+);
+
+CREATE INDEX idx_absences_student_date ON absences(matricule, date);
+
+CREATE TABLE student_profiles (
+/*
+    Civil status and contact details of the student
+    "matricule": born on "birth_date" in
+    "birth_place", gender "gender" ('M' or 'F'),
+    living at "address". "photo_path" points to an
+    identity photo on disk.
+*/
+    matricule   VARCHAR(255) NOT NULL PRIMARY KEY,
+    birth_date  DATE         CHECK (birth_date IS NULL OR date(birth_date) IS birth_date),
+    birth_place VARCHAR(120),
+    gender      CHAR(1)      CHECK (gender IN ('M', 'F')),
+    photo_path  VARCHAR(255),
+    address     VARCHAR(255),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE guardians (
+/*
+    The guardian "name" of the student "matricule"
+    ("relation": père, mère, tuteur...), reachable
+    at "phone" and "email".
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    matricule   VARCHAR(255) NOT NULL,
+    name        VARCHAR(120) NOT NULL,
+    relation    VARCHAR(30)  NOT NULL,
+    phone       VARCHAR(30),
+    email       VARCHAR(120),
+
+    FOREIGN KEY (matricule) REFERENCES students(matricule) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE INDEX idx_guardians_student ON guardians(matricule);
+
+CREATE TABLE matricule_sequences (
+/*
+    Last sequence number "last_value" handed out for
+    the matricules sharing the prefix "scope" (the
+    pattern with its year and level filled in).
+*/
+    scope       VARCHAR(255) NOT NULL PRIMARY KEY,
+    last_value  INTEGER      NOT NULL DEFAULT 0
 );
 
 CREATE TABLE admins (
     id              INTEGER PRIMARY KEY AUTOINCREMENT,
     login           VARCHAR(25) NOT NULL,
-    password        VARCHAR(25) NOT NULL
+    password        VARCHAR(25) NOT NULL,
+    is_super_admin  BOOLEAN     NOT NULL DEFAULT 0
+);
+
+CREATE TABLE settings (
+/*
+    The configuration entry "key" holding the
+    textual value "value".
+*/
+    key         VARCHAR(60)  NOT NULL PRIMARY KEY,
+    value       VARCHAR(255) NOT NULL
+);
+
+INSERT INTO settings (key, value) VALUES ('grading.missing_notes', 'exclude');
+
+INSERT INTO settings (key, value) VALUES
+    ('grading.scale_min', '0'),
+    ('grading.scale_max', '20'),
+    ('grading.scale_step', 'any');
+
+INSERT INTO settings (key, value) VALUES
+    ('attendance.max_unjustified_hours', '10'),
+    ('attendance.max_lateness', '5');
+
+INSERT INTO settings (key, value) VALUES
+    ('students.matricule_pattern', 'STU-{LEVEL}-{SEQ:3}');
+
+INSERT INTO settings (key, value) VALUES
+    ('warnings.max_drop', '2'),
+    ('warnings.core_failures', '2'),
+    ('warnings.outlier_z_score', '1.5');
+
+INSERT INTO settings (key, value) VALUES ('notes.free_edit_days', '7');
+
+INSERT INTO settings (key, value) VALUES ('archives.retention_days', '365');
+
+CREATE TABLE mention_rules (
+/*
+    The mention "label" granted to averages
+    greater than or equal to "min_average".
+*/
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    label       VARCHAR(30)  NOT NULL UNIQUE,
+    min_average REAL         NOT NULL
 );
+
+CREATE TABLE decision_rules (
+/*
+    Single row holding the thresholds of the
+    end-of-year decision (admis, redouble, exclu).
+*/
+    id                      INTEGER PRIMARY KEY CHECK (id = 1),
+    admission_average       REAL    NOT NULL DEFAULT 10,
+    exclusion_average       REAL    NOT NULL DEFAULT 7,
+    failing_subject_average REAL    NOT NULL DEFAULT 10,
+    max_failed_subjects     INTEGER NOT NULL DEFAULT 3
+);
+
+CREATE TABLE key_subject_rules (
+/*
+    Minimum average "min_average" required in the
+    subjects whose code starts with "subject_prefix".
+*/
+    subject_prefix VARCHAR(60) NOT NULL PRIMARY KEY,
+    min_average    REAL        NOT NULL
+);
+
+INSERT INTO mention_rules (label, min_average) VALUES
+    ('Excellent', 18),
+    ('Très Bien', 16),
+    ('Bien', 14),
+    ('Assez Bien', 12),
+    ('Passable', 10),
+    ('Insuffisant', 0);
+
+INSERT INTO decision_rules (id) VALUES (1);
+
+INSERT INTO key_subject_rules (subject_prefix, min_average) VALUES
+    ('MATH', 8),
+    ('FR', 8);
+
+-- Fixed school year of the test fixtures; migration 005 creates the
+-- year running when it is applied
+INSERT INTO academic_years (label, start_date, end_date, period_kind, is_current)
+VALUES ('2025-2026', '2025-09-01', '2026-07-03', 'trimestre', 1);
+
+INSERT INTO periods (academic_year_id, number, start_date, end_date) VALUES
+    (1, 1, '2025-09-01', '2025-12-19'),
+    (1, 2, '2026-01-05', '2026-03-27'),
+    (1, 3, '2026-04-13', '2026-07-03');
+
+INSERT INTO evaluation_types (code, label, weight, is_default) VALUES
+    ('devoir', 'Devoir', 20, 1),
+    ('interrogation', 'Interrogation', 20, 0),
+    ('composition', 'Composition', 60, 0),
+    ('examen', 'Examen', 60, 0);
@@ -9,6 +9,10 @@
 */
 
 -- Insertion des classes et matiÃ¨res
+INSERT INTO classes (code, level, academic_year_id, capacity)
+SELECT level, level, (SELECT id FROM academic_years WHERE is_current = 1), 30
+FROM (SELECT '6Ã¨me' AS level UNION ALL SELECT '5Ã¨me' UNION ALL SELECT '4Ã¨me' UNION ALL SELECT '3Ã¨me');
+
 -- Classe 6Ã¨me (15 matiÃ¨res)
 INSERT INTO subjects (code, name, class, coefficient) VALUES
 ('FR6', 'FranÃ§ais', '6Ã¨me', 4),
@@ -1705,3 +1709,13 @@ INSERT INTO notes (matricule, subject_code, value) VALUES
 ('STU-3-025', 'LAT3', 6.9), ('STU-3-025', 'LAT3', 5.5), ('STU-3-025', 'LAT3', 4.7), ('STU-3-025', 'LAT3', 3.6), ('STU-3-025', 'LAT3', 4.8),
 ('STU-3-025', 'INFO3', 6.2), ('STU-3-025', 'INFO3', 3.2), ('STU-3-025', 'INFO3', 3.9), ('STU-3-025', 'INFO3', 3.0), ('STU-3-025', 'INFO3', 3.9),
 ('STU-3-025', 'EDM3', 3.9), ('STU-3-025', 'EDM3', 5.5), ('STU-3-025', 'EDM3', 4.0), ('STU-3-025', 'EDM3', 5.9), ('STU-3-025', 'EDM3', 6.8);
+
+-- Rattachement des notes aux trimestres : 2 notes au 1er, 2 au 2e, 1 au 3e
+UPDATE notes SET period_id = p.id
+FROM (
+    SELECT id, (ROW_NUMBER() OVER (PARTITION BY matricule, subject_code ORDER BY id) - 1) / 2 + 1 AS number
+    FROM notes
+) AS ranked
+JOIN periods p ON p.number = ranked.number
+JOIN academic_years y ON y.id = p.academic_year_id AND y.is_current = 1
+WHERE notes.id = ranked.id;
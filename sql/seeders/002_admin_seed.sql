@@ -19,3 +19,6 @@ INSERT OR IGNORE INTO admins(login, password) VALUES
     ('admin7', 'password7'),
     ('admin8', 'password8'),
     ('admin9', 'password9');
+
+-- Seul le compte admin peut verrouiller et rouvrir les périodes
+UPDATE admins SET is_super_admin = 1 WHERE login = 'admin';
@@ -4,16 +4,46 @@ use rusqlite::Connection;
 use crate::db::repositories::admin_repository::{AdminSession, Admin};
 use crate::db::repositories::student_repository::StudentRepository;
 use crate::db::repositories::subject_repository::SubjectRepository;
-use crate::db::repositories::note_repository::NoteRepository;
+use crate::db::repositories::note_repository::{NoteRepository, DEFAULT_FREE_EDIT_DAYS, FREE_EDIT_DAYS_SETTING};
+use crate::db::repositories::rules_repository::RulesRepository;
+use crate::db::repositories::period_repository::PeriodRepository;
+use crate::db::repositories::evaluation_repository::EvaluationRepository;
+use crate::db::repositories::class_repository::ClassRepository;
+use crate::db::repositories::teacher_repository::TeacherRepository;
+use crate::db::repositories::enrolment_repository::EnrolmentRepository;
+use crate::db::repositories::absence_repository::AbsenceRepository;
+use crate::db::repositories::profile_repository::ProfileRepository;
+use crate::db::repositories::moderation_repository::ModerationRepository;
+use crate::db::repositories::lock_repository::LockRepository;
+use crate::db::repositories::audit_repository::AuditRepository;
+use crate::db::repositories::archive_repository::{ArchiveRepository, DEFAULT_RETENTION_DAYS, RETENTION_DAYS_SETTING};
+use crate::db::repositories::deletion_repository::DeletionRepository;
+use crate::db::repositories::settings_repository::SettingsRepository;
+use crate::grading::service::GradingService;
+use crate::students::matricule::{MatriculePattern, MATRICULE_PATTERN_SETTING};
 
 /// Application state
 pub struct App {
-    pub conn: Rc<RefCell<Connection>>,
     pub current_admin: Option<Admin>,
     pub admin_session: AdminSession,
     pub student_repo: StudentRepository,
     pub subject_repo: SubjectRepository,
     pub note_repo: NoteRepository,
+    pub rules_repo: RulesRepository,
+    pub period_repo: PeriodRepository,
+    pub evaluation_repo: EvaluationRepository,
+    pub class_repo: ClassRepository,
+    pub teacher_repo: TeacherRepository,
+    pub enrolment_repo: EnrolmentRepository,
+    pub absence_repo: AbsenceRepository,
+    pub profile_repo: ProfileRepository,
+    pub moderation_repo: ModerationRepository,
+    pub lock_repo: LockRepository,
+    pub audit_repo: AuditRepository,
+    pub archive_repo: ArchiveRepository,
+    pub deletion_repo: DeletionRepository,
+    pub settings_repo: SettingsRepository,
+    pub grading_service: GradingService,
 }
 
 impl App {
@@ -22,14 +52,43 @@ impl App {
         let student_repo = StudentRepository::new(conn.clone());
         let subject_repo = SubjectRepository::new(conn.clone());
         let note_repo = NoteRepository::new(conn.clone());
+        let rules_repo = RulesRepository::new(conn.clone());
+        let period_repo = PeriodRepository::new(conn.clone());
+        let evaluation_repo = EvaluationRepository::new(conn.clone());
+        let class_repo = ClassRepository::new(conn.clone());
+        let teacher_repo = TeacherRepository::new(conn.clone());
+        let enrolment_repo = EnrolmentRepository::new(conn.clone());
+        let absence_repo = AbsenceRepository::new(conn.clone());
+        let profile_repo = ProfileRepository::new(conn.clone());
+        let moderation_repo = ModerationRepository::new(conn.clone());
+        let lock_repo = LockRepository::new(conn.clone());
+        let audit_repo = AuditRepository::new(conn.clone());
+        let archive_repo = ArchiveRepository::new(conn.clone());
+        let deletion_repo = DeletionRepository::new(conn.clone());
+        let settings_repo = SettingsRepository::new(conn.clone());
+        let grading_service = GradingService::new(conn.clone());
 
         Self {
-            conn,
             current_admin: None,
             admin_session,
             student_repo,
             subject_repo,
             note_repo,
+            rules_repo,
+            period_repo,
+            evaluation_repo,
+            class_repo,
+            teacher_repo,
+            enrolment_repo,
+            absence_repo,
+            profile_repo,
+            moderation_repo,
+            lock_repo,
+            audit_repo,
+            archive_repo,
+            deletion_repo,
+            settings_repo,
+            grading_service,
         }
     }
 
@@ -50,5 +109,49 @@ impl App {
     pub fn logout(&mut self) {
         self.current_admin = None;
     }
+
+    /// Pattern of the generated matricules, the default one if the setting is invalid
+    pub fn matricule_pattern(&self) -> rusqlite::Result<MatriculePattern> {
+        Ok(self
+            .settings_repo
+            .get(MATRICULE_PATTERN_SETTING)?
+            .and_then(|pattern| MatriculePattern::parse(&pattern).ok())
+            .unwrap_or_default())
+    }
+
+    pub fn set_matricule_pattern(&self, pattern: &MatriculePattern) -> rusqlite::Result<()> {
+        self.settings_repo.set(MATRICULE_PATTERN_SETTING, pattern.as_str())
+    }
+
+    /// Days during which a note can be changed without giving a reason
+    pub fn free_edit_days(&self) -> rusqlite::Result<u32> {
+        Ok(self
+            .settings_repo
+            .get(FREE_EDIT_DAYS_SETTING)?
+            .and_then(|days| days.parse().ok())
+            .unwrap_or(DEFAULT_FREE_EDIT_DAYS))
+    }
+
+    pub fn set_free_edit_days(&self, days: u32) -> rusqlite::Result<()> {
+        self.settings_repo.set(FREE_EDIT_DAYS_SETTING, &days.to_string())
+    }
+
+    /// Days an archived record is kept before it can be purged
+    pub fn retention_days(&self) -> rusqlite::Result<u32> {
+        Ok(self
+            .settings_repo
+            .get(RETENTION_DAYS_SETTING)?
+            .and_then(|days| days.parse().ok())
+            .unwrap_or(DEFAULT_RETENTION_DAYS))
+    }
+
+    pub fn set_retention_days(&self, days: u32) -> rusqlite::Result<()> {
+        self.settings_repo.set(RETENTION_DAYS_SETTING, &days.to_string())
+    }
+
+    /// Login of the admin signed in, to sign their changes
+    pub fn author(&self) -> Option<String> {
+        self.current_admin.as_ref().map(|admin| admin.login.clone())
+    }
 }
 
@@ -0,0 +1,254 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::{optional, periods, prompt};
+use crate::db::repositories::absence_repository::{Absence, AbsenceKind};
+use crate::db::repositories::enrolment_repository::ENROLLED_IN_CURRENT_YEAR;
+use crate::db::repositories::repository::Repository;
+use crate::views;
+
+/// Absences sub-menu
+pub fn show_absences_menu() {
+    println!("\n=== Absences et retards ===");
+    println!("1.  Faire l'appel d'une classe");
+    println!("2.  Enregistrer une absence ou un retard");
+    println!("3.  Justifier une absence ou un retard");
+    println!("4.  Voir les absences d'un étudiant");
+    println!("5.  Étudiants dépassant les seuils d'alerte");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Whether `input` looks like AAAA-MM-JJ; the database checks the date itself
+fn is_date(input: &str) -> bool {
+    input.len() == 10
+        && input
+            .chars()
+            .enumerate()
+            .all(|(index, c)| if index == 4 || index == 7 { c == '-' } else { c.is_ascii_digit() })
+}
+
+fn prompt_date(label: &str) -> io::Result<Option<String>> {
+    let date = prompt(label)?;
+    if is_date(&date) {
+        Ok(Some(date))
+    } else {
+        println!("\n✗ Date invalide (AAAA-MM-JJ).\n");
+        Ok(None)
+    }
+}
+
+/// Positive number, `default` for an empty answer
+fn prompt_duration(label: &str, default: u32) -> io::Result<Option<u32>> {
+    let input = prompt(label)?;
+    if input.is_empty() {
+        return Ok(Some(default));
+    }
+    match input.parse::<u32>() {
+        Ok(duration) if duration > 0 => Ok(Some(duration)),
+        _ => {
+            println!("\n✗ Durée invalide.\n");
+            Ok(None)
+        }
+    }
+}
+
+/// Handle absences sub-menu commands
+pub fn handle_absences_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let class = prompt("\nClasse: ")?;
+            let students = app.student_repo.filter(
+                &format!("{} ORDER BY name, surname", ENROLLED_IN_CURRENT_YEAR),
+                &[&class],
+            )?;
+            if students.is_empty() {
+                println!("\n✗ Aucun étudiant inscrit dans cette classe.\n");
+                return Ok(());
+            }
+
+            let Some(date) = prompt_date("Date (AAAA-MM-JJ): ")? else {
+                return Ok(());
+            };
+            let subject_code = optional(prompt("Matière (vide = journée entière): ")?);
+            if let Some(code) = &subject_code
+                && app.subject_repo.filter("code = ?1 AND class = ?2", &[code, &class])?.is_empty()
+            {
+                println!("\n✗ Cette matière n'est pas enseignée en {}.\n", class);
+                return Ok(());
+            }
+            let Some(hours) = prompt_duration("Durée de la séance en heures (vide = 1): ", 1)? else {
+                return Ok(());
+            };
+
+            println!("\nPour chaque étudiant : vide = présent, a = absent, r = en retard");
+            let mut absences = Vec::new();
+            for student in &students {
+                let answer = prompt(&format!("{} {} {}: ", student.matricule, student.name, student.surname))?;
+                let (kind, duration) = match answer.to_lowercase().as_str() {
+                    "" => continue,
+                    "a" => (AbsenceKind::Absence, hours),
+                    "r" => match prompt_duration("  Minutes de retard (vide = 5): ", 5)? {
+                        Some(minutes) => (AbsenceKind::Lateness, minutes),
+                        None => return Ok(()),
+                    },
+                    _ => {
+                        println!("\n✗ Réponse invalide, appel annulé.\n");
+                        return Ok(());
+                    }
+                };
+
+                absences.push(Absence {
+                    id: 0,
+                    matricule: student.matricule.clone(),
+                    date: date.clone(),
+                    kind,
+                    subject_code: subject_code.clone(),
+                    duration,
+                    justified: false,
+                    reason: None,
+                });
+            }
+
+            match app.absence_repo.record_roll_call(&absences) {
+                Ok(()) => println!(
+                    "\n✓ Appel enregistré : {} présents, {} absences ou retards.\n",
+                    students.len() - absences.len(),
+                    absences.len()
+                ),
+                Err(e) => println!("\n✗ Appel invalide: {}\n", e),
+            }
+        }
+        "2" => {
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+            let Some(student) = app.student_repo.get(&matricule)? else {
+                println!("\n✗ Étudiant non trouvé.\n");
+                return Ok(());
+            };
+            let Some(date) = prompt_date("Date (AAAA-MM-JJ): ")? else {
+                return Ok(());
+            };
+            let kind = match prompt("Type (1. absence, 2. retard): ")?.as_str() {
+                "1" => AbsenceKind::Absence,
+                "2" => AbsenceKind::Lateness,
+                _ => {
+                    println!("\n✗ Option invalide.\n");
+                    return Ok(());
+                }
+            };
+            let subject_code = optional(prompt("Matière (vide = journée entière): ")?);
+            let duration = match kind {
+                AbsenceKind::Absence => prompt_duration("Durée en heures (vide = 1): ", 1)?,
+                AbsenceKind::Lateness => prompt_duration("Minutes de retard (vide = 5): ", 5)?,
+            };
+            let Some(duration) = duration else {
+                return Ok(());
+            };
+            let justified = prompt("Justifiée ? (o/N): ")?.eq_ignore_ascii_case("o");
+            let reason = if justified { optional(prompt("Motif: ")?) } else { None };
+
+            let absence = Absence {
+                id: 0,
+                matricule: student.matricule,
+                date,
+                kind,
+                subject_code,
+                duration,
+                justified,
+                reason,
+            };
+            match app.absence_repo.create(&absence) {
+                Ok(()) => println!("\n✓ {} enregistrée.\n", absence.kind),
+                Err(e) => println!("\n✗ Absence invalide: {}\n", e),
+            }
+        }
+        "3" => {
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+            let unjustified = app.absence_repo.filter(
+                "matricule = ?1 AND NOT justified ORDER BY date, id",
+                &[&matricule],
+            )?;
+            if unjustified.is_empty() {
+                println!("\n✓ Aucune absence non justifiée.\n");
+                return Ok(());
+            }
+
+            views::display_absences(&unjustified);
+            let Ok(id) = prompt("\nID de l'absence à justifier: ")?.parse::<i64>() else {
+                println!("\n✗ ID invalide.\n");
+                return Ok(());
+            };
+            if !unjustified.iter().any(|absence| absence.id == id) {
+                println!("\n✗ Absence non trouvée.\n");
+                return Ok(());
+            }
+            let reason = optional(prompt("Motif: ")?);
+
+            app.absence_repo.justify(id, reason.as_deref())?;
+            println!("\n✓ Absence justifiée.\n");
+        }
+        "4" => {
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+            let Some(student) = app.student_repo.get(&matricule)? else {
+                println!("\n✗ Étudiant non trouvé.\n");
+                return Ok(());
+            };
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+
+            let (from, to) = choice.dates();
+            let absences = app.absence_repo.get_by_student(&student.matricule, from, to)?;
+            let totals = app.absence_repo.get_totals(from, to)?.remove(&student.matricule).unwrap_or_default();
+
+            println!("\n=== Absences de {} {} — {} ===", student.name, student.surname, choice.label());
+            if absences.is_empty() {
+                println!("Aucune absence ni retard.\n");
+                return Ok(());
+            }
+            views::display_absences(&absences);
+            println!("Total : {}", totals);
+            for alert in app.grading_service.attendance_thresholds()?.alerts(&totals) {
+                println!("⚠ {}", alert);
+            }
+            println!();
+        }
+        "5" => {
+            let class = prompt("\nClasse: ")?;
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+
+            let students = app.student_repo.filter(
+                &format!("{} ORDER BY name, surname", ENROLLED_IN_CURRENT_YEAR),
+                &[&class],
+            )?;
+            let (from, to) = choice.dates();
+            let mut totals = app.absence_repo.get_totals(from, to)?;
+            let thresholds = app.grading_service.attendance_thresholds()?;
+
+            let alerts: Vec<_> = students
+                .into_iter()
+                .filter_map(|student| {
+                    let student_totals = totals.remove(&student.matricule).unwrap_or_default();
+                    let reasons = thresholds.alerts(&student_totals);
+                    (!reasons.is_empty()).then_some((student, student_totals, reasons))
+                })
+                .collect();
+
+            if alerts.is_empty() {
+                println!("\n✓ Aucun étudiant de {} ne dépasse les seuils ({}).\n", class, thresholds);
+            } else {
+                println!("\n=== Alertes d'assiduité en {} — {} ===", class, choice.label());
+                views::display_attendance_alerts(&alerts);
+                println!();
+            }
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
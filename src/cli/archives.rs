@@ -0,0 +1,204 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::prompt;
+use crate::db::repositories::archive_repository::Archivable;
+use crate::db::repositories::deletion_repository::{DeleteError, DeleteOutcome, DeletePolicy};
+use crate::db::repositories::repository::Repository;
+use crate::views;
+
+/// Archives sub-menu
+pub fn show_archives_menu() {
+    println!("\n=== Archives ===");
+    println!("1.  Archiver un étudiant");
+    println!("2.  Archiver une matière");
+    println!("3.  Archiver une note");
+    println!("4.  Voir les éléments archivés");
+    println!("5.  Restaurer un élément archivé");
+    println!("6.  Purger les archives anciennes");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Display an archived record with its notes, archived or not
+fn display_archived(app: &App, record: &Archivable) -> Result<(), Box<dyn std::error::Error>> {
+    let notes = app.note_repo.with_archived();
+    match record {
+        Archivable::Student(matricule) => {
+            if let Some(student) = app.student_repo.with_archived().get(matricule)? {
+                views::display_students(std::slice::from_ref(&student));
+            }
+            views::display_notes(&notes.get_by_student(matricule, None)?);
+        }
+        Archivable::Subject(code) => {
+            if let Some(subject) = app.subject_repo.with_archived().get(code)? {
+                views::display_subjects(std::slice::from_ref(&subject), &app.teacher_repo.get_subject_teachers()?);
+            }
+            views::display_notes(&notes.get_by_subject(code, None)?);
+        }
+        Archivable::Note(id) => {
+            if let Some(note) = notes.get(&id.to_string())? {
+                views::display_notes(std::slice::from_ref(&note));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Archive `record` once confirmed
+fn archive(app: &App, record: Archivable) -> Result<(), Box<dyn std::error::Error>> {
+    if !prompt(&format!("Archiver {} ? (o/N): ", record))?.eq_ignore_ascii_case("o") {
+        return Ok(());
+    }
+
+    match app.archive_repo.archive(&record) {
+        Ok(notes) => println!("\n✓ Archivé : {} ({} note(s)).\n", record, notes),
+        Err(e) => println!("\n✗ Archivage impossible: {}\n", e),
+    }
+    Ok(())
+}
+
+/// Delete a student or subject, asking what becomes of its notes
+pub fn delete(app: &App, record: Archivable) -> Result<(), Box<dyn std::error::Error>> {
+    let notes = app.note_repo.with_archived();
+    let (count, reassignable) = match &record {
+        Archivable::Student(matricule) => (notes.get_by_student(matricule, None)?.len(), false),
+        Archivable::Subject(code) => (notes.get_by_subject(code, None)?.len(), true),
+        Archivable::Note(_) => (1, false),
+    };
+    println!("\n{} : {} note(s) rattachée(s), archivées comprises.", record, count);
+
+    println!("\nQue faire des notes ?");
+    println!("1.  Refuser la suppression s'il en reste");
+    println!("2.  Les supprimer aussi");
+    println!("3.  Archiver au lieu de supprimer");
+    if reassignable {
+        println!("4.  Les transférer vers une autre matière de la classe");
+    }
+    let policy = match prompt("\nChoisissez une politique: ")?.as_str() {
+        "1" => DeletePolicy::Refuse,
+        "2" => DeletePolicy::Cascade,
+        "3" => DeletePolicy::Archive,
+        "4" if reassignable => DeletePolicy::Reassign(prompt("Code de la matière qui reçoit les notes: ")?),
+        _ => {
+            println!("\n✗ Politique invalide.\n");
+            return Ok(());
+        }
+    };
+    if !prompt(&format!("Supprimer {} ({}) ? (o/N): ", record, policy))?.eq_ignore_ascii_case("o") {
+        return Ok(());
+    }
+
+    let result = match &record {
+        Archivable::Student(matricule) => app.deletion_repo.delete_student(matricule, &policy),
+        Archivable::Subject(code) => app.deletion_repo.delete_subject(code, &policy),
+        Archivable::Note(_) => return Ok(()),
+    };
+    match result {
+        Ok(DeleteOutcome::Deleted { notes }) =>
+            println!("\n✓ Supprimé : {} ({} note(s) supprimée(s)).\n", record, notes),
+        Ok(DeleteOutcome::Reassigned { notes, to }) =>
+            println!("\n✓ Supprimé : {} ({} note(s) transférée(s) vers {}).\n", record, notes, to),
+        Ok(DeleteOutcome::Archived { notes }) =>
+            println!("\n✓ Archivé : {} ({} note(s)).\n", record, notes),
+        Err(DeleteError::HasNotes(notes)) => {
+            println!("\n✗ Suppression refusée, {} note(s) y font encore référence :", notes.len());
+            views::display_notes(&notes);
+            println!();
+        }
+        Err(e) => println!("\n✗ Suppression impossible: {}\n", e),
+    }
+    Ok(())
+}
+
+/// Handle archives sub-menu commands
+pub fn handle_archives_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+            let Some(student) = app.student_repo.get(&matricule)? else {
+                println!("\n✗ Étudiant non trouvé.\n");
+                return Ok(());
+            };
+            views::display_students(std::slice::from_ref(&student));
+            archive(app, Archivable::Student(student.matricule))?;
+        }
+        "2" => {
+            let code = prompt("\nCode de la matière: ")?;
+            let Some(subject) = app.subject_repo.get(&code)? else {
+                println!("\n✗ Matière non trouvée.\n");
+                return Ok(());
+            };
+            views::display_subjects(std::slice::from_ref(&subject), &app.teacher_repo.get_subject_teachers()?);
+            archive(app, Archivable::Subject(subject.code))?;
+        }
+        "3" => {
+            let id = prompt("\nID de la note: ")?;
+            let Some(note) = app.note_repo.get(&id)? else {
+                println!("\n✗ Note non trouvée.\n");
+                return Ok(());
+            };
+            views::display_notes(std::slice::from_ref(&note));
+            archive(app, Archivable::Note(note.id))?;
+        }
+        "4" => {
+            let archived = app.archive_repo.get_archived()?;
+            if archived.is_empty() {
+                println!("\nAucun élément archivé.\n");
+            } else {
+                views::display_archives(&archived);
+            }
+        }
+        "5" => {
+            let archived = app.archive_repo.get_archived()?;
+            if archived.is_empty() {
+                println!("\nAucun élément archivé.\n");
+                return Ok(());
+            }
+            views::display_archives(&archived);
+
+            let Some(record) = prompt("N° de l'élément à restaurer: ")?
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| archived.into_iter().nth(number.wrapping_sub(1)))
+                .map(|archived| archived.record)
+            else {
+                println!("\n✗ Élément non trouvé.\n");
+                return Ok(());
+            };
+            display_archived(app, &record)?;
+            if !prompt(&format!("Restaurer {} ? (o/N): ", record))?.eq_ignore_ascii_case("o") {
+                return Ok(());
+            }
+
+            match app.archive_repo.restore(&record) {
+                Ok(notes) => println!("\n✓ Restauré : {} ({} note(s)).\n", record, notes),
+                Err(e) => println!("\n✗ Restauration impossible: {}\n", e),
+            }
+        }
+        "6" => {
+            let days = app.retention_days()?;
+            let confirm = prompt(&format!(
+                "\nSupprimer définitivement les éléments archivés depuis plus de {} jours, avec toutes leurs notes ? (o/N): ",
+                days
+            ))?;
+            if !confirm.eq_ignore_ascii_case("o") {
+                return Ok(());
+            }
+
+            match app.archive_repo.purge(days) {
+                Ok(report) => println!(
+                    "\n✓ Archives purgées : {} étudiant(s), {} matière(s) et {} note(s) supprimés.\n",
+                    report.students, report.subjects, report.notes
+                ),
+                Err(e) => println!("\n✗ Purge impossible: {}\n", e),
+            }
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::{archives, optional, periods, prompt};
+use crate::db::repositories::archive_repository::Archivable;
+use crate::db::repositories::class_repository::Class;
+use crate::db::repositories::enrolment_repository::ENROLLED_IN_CURRENT_YEAR;
+use crate::db::repositories::repository::Repository;
+use crate::grading::promotion::{self, Outcome};
+use crate::grading::Scope;
+use crate::views;
+
+/// Classes sub-menu
+pub fn show_classes_menu() {
+    println!("\n=== Classes ===");
+    println!("1.  Voir les classes et leurs effectifs");
+    println!("2.  Voir la liste d'une classe");
+    println!("3.  Créer une classe");
+    println!("4.  Contrôler la cohérence des notes");
+    println!("5.  Voir la scolarité d'un étudiant");
+    println!("6.  Changer un étudiant de classe ou enregistrer son départ");
+    println!("7.  Passage de fin d'année");
+    println!("8.  Supprimer une matière");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Handle classes sub-menu commands
+pub fn handle_classes_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let summaries = app.class_repo.get_summaries()?;
+            if summaries.is_empty() {
+                println!("\nAucune classe trouvée.\n");
+            } else {
+                println!("\n=== Classes ===");
+                views::display_classes(&summaries);
+                println!();
+            }
+        }
+        "2" => {
+            let code = prompt("\nCode de la classe: ")?;
+            let Some(class) = app.class_repo.get(&code)? else {
+                println!("\n✗ Classe non trouvée.\n");
+                return Ok(());
+            };
+
+            let students = app.student_repo.filter(
+                &format!("{} ORDER BY name, surname", ENROLLED_IN_CURRENT_YEAR),
+                &[&class.code],
+            )?;
+            println!("\n=== Classe {} ===", class.code);
+            if let Some(head_teacher) = &class.head_teacher {
+                println!("Professeur principal : {}", head_teacher);
+            }
+            match class.capacity {
+                Some(capacity) => println!("Effectif : {}/{}", students.len(), capacity),
+                None => println!("Effectif : {}", students.len()),
+            }
+
+            if students.is_empty() {
+                println!("Aucun étudiant dans cette classe.\n");
+            } else {
+                views::display_students(&students);
+                println!();
+            }
+        }
+        "3" => {
+            let code = prompt("\nCode de la classe (ex: 6ème A): ")?;
+            let level = prompt("Niveau (ex: 6ème): ")?;
+            let section = optional(prompt("Section (vide = aucune): ")?);
+            let head_teacher = optional(prompt("Professeur principal (vide = aucun): ")?);
+            let capacity = match prompt("Capacité (vide = illimitée): ")?.as_str() {
+                "" => None,
+                input => match input.parse::<u32>() {
+                    Ok(capacity) if capacity > 0 => Some(capacity),
+                    _ => {
+                        println!("\n✗ Capacité invalide.\n");
+                        return Ok(());
+                    }
+                },
+            };
+
+            if code.is_empty() || level.is_empty() {
+                println!("\n✗ Le code et le niveau sont obligatoires.\n");
+                return Ok(());
+            }
+            if app.class_repo.get(&code)?.is_some() {
+                println!("\n✗ La classe {} existe déjà.\n", code);
+                return Ok(());
+            }
+
+            let class = Class {
+                code,
+                level,
+                section,
+                academic_year_id: app.period_repo.get_current_year()?.map(|year| year.id),
+                head_teacher,
+                capacity,
+            };
+            app.class_repo.create(&class)?;
+            println!("\n✓ Classe {} créée.\n", class.code);
+        }
+        "4" => {
+            let misplaced = app.note_repo.get_misplaced()?;
+            if misplaced.is_empty() {
+                println!("\n✓ Toutes les notes portent sur une matière de la classe de l'étudiant.\n");
+            } else {
+                println!("\n✗ {} notes portent sur une matière d'une autre classe:", misplaced.len());
+                views::display_misplaced_notes(&misplaced);
+                println!();
+            }
+
+            let without_period = app.note_repo.get_without_period()?;
+            if without_period.is_empty() {
+                println!("✓ Toutes les notes sont rattachées à une période.\n");
+                return Ok(());
+            }
+            println!("✗ {} notes ne sont rattachées à aucune période et ne comptent dans aucune moyenne:", without_period.len());
+            let shown = without_period.len().min(50);
+            views::display_notes(&without_period[..shown]);
+            if without_period.len() > shown {
+                println!("… et {} autres.", without_period.len() - shown);
+            }
+            if !prompt("\nLes rattacher à une période ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                return Ok(());
+            }
+            let Some(choice) = periods::choose_period(app, "annuler")? else {
+                return Ok(());
+            };
+            let Some(period) = &choice.period else {
+                return Ok(());
+            };
+            match app.note_repo.attach_to_period(period.id) {
+                Ok(attached) => println!("\n✓ {} notes rattachées au {}.\n", attached, choice.label()),
+                Err(e) => println!("\n✗ Rattachement impossible: {}\n", e),
+            }
+        }
+        "5" => {
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+            let Some(student) = app.student_repo.get(&matricule)? else {
+                println!("\n✗ Étudiant non trouvé.\n");
+                return Ok(());
+            };
+
+            let history = app.enrolment_repo.get_history(&student.matricule)?;
+            println!("\n=== Scolarité de {} {} ===", student.name, student.surname);
+            if history.is_empty() {
+                println!("Aucune inscription enregistrée.\n");
+            } else {
+                views::display_enrolments(&history);
+                println!();
+            }
+        }
+        "6" => {
+            let Some(year) = app.period_repo.get_current_year()? else {
+                println!("\n✗ Aucune année scolaire en cours.\n");
+                return Ok(());
+            };
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+            let Some(enrolment) = app.enrolment_repo.get_current(&matricule, year.id)? else {
+                println!("\n✗ Aucun étudiant inscrit en {} avec ce matricule.\n", year.label);
+                return Ok(());
+            };
+
+            let class = prompt(&format!(
+                "Nouvelle classe (actuellement {}, vide = départ de l'établissement): ",
+                enrolment.class
+            ))?;
+            if class.is_empty() {
+                app.enrolment_repo.leave(&matricule, year.id)?;
+                println!("\n✓ Départ de l'étudiant {} enregistré.\n", matricule);
+            } else if class == enrolment.class {
+                println!("\n✗ L'étudiant est déjà en {}.\n", class);
+            } else if app.class_repo.get(&class)?.is_none() {
+                println!("\n✗ Classe non trouvée.\n");
+            } else {
+                app.enrolment_repo.transfer(&matricule, year.id, &class)?;
+                println!("\n✓ L'étudiant {} passe en {}. Ses notes restent attachées à la {}.\n", matricule, class, enrolment.class);
+            }
+        }
+        "7" => {
+            let Some(year) = app.period_repo.get_current_year()? else {
+                println!("\n✗ Aucune année scolaire en cours.\n");
+                return Ok(());
+            };
+            let label = prompt(&format!("\nAnnée scolaire suivante (après {}): ", year.label))?;
+            let Some(next_year) = app
+                .period_repo
+                .get_academic_years()?
+                .into_iter()
+                .find(|other| other.label == label && other.start_date > year.start_date)
+            else {
+                println!("\n✗ Année scolaire suivante non trouvée. Créez-la depuis le menu des années scolaires.\n");
+                return Ok(());
+            };
+
+            let class = prompt("Classe: ")?;
+            if app.class_repo.get(&class)?.is_none() {
+                println!("\n✗ Classe non trouvée.\n");
+                return Ok(());
+            }
+            let next_class = optional(prompt("Classe des admis (vide = fin de cycle, les admis sont diplômés): ")?);
+            if let Some(next_class) = &next_class
+                && app.class_repo.get(next_class)?.is_none()
+            {
+                println!("\n✗ Classe {} non trouvée.\n", next_class);
+                return Ok(());
+            }
+
+            let averages = app.grading_service.class_averages(&class, Scope::Year(year.id))?;
+            let mut moves = Vec::new();
+            for change in promotion::plan(&class, next_class.as_deref(), &averages, &app.grading_service.rules()?) {
+                if app.enrolment_repo.get_current(&change.student.matricule, next_year.id)?.is_some() {
+                    println!("✗ {} est déjà inscrit en {}.", change.student.matricule, next_year.label);
+                    continue;
+                }
+                moves.push(change);
+            }
+            if moves.is_empty() {
+                println!("\n✗ Aucun étudiant à faire passer.\n");
+                return Ok(());
+            }
+
+            println!("\n=== Passage {} → {} : {} ===", year.label, next_year.label, class);
+            views::display_promotion(&moves);
+            let undecided = moves.iter().filter(|change| change.outcome == Outcome::Undecided).count();
+            if undecided > 0 {
+                println!("{} étudiants sans moyenne annuelle ne seront pas inscrits en {}.", undecided, next_year.label);
+            }
+
+            if prompt("\nConfirmer le passage ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                app.enrolment_repo.promote(year.id, next_year.id, &moves)?;
+                println!("\n✓ Passage enregistré pour {} étudiants.\n", moves.len() - undecided);
+            } else {
+                println!("\nPassage annulé.\n");
+            }
+        }
+        "8" => {
+            let code = prompt("\nCode de la matière: ")?;
+            let Some(subject) = app.subject_repo.with_archived().get(&code)? else {
+                println!("\n✗ Matière non trouvée.\n");
+                return Ok(());
+            };
+            views::display_subjects(std::slice::from_ref(&subject), &app.teacher_repo.get_subject_teachers()?);
+            archives::delete(app, Archivable::Subject(subject.code))?;
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,314 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::{periods, prompt};
+use crate::db::repositories::evaluation_repository::{Evaluation, EvaluationType};
+use crate::db::repositories::moderation_repository::{ModeratedNote, ModerationRecord};
+use crate::db::repositories::note_repository::Note;
+use crate::db::repositories::repository::Repository;
+use crate::grading::moderation::Moderation;
+use crate::grading::Scope;
+use crate::views;
+
+/// Evaluations sub-menu
+pub fn show_evaluations_menu() {
+    println!("\n=== Évaluations ===");
+    println!("1.  Voir les types d'évaluation");
+    println!("2.  Définir un type d'évaluation");
+    println!("3.  Choisir le type des notes hors évaluation");
+    println!("4.  Créer une évaluation");
+    println!("5.  Voir les évaluations d'une matière");
+    println!("6.  Saisir les notes d'une évaluation");
+    println!("7.  Moduler des notes (barème, bonus, courbe)");
+    println!("8.  Annuler une modulation");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Read a positive number, accepting a decimal comma
+fn parse_positive(input: &str) -> Option<f64> {
+    input.replace(',', ".").parse::<f64>().ok().filter(|value| *value > 0.0)
+}
+
+/// Read a number, accepting a decimal comma
+fn parse_number(input: &str) -> Option<f64> {
+    input.replace(',', ".").parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// Ask for the transformation to apply to the notes
+fn choose_moderation() -> Result<Option<Moderation>, Box<dyn std::error::Error>> {
+    println!("\n1. Barème linéaire (note × facteur + décalage)");
+    println!("2. Bonus de points");
+    println!("3. Courbe vers une moyenne cible");
+
+    let moderation = match prompt("Modulation: ")?.as_str() {
+        "1" => {
+            let factor = parse_positive(&prompt("Facteur: ")?);
+            let offset = match prompt("Décalage (vide = 0): ")?.as_str() {
+                "" => Some(0.0),
+                input => parse_number(input),
+            };
+            factor.zip(offset).map(|(factor, offset)| Moderation::Linear { factor, offset })
+        }
+        "2" => parse_number(&prompt("Points ajoutés (négatif pour retirer): ")?).map(Moderation::Bonus),
+        "3" => parse_positive(&prompt("Moyenne visée: ")?).map(Moderation::TargetMean),
+        _ => None,
+    };
+
+    if moderation.is_none() {
+        println!("\n✗ Modulation invalide.\n");
+    }
+    Ok(moderation)
+}
+
+/// Handle evaluations sub-menu commands
+pub fn handle_evaluations_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            views::display_evaluation_types(&app.evaluation_repo.get_types()?);
+        }
+        "2" => {
+            let code = prompt("\nCode du type (ex: tp): ")?.to_lowercase();
+            let label = prompt("Libellé: ")?;
+            let Some(weight) = parse_positive(&prompt("Poids dans la moyenne de la matière: ")?) else {
+                println!("\n✗ Poids invalide.\n");
+                return Ok(());
+            };
+
+            if code.is_empty() || label.is_empty() {
+                println!("\n✗ Le code et le libellé sont obligatoires.\n");
+            } else {
+                app.evaluation_repo.set_type(&EvaluationType { code, label, weight, is_default: false })?;
+                println!("\n✓ Type d'évaluation enregistré.\n");
+            }
+        }
+        "3" => {
+            let code = prompt("\nCode du type: ")?.to_lowercase();
+
+            if app.evaluation_repo.get_types()?.iter().any(|t| t.code == code) {
+                app.evaluation_repo.set_default_type(&code)?;
+                println!("\n✓ Les notes hors évaluation comptent comme « {} ».\n", code);
+            } else {
+                println!("\n✗ Type d'évaluation non trouvé.\n");
+            }
+        }
+        "4" => {
+            let code = prompt("\nCode de la matière: ")?;
+            let Some(subject) = app.subject_repo.get(&code)? else {
+                println!("\n✗ Matière non trouvée.\n");
+                return Ok(());
+            };
+            let Some(period) = periods::choose_period(app, "annuler")?.and_then(|c| c.period) else {
+                return Ok(());
+            };
+
+            let type_code = prompt("Type (devoir, interrogation, composition, examen...): ")?.to_lowercase();
+            let title = prompt("Intitulé: ")?;
+            let date = prompt("Date (AAAA-MM-JJ): ")?;
+            let max_score = match prompt("Barème (vide = 20): ")?.as_str() {
+                "" => Some(20.0),
+                input => parse_positive(input),
+            };
+            let weight = match prompt("Poids parmi les évaluations du même type (vide = 1): ")?.as_str() {
+                "" => Some(1.0),
+                input => parse_positive(input),
+            };
+            let (Some(max_score), Some(weight)) = (max_score, weight) else {
+                println!("\n✗ Barème ou poids invalide.\n");
+                return Ok(());
+            };
+
+            let evaluation = Evaluation {
+                id: 0,
+                subject_code: subject.code,
+                period_id: period.id,
+                type_code,
+                title,
+                date,
+                max_score,
+                weight,
+            };
+            match app.evaluation_repo.create(&evaluation) {
+                Ok(id) => println!("\n✓ Évaluation n°{} créée.\n", id),
+                Err(e) => println!("\n✗ Évaluation invalide: {}\n", e),
+            }
+        }
+        "5" => {
+            let code = prompt("\nCode de la matière: ")?;
+            let Some(choice) = periods::choose_period(app, "toutes les périodes")? else {
+                return Ok(());
+            };
+
+            let evaluations = app.evaluation_repo.get_by_subject(&code, choice.period_id())?;
+            if evaluations.is_empty() {
+                println!("\nAucune évaluation trouvée.\n");
+            } else {
+                views::display_evaluations(&evaluations);
+            }
+        }
+        "6" => {
+            let Ok(id) = prompt("\nNuméro de l'évaluation: ")?.parse::<i64>() else {
+                println!("\n✗ Numéro invalide.\n");
+                return Ok(());
+            };
+            let Some(evaluation) = app.evaluation_repo.get(id)? else {
+                println!("\n✗ Évaluation non trouvée.\n");
+                return Ok(());
+            };
+            let Some(subject) = app.subject_repo.get(&evaluation.subject_code)? else {
+                println!("\n✗ Matière non trouvée.\n");
+                return Ok(());
+            };
+
+            println!(
+                "\n{} — notes sur {} (vide = absent), converties sur 20",
+                evaluation.title, evaluation.max_score
+            );
+            let scale = app.grading_service.scale()?;
+            // Students of the class the year of the evaluation, not today
+            let students = app.grading_service.enrolled_in_class(&subject.class, Scope::Period(evaluation.period_id))?;
+            let mut recorded = 0;
+            for student in students {
+                let label = format!("{} {} ({}): ", student.name, student.surname, student.matricule);
+                let input = loop {
+                    let input = prompt(&label)?;
+                    if input.is_empty() {
+                        break None;
+                    }
+                    match input.replace(',', ".").parse().ok().and_then(|score| evaluation.normalize(score)) {
+                        Some(value) => match scale.check(value) {
+                            Ok(()) => break Some(value),
+                            Err(e) => println!("✗ Note invalide: {} (sur 20).", e),
+                        },
+                        None => println!("✗ Note invalide (0 à {}).", evaluation.max_score),
+                    }
+                };
+
+                if let Some(value) = input {
+                    let note = Note {
+                        id: 0,
+                        value,
+                        matricule: student.matricule,
+                        subject_code: evaluation.subject_code.clone(),
+                        period_id: Some(evaluation.period_id),
+                        evaluation_id: Some(evaluation.id),
+                    };
+                    match app.note_repo.record(&note, &scale) {
+                        Ok(()) => recorded += 1,
+                        Err(e) => println!("✗ Note non enregistrée: {}", e),
+                    }
+                }
+            }
+            println!("\n✓ {} notes enregistrées.\n", recorded);
+        }
+        "7" => {
+            let mut record = ModerationRecord {
+                id: 0,
+                subject_code: String::new(),
+                evaluation_id: None,
+                period_id: None,
+                description: String::new(),
+                applied_by: app.author(),
+                applied_at: String::new(),
+                reverted_at: None,
+                notes: 0,
+            };
+
+            let notes = match prompt("\nNuméro de l'évaluation (vide = notes d'une matière): ")?.as_str() {
+                "" => {
+                    let code = prompt("Code de la matière: ")?;
+                    let Some(choice) = periods::choose_period(app, "toutes les périodes")? else {
+                        return Ok(());
+                    };
+                    record.period_id = choice.period_id();
+                    record.subject_code = code;
+                    app.note_repo.get_by_subject(&record.subject_code, record.period_id)?
+                }
+                input => {
+                    let Ok(id) = input.parse::<i64>() else {
+                        println!("\n✗ Numéro invalide.\n");
+                        return Ok(());
+                    };
+                    let Some(evaluation) = app.evaluation_repo.get(id)? else {
+                        println!("\n✗ Évaluation non trouvée.\n");
+                        return Ok(());
+                    };
+                    record.subject_code = evaluation.subject_code;
+                    record.evaluation_id = Some(evaluation.id);
+                    record.period_id = Some(evaluation.period_id);
+                    app.note_repo.filter("evaluation_id = ?1 ORDER BY id", &[&evaluation.id])?
+                }
+            };
+            if notes.is_empty() {
+                println!("\n✗ Aucune note à moduler.\n");
+                return Ok(());
+            }
+            let Some(moderation) = choose_moderation()? else {
+                return Ok(());
+            };
+            // A curve is fitted to one set of notes, not across terms
+            if matches!(moderation, Moderation::TargetMean(_)) && record.period_id.is_none() {
+                println!("\n✗ Une courbe ne s'applique qu'aux notes d'une évaluation ou d'une période.\n");
+                return Ok(());
+            }
+            let values: Vec<f64> = notes.iter().map(|note| note.value).collect();
+            let moderated = moderation.apply(&values, &app.grading_service.scale()?);
+
+            println!("\n=== Aperçu — {} ===", moderation);
+            views::display_moderation_preview(&notes, &moderated);
+
+            if prompt("\nAppliquer la modulation ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                record.description = moderation.to_string();
+                let changes: Vec<ModeratedNote> = notes
+                    .iter()
+                    .zip(&moderated)
+                    .map(|(note, value)| ModeratedNote { note_id: note.id, original_value: note.value, moderated_value: *value })
+                    .collect();
+                match app.moderation_repo.apply(&record, &changes) {
+                    Ok(id) => {
+                        let changed = changes.iter().filter(|c| c.moderated_value != c.original_value).count();
+                        println!("\n✓ Modulation n°{} appliquée à {} notes.\n", id, changed);
+                    }
+                    Err(e) => println!("\n✗ Modulation non appliquée: {}\n", e),
+                }
+            }
+        }
+        "8" => {
+            let code = prompt("\nCode de la matière: ")?;
+            let records = app.moderation_repo.get_by_subject(&code)?;
+            if records.is_empty() {
+                println!("\nAucune modulation pour cette matière.\n");
+                return Ok(());
+            }
+            views::display_moderations(&records);
+
+            let Some(record) = prompt("Numéro de la modulation à annuler: ")?
+                .parse::<i64>()
+                .ok()
+                .and_then(|id| records.into_iter().find(|r| r.id == id))
+            else {
+                println!("\n✗ Modulation non trouvée.\n");
+                return Ok(());
+            };
+
+            match app.moderation_repo.revert(record.id, app.author().as_deref()) {
+                Ok(Some(restored)) => {
+                    println!("\n✓ Modulation annulée : {} notes restaurées.", restored);
+                    let kept = record.notes as usize - restored;
+                    if kept > 0 {
+                        println!("  {} notes modifiées depuis ont été conservées.", kept);
+                    }
+                    println!();
+                }
+                Ok(None) => println!("\n✗ Cette modulation est déjà annulée.\n"),
+                Err(e) => println!("\n✗ Modulation non annulée: {}\n", e),
+            }
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
@@ -1,7 +1,40 @@
+pub mod absences;
+pub mod archives;
+pub mod classes;
+pub mod evaluations;
+pub mod notes;
+pub mod periods;
+pub mod reports;
+pub mod settings;
+pub mod students;
+pub mod teachers;
+
 use std::io::{self, Write};
 use crate::app::App;
 use crate::db::repositories::repository::Repository;
 
+/// Print `label` and read a trimmed line from stdin
+pub fn prompt(label: &str) -> io::Result<String> {
+    print!("{}", label);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+/// `None` for an empty answer
+pub fn optional(input: String) -> Option<String> {
+    if input.is_empty() { None } else { Some(input) }
+}
+
+/// Title of a notes listing for the chosen period
+fn notes_label(choice: &periods::PeriodChoice) -> String {
+    match &choice.period {
+        Some(_) => choice.label(),
+        None => "toutes les périodes".to_string(),
+    }
+}
+
 /// Main menu for authenticated users
 pub fn show_main_menu() {
     println!("\n=== Menu Principal ===");
@@ -12,6 +45,16 @@ pub fn show_main_menu() {
     println!("5.  Voir toutes les notes");
     println!("6.  Voir les notes d'un étudiant");
     println!("7.  Voir les notes d'une matière");
+    println!("10. Rapports et exports");
+    println!("11. Paramètres");
+    println!("12. Années scolaires et périodes");
+    println!("13. Évaluations");
+    println!("14. Classes");
+    println!("15. Enseignants");
+    println!("16. Absences et retards");
+    println!("17. Gestion des étudiants");
+    println!("18. Corrections de notes");
+    println!("19. Archives");
     println!("8.  Déconnexion");
     println!("9.  Quitter");
     print!("\nChoisissez une option: ");
@@ -41,14 +84,47 @@ pub fn handle_main_command(app: &mut App, choice: &str) -> Result<bool, Box<dyn
 
             match app.student_repo.get(matricule)? {
                 Some(student) => {
-                    println!("\n=== Étudiant ===");
-                    crate::views::display_students(&[student.clone()]);
+                    let Some(choice) = periods::choose_period(app, "année complète")? else {
+                        return Ok(true);
+                    };
+
+                    println!("\n=== Étudiant — {} ===", choice.label());
+                    crate::views::display_students(std::slice::from_ref(&student));
+                    if let Some(profile) = app.profile_repo.get_profile(matricule)? {
+                        crate::views::display_profile(Some(&profile));
+                    }
+                    let guardians = app.profile_repo.get_guardians(matricule)?;
+                    if !guardians.is_empty() {
+                        crate::views::display_guardians(&guardians);
+                    }
                     
                     // Show student notes
-                    let notes = app.note_repo.get_by_student(matricule)?;
+                    let without_period = app
+                        .note_repo
+                        .get_by_student(matricule, None)?
+                        .iter()
+                        .filter(|note| note.period_id.is_none())
+                        .count();
+                    periods::warn_without_period(without_period);
+                    let notes = app.note_repo.get_by_student(matricule, choice.period_id())?;
                     if !notes.is_empty() {
                         let subjects = app.subject_repo.get_all()?;
                         crate::views::display_student_notes_with_details(&notes, &subjects, &student);
+
+                        let average = app.grading_service.student_average(&student, choice.scope())?;
+                        crate::views::display_student_average(&average, &subjects);
+
+                        if choice.period.is_none() {
+                            let terms = app.grading_service.term_averages(&student, choice.year.id)?;
+                            crate::views::display_term_averages(&terms, &average);
+                        }
+                    }
+
+                    let (from, to) = choice.dates();
+                    let totals = app.absence_repo.get_totals(from, to)?.remove(matricule).unwrap_or_default();
+                    println!("\nAssiduité : {}", totals);
+                    for alert in app.grading_service.attendance_thresholds()?.alerts(&totals) {
+                        println!("⚠ {}", alert);
                     }
                     println!();
                 }
@@ -64,7 +140,7 @@ pub fn handle_main_command(app: &mut App, choice: &str) -> Result<bool, Box<dyn
             if subjects.is_empty() {
                 println!("Aucune matière trouvée.\n");
             } else {
-                crate::views::display_subjects(&subjects);
+                crate::views::display_subjects(&subjects, &app.teacher_repo.get_subject_teachers()?);
                 println!();
             }
             Ok(true)
@@ -79,12 +155,24 @@ pub fn handle_main_command(app: &mut App, choice: &str) -> Result<bool, Box<dyn
             match app.subject_repo.get(code)? {
                 Some(subject) => {
                     println!("\n=== Matière ===");
-                    crate::views::display_subjects(&[subject.clone()]);
+                    crate::views::display_subjects(std::slice::from_ref(&subject), &app.teacher_repo.get_subject_teachers()?);
                     
                     // Show subject notes
-                    let notes = app.note_repo.get_by_subject(code)?;
+                    let Some(choice) = periods::choose_period(app, "toutes les périodes")? else {
+                        return Ok(true);
+                    };
+                    let notes = app.note_repo.get_by_subject(code, choice.period_id())?;
                     if !notes.is_empty() {
-                        println!("\n=== Notes pour cette matière ===");
+                        let statistics = crate::grading::stats::by_subject(vec![subject], &notes);
+                        println!("\n=== Statistiques — {} ===", notes_label(&choice));
+                        crate::views::display_subject_statistics(&statistics);
+                        if let Some(summary) = &statistics[0].1 {
+                            crate::views::display_distribution(&summary.buckets);
+                            println!();
+                            crate::views::display_box_plots(&statistics);
+                        }
+
+                        println!("\n=== Notes pour cette matière — {} ===", notes_label(&choice));
                         crate::views::display_notes(&notes);
                     }
                     println!();
@@ -96,8 +184,14 @@ pub fn handle_main_command(app: &mut App, choice: &str) -> Result<bool, Box<dyn
             Ok(true)
         }
         "5" => {
-            println!("\n=== Toutes les notes ===");
-            let notes = app.note_repo.get_all()?;
+            let Some(choice) = periods::choose_period(app, "toutes les périodes")? else {
+                return Ok(true);
+            };
+            println!("\n=== Toutes les notes — {} ===", notes_label(&choice));
+            let notes = match choice.period_id() {
+                Some(period_id) => app.note_repo.filter("period_id = ?1", &[&period_id])?,
+                None => app.note_repo.get_all()?,
+            };
             if notes.is_empty() {
                 println!("Aucune note trouvée.\n");
             } else {
@@ -115,11 +209,15 @@ pub fn handle_main_command(app: &mut App, choice: &str) -> Result<bool, Box<dyn
 
             match app.student_repo.get(matricule)? {
                 Some(student) => {
-                    let notes = app.note_repo.get_by_student(matricule)?;
+                    let Some(choice) = periods::choose_period(app, "toutes les périodes")? else {
+                        return Ok(true);
+                    };
+                    let notes = app.note_repo.get_by_student(matricule, choice.period_id())?;
                     if notes.is_empty() {
                         println!("\nAucune note trouvée pour cet étudiant.\n");
                     } else {
                         let subjects = app.subject_repo.get_all()?;
+                        println!("\n=== {} ===", notes_label(&choice));
                         crate::views::display_student_notes_with_details(&notes, &subjects, &student);
                         println!();
                     }
@@ -139,11 +237,14 @@ pub fn handle_main_command(app: &mut App, choice: &str) -> Result<bool, Box<dyn
 
             match app.subject_repo.get(code)? {
                 Some(_) => {
-                    let notes = app.note_repo.get_by_subject(code)?;
+                    let Some(choice) = periods::choose_period(app, "toutes les périodes")? else {
+                        return Ok(true);
+                    };
+                    let notes = app.note_repo.get_by_subject(code, choice.period_id())?;
                     if notes.is_empty() {
                         println!("\nAucune note trouvée pour cette matière.\n");
                     } else {
-                        println!("\n=== Notes pour la matière {} ===", code);
+                        println!("\n=== Notes pour la matière {} — {} ===", code, notes_label(&choice));
                         crate::views::display_notes(&notes);
                         println!();
                     }
@@ -154,6 +255,66 @@ pub fn handle_main_command(app: &mut App, choice: &str) -> Result<bool, Box<dyn
             }
             Ok(true)
         }
+        "10" => {
+            reports::show_reports_menu();
+            let choice = prompt("")?;
+            reports::handle_reports_command(app, &choice)?;
+            Ok(true)
+        }
+        "11" => {
+            settings::show_settings_menu(app)?;
+            let choice = prompt("")?;
+            settings::handle_settings_command(app, &choice)?;
+            Ok(true)
+        }
+        "12" => {
+            periods::show_periods_menu();
+            let choice = prompt("")?;
+            periods::handle_periods_command(app, &choice)?;
+            Ok(true)
+        }
+        "13" => {
+            evaluations::show_evaluations_menu();
+            let choice = prompt("")?;
+            evaluations::handle_evaluations_command(app, &choice)?;
+            Ok(true)
+        }
+        "14" => {
+            classes::show_classes_menu();
+            let choice = prompt("")?;
+            classes::handle_classes_command(app, &choice)?;
+            Ok(true)
+        }
+        "15" => {
+            teachers::show_teachers_menu();
+            let choice = prompt("")?;
+            teachers::handle_teachers_command(app, &choice)?;
+            Ok(true)
+        }
+        "16" => {
+            absences::show_absences_menu();
+            let choice = prompt("")?;
+            absences::handle_absences_command(app, &choice)?;
+            Ok(true)
+        }
+        "17" => {
+            students::show_students_menu();
+            let choice = prompt("")?;
+            students::handle_students_command(app, &choice)?;
+            Ok(true)
+        }
+        "18" => {
+            notes::show_notes_menu();
+            let choice = prompt("")?;
+            notes::handle_notes_command(app, &choice)?;
+            Ok(true)
+        }
+        "19" => {
+            archives::show_archives_menu();
+            let choice = prompt("")?;
+            archives::handle_archives_command(app, &choice)?;
+            Ok(true)
+        }
         "8" => {
             app.logout();
             println!("\n✓ Déconnexion réussie.\n");
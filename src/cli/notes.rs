@@ -0,0 +1,95 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::prompt;
+use crate::db::repositories::note_repository::{Note, NoteChange};
+use crate::db::repositories::repository::Repository;
+use crate::views;
+
+/// Note corrections sub-menu
+pub fn show_notes_menu() {
+    println!("\n=== Corrections de notes ===");
+    println!("1.  Modifier une note");
+    println!("2.  Historique d'une note");
+    println!("3.  Rétablir une version d'une note");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Ask for a note by its id and display it, `None` after printing an
+/// error if there is no such note
+fn choose_note(app: &App) -> Result<Option<Note>, Box<dyn std::error::Error>> {
+    let id = prompt("\nID de la note: ")?;
+    let Some(note) = app.note_repo.get(&id)? else {
+        println!("\n✗ Note non trouvée.\n");
+        return Ok(None);
+    };
+
+    views::display_notes(std::slice::from_ref(&note));
+    Ok(Some(note))
+}
+
+/// Ask why a note is changed
+fn ask_change(app: &App) -> Result<NoteChange, Box<dyn std::error::Error>> {
+    let reason = prompt(&format!(
+        "Motif (obligatoire pour une note enregistrée il y a plus de {} jours): ",
+        app.free_edit_days()?
+    ))?;
+
+    Ok(NoteChange {
+        author: app.author(),
+        reason: (!reason.is_empty()).then_some(reason),
+    })
+}
+
+/// Handle note corrections sub-menu commands
+pub fn handle_notes_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let Some(note) = choose_note(app)? else {
+                return Ok(());
+            };
+            let Ok(value) = prompt("Nouvelle note (sur 20): ")?.replace(',', ".").parse::<f64>() else {
+                println!("\n✗ Note invalide.\n");
+                return Ok(());
+            };
+            let change = ask_change(app)?;
+
+            let scale = app.grading_service.scale()?;
+            match app.note_repo.change_value(note.id, value, &change, &scale, app.free_edit_days()?) {
+                Ok(()) => println!("\n✓ Note modifiée : {:.2} → {:.2}.\n", note.value, value),
+                Err(e) => println!("\n✗ Note non modifiée: {}\n", e),
+            }
+        }
+        "2" => {
+            let Some(note) = choose_note(app)? else {
+                return Ok(());
+            };
+            views::display_note_history(&app.note_repo.get_history(note.id)?);
+        }
+        "3" => {
+            let Some(note) = choose_note(app)? else {
+                return Ok(());
+            };
+            views::display_note_history(&app.note_repo.get_history(note.id)?);
+
+            let Ok(version) = prompt("Version à rétablir: ")?.parse::<u32>() else {
+                println!("\n✗ Version invalide.\n");
+                return Ok(());
+            };
+            let change = ask_change(app)?;
+
+            let scale = app.grading_service.scale()?;
+            match app.note_repo.revert(note.id, version, &change, &scale, app.free_edit_days()?) {
+                Ok(()) => println!("\n✓ Version {} rétablie.\n", version),
+                Err(e) => println!("\n✗ Version non rétablie: {}\n", e),
+            }
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,285 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::prompt;
+use crate::db::repositories::lock_repository::{LockScope, PeriodLock};
+use crate::db::repositories::period_repository::{AcademicYear, Period, PeriodKind};
+use crate::db::repositories::repository::Repository;
+use crate::grading::Scope;
+use crate::views;
+
+/// Term of the current academic year, or the whole year
+pub struct PeriodChoice {
+    pub year: AcademicYear,
+    pub period: Option<Period>,
+}
+
+impl PeriodChoice {
+    pub fn scope(&self) -> Scope {
+        match &self.period {
+            Some(period) => Scope::Period(period.id),
+            None => Scope::Year(self.year.id),
+        }
+    }
+
+    /// Period filter of the notes, `None` for every period
+    pub fn period_id(&self) -> Option<i64> {
+        self.period.as_ref().map(|period| period.id)
+    }
+
+    /// First and last day of the chosen term or year
+    pub fn dates(&self) -> (&str, &str) {
+        match &self.period {
+            Some(period) => (&period.start_date, &period.end_date),
+            None => (&self.year.start_date, &self.year.end_date),
+        }
+    }
+
+    /// e.g. "1er trimestre 2025-2026" or "Année 2025-2026"
+    pub fn label(&self) -> String {
+        match &self.period {
+            Some(period) => format!("{} {}", period, self.year.label),
+            None => format!("Année {}", self.year.label),
+        }
+    }
+}
+
+/// Ask for a term of the current academic year. An empty answer selects
+/// the whole year, described by `empty_label`. Returns `None` after
+/// printing an error if there is no current year or the answer is invalid.
+pub fn choose_period(app: &App, empty_label: &str) -> Result<Option<PeriodChoice>, Box<dyn std::error::Error>> {
+    let Some(year) = app.period_repo.get_current_year()? else {
+        println!("\n✗ Aucune année scolaire en cours.\n");
+        return Ok(None);
+    };
+    let periods = app.period_repo.get_periods(year.id)?;
+
+    println!("\nAnnée scolaire {}:", year.label);
+    for period in &periods {
+        println!("{}.  {} ({} → {})", period.number, period, period.start_date, period.end_date);
+    }
+
+    let answer = prompt(&format!("Période (vide = {}): ", empty_label))?;
+    if answer.is_empty() {
+        return Ok(Some(PeriodChoice { year, period: None }));
+    }
+
+    match periods.into_iter().find(|p| p.number.to_string() == answer) {
+        Some(period) => Ok(Some(PeriodChoice { year, period: Some(period) })),
+        None => {
+            println!("\n✗ Période invalide.\n");
+            Ok(None)
+        }
+    }
+}
+
+/// Warn that `count` notes are attached to no period and so count in no
+/// average
+pub fn warn_without_period(count: usize) {
+    if count > 0 {
+        println!(
+            "⚠ {} note(s) sans période ne comptent dans aucune moyenne : rattachez-les depuis Classes → Contrôler la cohérence des notes.",
+            count
+        );
+    }
+}
+
+/// Locks of the chosen period, or of every period of the year
+fn period_locks(app: &App, choice: &PeriodChoice) -> Result<Vec<(Period, PeriodLock)>, Box<dyn std::error::Error>> {
+    let periods = match &choice.period {
+        Some(period) => vec![period.clone()],
+        None => app.period_repo.get_periods(choice.year.id)?,
+    };
+
+    let mut locks = Vec::new();
+    for period in periods {
+        for lock in app.lock_repo.get_by_period(period.id)? {
+            locks.push((period.clone(), lock));
+        }
+    }
+    Ok(locks)
+}
+
+/// Academic years sub-menu
+pub fn show_periods_menu() {
+    println!("\n=== Années scolaires et périodes ===");
+    println!("1.  Voir les années scolaires");
+    println!("2.  Créer une année scolaire");
+    println!("3.  Définir l'année en cours");
+    println!("4.  Clôturer une période (verrouiller les notes)");
+    println!("5.  Rouvrir une période");
+    println!("6.  Voir les périodes verrouillées");
+    println!("7.  Journal d'audit");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Handle academic years sub-menu commands
+pub fn handle_periods_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let years = app.period_repo.get_academic_years()?;
+            if years.is_empty() {
+                println!("\nAucune année scolaire.\n");
+            }
+            for year in &years {
+                let current = if year.is_current { " (en cours)" } else { "" };
+                println!("\n=== Année {}{} : du {} au {} ===", year.label, current, year.start_date, year.end_date);
+                views::display_periods(&app.period_repo.get_periods(year.id)?);
+            }
+        }
+        "2" => {
+            let label = prompt("\nLibellé (ex: 2026-2027): ")?;
+            let start_date = prompt("Date de début (AAAA-MM-JJ): ")?;
+            let end_date = prompt("Date de fin (AAAA-MM-JJ): ")?;
+            let period_kind = match prompt("Découpage (1. trimestres, 2. semestres): ")?.as_str() {
+                "1" => PeriodKind::Trimester,
+                "2" => PeriodKind::Semester,
+                _ => {
+                    println!("\n✗ Option invalide.\n");
+                    return Ok(());
+                }
+            };
+
+            let mut periods = Vec::new();
+            for number in 1..=period_kind.count() {
+                let mut period = Period {
+                    id: 0,
+                    academic_year_id: 0,
+                    kind: period_kind,
+                    number,
+                    start_date: String::new(),
+                    end_date: String::new(),
+                };
+                period.start_date = prompt(&format!("Début du {} (AAAA-MM-JJ): ", period))?;
+                period.end_date = prompt(&format!("Fin du {} (AAAA-MM-JJ): ", period))?;
+
+                if period.start_date < start_date || period.end_date > end_date {
+                    println!("\n✗ Le {} doit être compris dans l'année scolaire.\n", period);
+                    return Ok(());
+                }
+                periods.push(period);
+            }
+
+            let year = AcademicYear {
+                id: 0,
+                label,
+                start_date,
+                end_date,
+                period_kind,
+                is_current: false,
+            };
+            match app.period_repo.create_academic_year(&year, &periods) {
+                Ok(_) => println!("\n✓ Année scolaire {} créée.\n", year.label),
+                Err(e) => println!("\n✗ Année scolaire invalide: {}\n", e),
+            }
+        }
+        "3" => {
+            let label = prompt("\nLibellé de l'année: ")?;
+            let years = app.period_repo.get_academic_years()?;
+
+            match years.into_iter().find(|y| y.label == label) {
+                Some(year) => {
+                    app.period_repo.set_current_year(year.id)?;
+                    println!("\n✓ L'année {} est l'année en cours.\n", year.label);
+                }
+                None => {
+                    println!("\n✗ Année scolaire non trouvée.\n");
+                }
+            }
+        }
+        "4" => {
+            let Some(admin) = app.current_admin.as_ref().filter(|admin| admin.is_super_admin) else {
+                println!("\n✗ Seul un super administrateur peut clôturer une période.\n");
+                return Ok(());
+            };
+            let Some(PeriodChoice { period: Some(period), .. }) = choose_period(app, "annuler")? else {
+                return Ok(());
+            };
+
+            let scope = match prompt("Verrouiller (1. toutes les notes, 2. une classe, 3. une matière): ")?.as_str() {
+                "1" => LockScope::Period,
+                "2" => {
+                    let code = prompt("Code de la classe: ")?;
+                    if app.class_repo.get(&code)?.is_none() {
+                        println!("\n✗ Classe non trouvée.\n");
+                        return Ok(());
+                    }
+                    LockScope::Class(code)
+                }
+                "3" => {
+                    let code = prompt("Code de la matière: ")?;
+                    if app.subject_repo.get(&code)?.is_none() {
+                        println!("\n✗ Matière non trouvée.\n");
+                        return Ok(());
+                    }
+                    LockScope::Subject(code)
+                }
+                _ => {
+                    println!("\n✗ Option invalide.\n");
+                    return Ok(());
+                }
+            };
+
+            match app.lock_repo.lock(&period, &scope, admin) {
+                Ok(_) => println!("\n✓ {} clôturé ({}).\n", period, scope),
+                Err(e) => println!("\n✗ Période non clôturée: {}\n", e),
+            }
+        }
+        "5" => {
+            let Some(admin) = app.current_admin.as_ref().filter(|admin| admin.is_super_admin) else {
+                println!("\n✗ Seul un super administrateur peut rouvrir une période.\n");
+                return Ok(());
+            };
+            let Some(choice) = choose_period(app, "toute l'année")? else {
+                return Ok(());
+            };
+            let locks = period_locks(app, &choice)?;
+            if locks.is_empty() {
+                println!("\nAucune période verrouillée.\n");
+                return Ok(());
+            }
+            views::display_locks(&locks);
+
+            let Ok(id) = prompt("N° du verrou à lever: ")?.parse::<i64>() else {
+                println!("\n✗ Numéro invalide.\n");
+                return Ok(());
+            };
+            if !locks.iter().any(|(_, lock)| lock.id == id) {
+                println!("\n✗ Verrou non trouvé.\n");
+                return Ok(());
+            }
+            let justification = prompt("Justification (obligatoire): ")?;
+
+            match app.lock_repo.reopen(id, admin, &justification) {
+                Ok(lock) => println!("\n✓ Période rouverte ({}).\n", lock.scope),
+                Err(e) => println!("\n✗ Période non rouverte: {}\n", e),
+            }
+        }
+        "6" => {
+            let Some(choice) = choose_period(app, "toute l'année")? else {
+                return Ok(());
+            };
+            let locks = period_locks(app, &choice)?;
+            if locks.is_empty() {
+                println!("\nAucune période verrouillée.\n");
+            } else {
+                views::display_locks(&locks);
+            }
+        }
+        "7" => {
+            let entries = app.audit_repo.get_all()?;
+            if entries.is_empty() {
+                println!("\nLe journal d'audit est vide.\n");
+            } else {
+                views::display_audit_log(&entries);
+            }
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,429 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::periods::{self, PeriodChoice};
+use crate::cli::prompt;
+use crate::db::repositories::enrolment_repository::ENROLLED_IN_CURRENT_YEAR;
+use crate::db::repositories::repository::Repository;
+use crate::db::repositories::note_repository::Note;
+use crate::db::repositories::subject_repository::Subject;
+use crate::db::repositories::student_repository::Student;
+use crate::grading::{self, comparison, ranking, stats, warnings, Scope, StudentAverage};
+use crate::reports::{self, bulletin, council, csv, html};
+
+/// Reports and exports sub-menu
+pub fn show_reports_menu() {
+    println!("\n=== Rapports et exports ===");
+    println!("1.  Bulletin PDF d'un étudiant");
+    println!("2.  Bulletins PDF d'une classe");
+    println!("3.  Export HTML d'une classe");
+    println!("4.  Classement d'une classe");
+    println!("5.  Classement dans une matière");
+    println!("6.  Liste d'une classe avec les fiches (CSV)");
+    println!("7.  Statistiques des matières d'une classe");
+    println!("8.  Conseil de classe");
+    println!("9.  Alertes précoces (étudiants en difficulté)");
+    println!("10. Comparer les classes d'un niveau");
+    println!("11. Comparer un niveau d'une année à l'autre");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Handle reports sub-menu commands
+pub fn handle_reports_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+
+            match app.student_repo.get(&matricule)? {
+                Some(student) => {
+                    let Some(choice) = periods::choose_period(app, "année complète")? else {
+                        return Ok(());
+                    };
+                    let bulletins = load_class_bulletins(app, &student.class, &choice)?;
+                    let bulletin = bulletins
+                        .into_iter()
+                        .find(|b| b.student.matricule == student.matricule)
+                        .expect("student belongs to its own class");
+
+                    let path = reports::export_path(&format!("bulletin_{}.pdf", student.matricule))?;
+                    bulletin::bulletins_to_pdf(&[bulletin]).save(&path)?;
+                    println!("\n✓ Bulletin généré: {}\n", path.display());
+                }
+                None => {
+                    println!("\n✗ Étudiant non trouvé.\n");
+                }
+            }
+        }
+        "2" => {
+            let class = prompt("\nClasse: ")?;
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+            let bulletins = load_class_bulletins(app, &class, &choice)?;
+
+            if bulletins.is_empty() {
+                println!("\n✗ Aucun étudiant dans cette classe.\n");
+            } else {
+                let path = reports::export_path(&format!("bulletins_{}.pdf", class))?;
+                let doc = bulletin::bulletins_to_pdf(&bulletins);
+                doc.save(&path)?;
+                println!("\n✓ {} bulletins générés: {}\n", doc.page_count(), path.display());
+            }
+        }
+        "3" => {
+            let class = prompt("\nClasse: ")?;
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+            let bulletins = load_class_bulletins(app, &class, &choice)?;
+
+            if bulletins.is_empty() {
+                println!("\n✗ Aucun étudiant dans cette classe.\n");
+            } else {
+                let (subjects, notes) = load_class_data(app, &class, &choice)?;
+                let report = html::class_report(&class, &choice.label(), &bulletins, &subjects, &notes);
+                let path = reports::export_path(&format!("classe_{}.html", class))?;
+                std::fs::write(&path, report)?;
+                println!("\n✓ Rapport HTML généré: {}\n", path.display());
+            }
+        }
+        "4" => {
+            let class = prompt("\nClasse: ")?;
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+            let averages = app.grading_service.class_averages(&class, choice.scope())?;
+
+            if averages.is_empty() {
+                println!("\n✗ Aucun étudiant dans cette classe.\n");
+            } else {
+                let entries = ranking::merit_list(&averages, &app.grading_service.rules()?, choice.scope());
+                println!("\n=== Classement de la classe {} — {} ===", class, choice.label());
+                crate::views::display_merit_list(&entries);
+
+                if prompt("\nExporter en CSV ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                    let path = reports::export_path(&format!("classement_{}.csv", class))?;
+                    std::fs::write(&path, csv::merit_list(&entries))?;
+                    println!("\n✓ Classement exporté: {}\n", path.display());
+                }
+            }
+        }
+        "5" => {
+            let code = prompt("\nCode de la matière: ")?;
+
+            match app.subject_repo.get(&code)? {
+                Some(subject) => {
+                    let Some(choice) = periods::choose_period(app, "année complète")? else {
+                        return Ok(());
+                    };
+                    let averages = app.grading_service.class_averages(&subject.class, choice.scope())?;
+                    let entries = ranking::subject_ranking(&subject.code, &averages, &app.grading_service.rules()?);
+                    println!("\n=== Classement en {} ({}) — {} ===", subject.name, subject.class, choice.label());
+                    crate::views::display_merit_list(&entries);
+
+                    if prompt("\nExporter en CSV ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                        let path = reports::export_path(&format!("classement_{}.csv", subject.code))?;
+                        std::fs::write(&path, csv::merit_list(&entries))?;
+                        println!("\n✓ Classement exporté: {}\n", path.display());
+                    }
+                }
+                None => {
+                    println!("\n✗ Matière non trouvée.\n");
+                }
+            }
+        }
+        "6" => {
+            let class = prompt("\nClasse: ")?;
+            let students = app.student_repo.filter(
+                &format!("{} ORDER BY name, surname", ENROLLED_IN_CURRENT_YEAR),
+                &[&class],
+            )?;
+
+            if students.is_empty() {
+                println!("\n✗ Aucun étudiant inscrit dans cette classe.\n");
+            } else {
+                let list = csv::class_list(
+                    &students,
+                    &app.profile_repo.get_profiles()?,
+                    &app.profile_repo.get_all_guardians()?,
+                );
+                let path = reports::export_path(&format!("liste_{}.csv", class))?;
+                std::fs::write(&path, list)?;
+                println!("\n✓ {} étudiants exportés: {}\n", students.len(), path.display());
+            }
+        }
+        "7" => {
+            let class = prompt("\nClasse: ")?;
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+            let (subjects, notes) = load_class_data(app, &class, &choice)?;
+
+            if subjects.is_empty() {
+                println!("\n✗ Aucune matière dans cette classe.\n");
+                return Ok(());
+            }
+
+            let statistics = stats::by_subject(subjects, &notes);
+            println!("\n=== Statistiques des matières de {} — {} ===", class, choice.label());
+            crate::views::display_subject_statistics(&statistics);
+            println!("\nDispersion des notes :");
+            crate::views::display_box_plots(&statistics);
+
+            let class_averages = app.grading_service.class_averages(&class, choice.scope())?;
+            let subject_averages: Vec<(Subject, Option<f64>)> = statistics
+                .iter()
+                .map(|(subject, _)| {
+                    let averages: Vec<f64> = class_averages
+                        .iter()
+                        .filter_map(|(_, average)| {
+                            average.subjects.iter().find(|s| s.subject_code == subject.code)?.average
+                        })
+                        .collect();
+                    (subject.clone(), grading::mean(&averages))
+                })
+                .collect();
+            println!("\nMoyenne de la classe par matière :");
+            crate::views::display_subject_average_bars(&subject_averages);
+
+            let averages: Vec<f64> = class_averages
+                .iter()
+                .filter_map(|(_, average)| average.average)
+                .collect();
+            if let Some(summary) = stats::summarize(&averages) {
+                println!(
+                    "\nMoyennes générales : {} étudiants, moyenne {:.2}, médiane {:.2}, écart-type {:.2}, réussite {:.0}%",
+                    summary.count, summary.mean, summary.median, summary.std_dev, summary.pass_rate
+                );
+                crate::views::display_distribution(&summary.buckets);
+            }
+
+            if prompt("\nExporter en CSV ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                let path = reports::export_path(&format!("statistiques_{}.csv", class))?;
+                std::fs::write(&path, csv::subject_statistics(&statistics))?;
+                println!("\n✓ Statistiques exportées: {}\n", path.display());
+            }
+        }
+        "8" => {
+            let class = prompt("\nClasse: ")?;
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+            let averages = app.grading_service.class_averages(&class, choice.scope())?;
+
+            if averages.is_empty() {
+                println!("\n✗ Aucun étudiant dans cette classe.\n");
+                return Ok(());
+            }
+
+            let previous = previous_term_averages(app, &class, &choice)?;
+            let (from, to) = choice.dates();
+            let data = council::CouncilData {
+                averages: &averages,
+                previous: previous.as_deref(),
+                max_drop: app.grading_service.warning_thresholds()?.max_drop,
+                scope: choice.scope(),
+                subjects: &app.subject_repo.filter("class = ?1", &[&class])?,
+                teachers: &app.teacher_repo.get_subject_teachers()?,
+                attendance: &app.absence_repo.get_totals(from, to)?,
+            };
+            let report = council::build_council_report(
+                &class,
+                &choice.label(),
+                &data,
+                &app.grading_service.rules()?,
+                &app.grading_service.attendance_thresholds()?,
+            );
+
+            println!("\n=== Conseil de classe {} — {} ===", class, choice.label());
+            crate::views::display_council_report(&report);
+
+            if prompt("\nExporter en HTML ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                let path = reports::export_path(&format!("conseil_{}_{}.html", class, choice.label()))?;
+                std::fs::write(&path, html::council_report(&report))?;
+                println!("\n✓ Rapport du conseil exporté: {}\n", path.display());
+            }
+        }
+        "9" => {
+            let class = prompt("\nClasse (vide = toutes les classes): ")?;
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+            let classes = if class.is_empty() {
+                app.class_repo.get_summaries()?.into_iter().map(|summary| summary.class.code).collect()
+            } else {
+                vec![class]
+            };
+            let rules = app.grading_service.rules()?;
+            let thresholds = app.grading_service.warning_thresholds()?;
+
+            let mut flagged = Vec::new();
+            for class in &classes {
+                let averages = app.grading_service.class_averages(class, choice.scope())?;
+                let previous = previous_term_averages(app, class, &choice)?;
+                flagged.extend(warnings::detect(&averages, previous.as_deref(), &rules, &thresholds));
+            }
+            warnings::prioritise(&mut flagged);
+
+            if flagged.is_empty() {
+                println!("\n✓ Aucun étudiant signalé ({}).\n", thresholds);
+                return Ok(());
+            }
+
+            println!("\n=== Alertes précoces — {} ===", choice.label());
+            println!("Critères : {}", thresholds);
+            crate::views::display_early_warnings(&flagged);
+
+            if prompt("\nExporter en CSV ? (o/N): ")?.eq_ignore_ascii_case("o") {
+                let path = reports::export_path(&format!("alertes_{}.csv", choice.label()))?;
+                std::fs::write(&path, csv::early_warnings(&flagged))?;
+                println!("\n✓ {} alertes exportées: {}\n", flagged.len(), path.display());
+            }
+        }
+        "10" => {
+            let Some(level) = choose_level(app)? else {
+                return Ok(());
+            };
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+            let notes: Vec<_> = app
+                .note_repo
+                .get_by_level(&level)?
+                .into_iter()
+                .filter(|note| match choice.period_id() {
+                    Some(period_id) => note.period_id == period_id,
+                    None => note.academic_year_id == choice.year.id,
+                })
+                .collect();
+
+            if notes.is_empty() {
+                println!("\n✗ Aucune note pour ce niveau.\n");
+                return Ok(());
+            }
+
+            let comparisons = comparison::by_subject(&notes, |note| &note.class);
+            println!("\n=== Classes de {} — {} ===", level, choice.label());
+            crate::views::display_comparisons(&comparisons, "Classe");
+            export_comparisons(&comparisons, "Classe", &format!("comparaison_classes_{}.csv", level))?;
+        }
+        "11" => {
+            let Some(level) = choose_level(app)? else {
+                return Ok(());
+            };
+            let notes = app.note_repo.get_by_level(&level)?;
+
+            if notes.is_empty() {
+                println!("\n✗ Aucune note pour ce niveau.\n");
+                return Ok(());
+            }
+
+            let comparisons = comparison::by_subject(&notes, |note| &note.year_label);
+            println!("\n=== Niveau {} d'une année à l'autre ===", level);
+            crate::views::display_comparisons(&comparisons, "Année");
+            export_comparisons(&comparisons, "Année", &format!("comparaison_annees_{}.csv", level))?;
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
+
+/// Ask for a class level among those of the existing classes, `None`
+/// after printing an error if there is no such level
+fn choose_level(app: &App) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let mut levels: Vec<String> = Vec::new();
+    for summary in app.class_repo.get_summaries()? {
+        if !levels.contains(&summary.class.level) {
+            levels.push(summary.class.level);
+        }
+    }
+
+    println!("\nNiveaux : {}", levels.join(", "));
+    let level = prompt("Niveau: ")?;
+    if !levels.contains(&level) {
+        println!("\n✗ Niveau inconnu.\n");
+        return Ok(None);
+    }
+    Ok(Some(level))
+}
+
+/// Offer to export a comparison as CSV
+fn export_comparisons(
+    comparisons: &[comparison::SubjectComparison],
+    group_header: &str,
+    file_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if prompt("\nExporter en CSV ? (o/N): ")?.eq_ignore_ascii_case("o") {
+        let path = reports::export_path(file_name)?;
+        std::fs::write(&path, csv::comparisons(comparisons, group_header))?;
+        println!("\n✓ Comparaison exportée: {}\n", path.display());
+    }
+    Ok(())
+}
+
+/// Averages of the students of a class over the term before the chosen
+/// one, `None` for the first term or the whole year
+fn previous_term_averages(
+    app: &App,
+    class: &str,
+    choice: &PeriodChoice,
+) -> rusqlite::Result<Option<Vec<(Student, StudentAverage)>>> {
+    let Some(period) = &choice.period else {
+        return Ok(None);
+    };
+    let previous = app
+        .period_repo
+        .get_periods(period.academic_year_id)?
+        .into_iter()
+        .find(|p| p.number + 1 == period.number);
+
+    previous
+        .map(|previous| app.grading_service.class_averages(class, Scope::Period(previous.id)))
+        .transpose()
+}
+
+/// Load the subjects of a class and the notes of its students in the chosen period
+fn load_class_data(app: &App, class: &str, choice: &PeriodChoice) -> rusqlite::Result<(Vec<Subject>, Vec<Note>)> {
+    let subjects = app.subject_repo.filter("class = ?1", &[&class])?;
+    let notes = app.note_repo.filter(
+        "subject_code IN (SELECT code FROM subjects WHERE class = ?1) AND (?2 IS NULL OR period_id = ?2)",
+        &[&class, &choice.period_id()],
+    )?;
+
+    Ok((subjects, notes))
+}
+
+/// Build the bulletins of every student of a class for the chosen period
+fn load_class_bulletins(app: &App, class: &str, choice: &PeriodChoice) -> rusqlite::Result<Vec<bulletin::Bulletin>> {
+    let without_period = app.note_repo.filter(
+        "period_id IS NULL AND subject_code IN (SELECT code FROM subjects WHERE class = ?1)",
+        &[&class],
+    )?;
+    periods::warn_without_period(without_period.len());
+    let averages = app.grading_service.class_averages(class, choice.scope())?;
+    let subjects = app.subject_repo.filter("class = ?1", &[&class])?;
+    let teachers = app.teacher_repo.get_subject_teachers()?;
+    let (from, to) = choice.dates();
+    let attendance = app.absence_repo.get_totals(from, to)?;
+    let rules = app.grading_service.rules()?;
+    let mut bulletins = bulletin::build_class_bulletins(
+        &averages,
+        &subjects,
+        &teachers,
+        &attendance,
+        &rules,
+        choice.scope(),
+        &choice.label(),
+    );
+    bulletin::attach_profiles(
+        &mut bulletins,
+        &mut app.profile_repo.get_profiles()?,
+        &mut app.profile_repo.get_all_guardians()?,
+    );
+    Ok(bulletins)
+}
@@ -0,0 +1,291 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::prompt;
+use crate::grading::MissingNotePolicy;
+use crate::grading::attendance::AttendanceThresholds;
+use crate::grading::rules::KeySubjectRule;
+use crate::grading::scale::{GradeScale, ScaleStep};
+use crate::students::matricule::MatriculePattern;
+use crate::views;
+
+/// Settings sub-menu, showing the current configuration
+pub fn show_settings_menu(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    let policy = app.grading_service.policy()?;
+    let scale = app.grading_service.scale()?;
+    let thresholds = app.grading_service.attendance_thresholds()?;
+    let pattern = app.matricule_pattern()?;
+    let warnings = app.grading_service.warning_thresholds()?;
+    let free_edit_days = app.free_edit_days()?;
+    let retention_days = app.retention_days()?;
+
+    println!("\n=== Paramètres ===");
+    println!("1.  Notes manquantes (actuellement: {})", policy.missing_notes);
+    println!("2.  Afficher les mentions et règles de passage");
+    println!("3.  Définir une mention");
+    println!("4.  Supprimer une mention");
+    println!("5.  Modifier les seuils de passage");
+    println!("6.  Définir une matière clé");
+    println!("7.  Supprimer une matière clé");
+    println!("8.  Échelle de notation (actuellement: {})", scale);
+    println!("9.  Contrôler les notes et coefficients hors limites");
+    println!("10. Seuils d'alerte d'assiduité (actuellement: {})", thresholds);
+    println!("11. Format des matricules (actuellement: {})", pattern);
+    println!("12. Seuils des alertes précoces (actuellement: {})", warnings);
+    println!("13. Délai de correction des notes sans motif (actuellement: {} jours)", free_edit_days);
+    println!("14. Durée de conservation des archives (actuellement: {} jours)", retention_days);
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush()?;
+    Ok(())
+}
+
+/// Handle settings sub-menu commands
+pub fn handle_settings_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            println!("\nLes matières sans note doivent être:");
+            println!("1.  {}", MissingNotePolicy::Exclude);
+            println!("2.  {}", MissingNotePolicy::CountAsZero);
+
+            let policy = match prompt("\nChoisissez une option: ")?.as_str() {
+                "1" => MissingNotePolicy::Exclude,
+                "2" => MissingNotePolicy::CountAsZero,
+                _ => {
+                    println!("\n✗ Option invalide.\n");
+                    return Ok(());
+                }
+            };
+
+            app.grading_service.set_missing_notes_policy(policy)?;
+            println!("\n✓ Notes manquantes {}.\n", policy);
+        }
+        "2" => {
+            views::display_grading_rules(&app.grading_service.rules()?);
+        }
+        "3" => {
+            let label = prompt("Libellé de la mention: ")?;
+            let Some(min_average) = prompt_average("Moyenne minimale: ")? else {
+                return Ok(());
+            };
+
+            if label.is_empty() {
+                println!("\n✗ Le libellé est obligatoire.\n");
+            } else {
+                app.rules_repo.set_mention_rule(&label, min_average)?;
+                println!("\n✓ Mention {} à partir de {:.2}.\n", label, min_average);
+            }
+        }
+        "4" => {
+            let label = prompt("Libellé de la mention: ")?;
+            app.rules_repo.delete_mention_rule(&label)?;
+            println!("\n✓ Mention supprimée.\n");
+        }
+        "5" => {
+            let mut rules = app.rules_repo.get_decision_rules()?;
+            println!("\nLaissez vide pour conserver la valeur actuelle.");
+
+            let fields = [
+                ("Moyenne d'admission", &mut rules.admission_average),
+                ("Moyenne d'exclusion", &mut rules.exclusion_average),
+                ("Matière en échec sous", &mut rules.failing_subject_average),
+            ];
+            for (label, value) in fields {
+                let input = prompt(&format!("{} ({:.2}): ", label, value))?;
+                if input.is_empty() {
+                    continue;
+                }
+                match parse_average(&input) {
+                    Some(parsed) => *value = parsed,
+                    None => {
+                        println!("\n✗ Moyenne invalide (0 à 20).\n");
+                        return Ok(());
+                    }
+                }
+            }
+
+            let input = prompt(&format!("Matières en échec tolérées ({}): ", rules.max_failed_subjects))?;
+            if !input.is_empty() {
+                match input.parse() {
+                    Ok(count) => rules.max_failed_subjects = count,
+                    Err(_) => {
+                        println!("\n✗ Nombre invalide.\n");
+                        return Ok(());
+                    }
+                }
+            }
+
+            if rules.exclusion_average > rules.admission_average {
+                println!("\n✗ La moyenne d'exclusion doit être inférieure à la moyenne d'admission.\n");
+                return Ok(());
+            }
+
+            app.rules_repo.update_decision_rules(&rules)?;
+            println!("\n✓ Règles de passage mises à jour.\n");
+        }
+        "6" => {
+            let subject_prefix = prompt("Préfixe du code matière (ex: MATH): ")?.to_uppercase();
+            let Some(min_average) = prompt_average("Moyenne minimale: ")? else {
+                return Ok(());
+            };
+
+            if subject_prefix.is_empty() {
+                println!("\n✗ Le préfixe est obligatoire.\n");
+            } else {
+                app.rules_repo.set_key_subject_rule(&KeySubjectRule { subject_prefix, min_average })?;
+                println!("\n✓ Matière clé enregistrée.\n");
+            }
+        }
+        "7" => {
+            let subject_prefix = prompt("Préfixe du code matière: ")?.to_uppercase();
+            app.rules_repo.delete_key_subject_rule(&subject_prefix)?;
+            println!("\n✓ Matière clé supprimée.\n");
+        }
+        "8" => {
+            let Some(min) = prompt_average("Note minimale: ")? else {
+                return Ok(());
+            };
+            let Some(max) = prompt_average("Note maximale: ")? else {
+                return Ok(());
+            };
+            println!("\nPrécision des notes:");
+            println!("1.  {}", ScaleStep::Any);
+            println!("2.  {}", ScaleStep::HalfPoint);
+            println!("3.  {}", ScaleStep::QuarterPoint);
+            let step = match prompt("\nChoisissez une option: ")?.as_str() {
+                "1" => ScaleStep::Any,
+                "2" => ScaleStep::HalfPoint,
+                "3" => ScaleStep::QuarterPoint,
+                _ => {
+                    println!("\n✗ Option invalide.\n");
+                    return Ok(());
+                }
+            };
+
+            match GradeScale::new(min, max, step) {
+                Some(scale) => {
+                    app.grading_service.set_scale(&scale)?;
+                    println!("\n✓ Notes {}.\n", scale);
+                }
+                None => println!("\n✗ La note minimale doit être inférieure à la note maximale.\n"),
+            }
+        }
+        "9" => {
+            let scale = app.grading_service.scale()?;
+            let notes = app.note_repo.get_out_of_scale(&scale)?;
+            let coefficients = app.subject_repo.get_invalid_coefficients()?;
+
+            if notes.is_empty() && coefficients.is_empty() {
+                println!("\n✓ Toutes les notes et tous les coefficients sont valides.\n");
+                return Ok(());
+            }
+            if !notes.is_empty() {
+                println!("\n✗ {} notes hors de l'échelle ({}):", notes.len(), scale);
+                views::display_invalid_notes(&notes);
+            }
+            for (code, name, coefficient) in &coefficients {
+                println!("✗ Matière {} ({}) : coefficient {} hors de 1 à 255", code, name, coefficient);
+            }
+            println!();
+        }
+        "10" => {
+            let hours = prompt("\nHeures d'absence non justifiées tolérées par période: ")?;
+            let lateness = prompt("Retards tolérés par période: ")?;
+            let (Ok(max_unjustified_hours), Ok(max_lateness)) = (hours.parse::<u32>(), lateness.parse::<u32>()) else {
+                println!("\n✗ Nombre invalide.\n");
+                return Ok(());
+            };
+
+            let thresholds = AttendanceThresholds { max_unjustified_hours, max_lateness };
+            app.grading_service.set_attendance_thresholds(&thresholds)?;
+            println!("\n✓ Alerte au-delà de {}.\n", thresholds);
+        }
+        "11" => {
+            println!("\nÉléments : {{YEAR}} année, {{YY}} année sur deux chiffres, {{LEVEL}} niveau de la classe,");
+            println!("{{SEQ:n}} numéro sur n chiffres, {{CHECK}} chiffre de contrôle");
+            let input = prompt("Nouveau format (ex. {YEAR}{LEVEL}{SEQ:4}{CHECK}): ")?;
+
+            match MatriculePattern::parse(&input) {
+                Ok(pattern) => {
+                    app.set_matricule_pattern(&pattern)?;
+                    println!("\n✓ Format enregistré, exemple: {}\n", pattern.format("2025", "6ème", 1));
+                }
+                Err(e) => println!("\n✗ Format invalide: {}\n", e),
+            }
+        }
+        "12" => {
+            let mut thresholds = app.grading_service.warning_thresholds()?;
+            println!("\nLaissez vide pour conserver la valeur actuelle.");
+
+            let input = prompt(&format!("Baisse de moyenne tolérée entre deux périodes ({:.2}): ", thresholds.max_drop))?;
+            if !input.is_empty() {
+                let Some(max_drop) = parse_average(&input) else {
+                    println!("\n✗ Baisse invalide (0 à 20).\n");
+                    return Ok(());
+                };
+                thresholds.max_drop = max_drop;
+            }
+            let input = prompt(&format!("Matières clés en échec déclenchant une alerte ({}): ", thresholds.core_failures))?;
+            if !input.is_empty() {
+                let Ok(core_failures) = input.parse() else {
+                    println!("\n✗ Nombre invalide.\n");
+                    return Ok(());
+                };
+                thresholds.core_failures = core_failures;
+            }
+            let input = prompt(&format!("Écarts-types sous la moyenne de la classe ({:.2}): ", thresholds.outlier_z_score))?;
+            if !input.is_empty() {
+                match input.replace(',', ".").parse::<f64>() {
+                    Ok(z_score) if z_score > 0.0 => thresholds.outlier_z_score = z_score,
+                    _ => {
+                        println!("\n✗ Nombre d'écarts-types invalide (supérieur à 0).\n");
+                        return Ok(());
+                    }
+                }
+            }
+
+            app.grading_service.set_warning_thresholds(&thresholds)?;
+            println!("\n✓ Alerte en cas de {}.\n", thresholds);
+        }
+        "13" => {
+            let Ok(days) = prompt("\nNombre de jours pendant lesquels une note peut être corrigée sans motif: ")?.parse::<u32>() else {
+                println!("\n✗ Nombre de jours invalide.\n");
+                return Ok(());
+            };
+
+            app.set_free_edit_days(days)?;
+            println!("\n✓ Un motif sera demandé pour corriger une note enregistrée il y a plus de {} jours.\n", days);
+        }
+        "14" => {
+            let Ok(days) = prompt("\nNombre de jours de conservation des éléments archivés: ")?.parse::<u32>() else {
+                println!("\n✗ Nombre de jours invalide.\n");
+                return Ok(());
+            };
+
+            app.set_retention_days(days)?;
+            println!("\n✓ Les éléments archivés depuis plus de {} jours pourront être purgés.\n", days);
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
+
+/// Average between 0 and 20
+fn parse_average(input: &str) -> Option<f64> {
+    input
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|value| (0.0..=20.0).contains(value))
+}
+
+fn prompt_average(label: &str) -> io::Result<Option<f64>> {
+    let average = parse_average(&prompt(label)?);
+    if average.is_none() {
+        println!("\n✗ Moyenne invalide (0 à 20).\n");
+    }
+    Ok(average)
+}
@@ -0,0 +1,243 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::{archives, optional, prompt};
+use crate::db::repositories::archive_repository::Archivable;
+use crate::db::repositories::profile_repository::{Gender, Guardian, StudentProfile};
+use crate::db::repositories::repository::Repository;
+use crate::db::repositories::student_repository::Student;
+use crate::students::import;
+use crate::views;
+
+/// Students sub-menu: registration, profiles and guardians
+pub fn show_students_menu() {
+    println!("\n=== Gestion des étudiants ===");
+    println!("1.  Voir la fiche d'un étudiant");
+    println!("2.  Modifier la fiche d'un étudiant");
+    println!("3.  Ajouter un responsable");
+    println!("4.  Modifier un responsable");
+    println!("5.  Supprimer un responsable");
+    println!("6.  Rechercher par responsable (nom ou téléphone)");
+    println!("7.  Inscrire un nouvel étudiant");
+    println!("8.  Importer des étudiants (CSV)");
+    println!("9.  Supprimer un étudiant");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Ask for a new value of a field: empty keeps `current`, "-" clears it
+fn edit(label: &str, current: &Option<String>) -> io::Result<Option<String>> {
+    let input = prompt(&format!("{} [{}]: ", label, current.as_deref().unwrap_or("-")))?;
+    Ok(match input.as_str() {
+        "" => current.clone(),
+        "-" => None,
+        _ => Some(input),
+    })
+}
+
+/// Ask for a student by matricule, `None` if there is no such student
+fn choose_student(app: &App) -> Result<Option<Student>, Box<dyn std::error::Error>> {
+    let matricule = prompt("\nMatricule de l'étudiant: ")?;
+    let student = app.student_repo.get(&matricule)?;
+    if student.is_none() {
+        println!("\n✗ Étudiant non trouvé.\n");
+    }
+    Ok(student)
+}
+
+/// Ask for one of the guardians of `student`, `None` if they have no such guardian
+fn choose_guardian(app: &App, student: &Student) -> Result<Option<Guardian>, Box<dyn std::error::Error>> {
+    let guardians = app.profile_repo.get_guardians(&student.matricule)?;
+    if guardians.is_empty() {
+        println!("\n✗ Aucun responsable pour {} {}.\n", student.name, student.surname);
+        return Ok(None);
+    }
+
+    views::display_guardians(&guardians);
+    let guardian = prompt("\nID du responsable: ")?
+        .parse::<i64>()
+        .ok()
+        .and_then(|id| guardians.into_iter().find(|guardian| guardian.id == id));
+    if guardian.is_none() {
+        println!("\n✗ Responsable non trouvé.\n");
+    }
+    Ok(guardian)
+}
+
+/// Handle students sub-menu commands
+pub fn handle_students_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let Some(student) = choose_student(app)? else {
+                return Ok(());
+            };
+
+            println!("\n=== Fiche de {} {} ({}) ===", student.name, student.surname, student.class);
+            views::display_profile(app.profile_repo.get_profile(&student.matricule)?.as_ref());
+            let guardians = app.profile_repo.get_guardians(&student.matricule)?;
+            if guardians.is_empty() {
+                println!("Aucun responsable enregistré.\n");
+            } else {
+                println!("\nResponsables :");
+                views::display_guardians(&guardians);
+                println!();
+            }
+        }
+        "2" => {
+            let Some(student) = choose_student(app)? else {
+                return Ok(());
+            };
+            let mut profile = app
+                .profile_repo
+                .get_profile(&student.matricule)?
+                .unwrap_or_else(|| StudentProfile::new(&student.matricule));
+
+            println!("\nVide = inchangé, \"-\" = effacer");
+            let gender = edit("Sexe (M/F)", &profile.gender.map(|g| g.as_str().to_string()))?;
+            profile.gender = match gender {
+                Some(gender) => match Gender::parse(&gender.to_uppercase()) {
+                    Some(gender) => Some(gender),
+                    None => {
+                        println!("\n✗ Sexe invalide (M ou F).\n");
+                        return Ok(());
+                    }
+                },
+                None => None,
+            };
+            profile.birth_date = edit("Date de naissance (AAAA-MM-JJ)", &profile.birth_date)?;
+            profile.birth_place = edit("Lieu de naissance", &profile.birth_place)?;
+            profile.address = edit("Adresse", &profile.address)?;
+            profile.photo_path = edit("Chemin de la photo", &profile.photo_path)?;
+
+            match app.profile_repo.save_profile(&profile) {
+                Ok(()) => println!("\n✓ Fiche de {} {} enregistrée.\n", student.name, student.surname),
+                Err(e) => println!("\n✗ Fiche invalide: {}\n", e),
+            }
+        }
+        "3" => {
+            let Some(student) = choose_student(app)? else {
+                return Ok(());
+            };
+            let name = prompt("Nom du responsable: ")?;
+            let relation = prompt("Lien (père, mère, tuteur...): ")?;
+            let phone = optional(prompt("Téléphone (vide = aucun): ")?);
+            let email = optional(prompt("Email (vide = aucun): ")?);
+
+            if name.is_empty() || relation.is_empty() {
+                println!("\n✗ Le nom et le lien sont obligatoires.\n");
+                return Ok(());
+            }
+
+            let guardian = Guardian { id: 0, matricule: student.matricule, name, relation, phone, email };
+            app.profile_repo.add_guardian(&guardian)?;
+            println!("\n✓ Responsable {} ajouté.\n", guardian);
+        }
+        "4" => {
+            let Some(student) = choose_student(app)? else {
+                return Ok(());
+            };
+            let Some(mut guardian) = choose_guardian(app, &student)? else {
+                return Ok(());
+            };
+
+            println!("\nVide = inchangé, \"-\" = effacer");
+            if let Some(name) = optional(prompt(&format!("Nom [{}]: ", guardian.name))?) {
+                guardian.name = name;
+            }
+            if let Some(relation) = optional(prompt(&format!("Lien [{}]: ", guardian.relation))?) {
+                guardian.relation = relation;
+            }
+            guardian.phone = edit("Téléphone", &guardian.phone)?;
+            guardian.email = edit("Email", &guardian.email)?;
+
+            app.profile_repo.update_guardian(&guardian)?;
+            println!("\n✓ Responsable {} modifié.\n", guardian);
+        }
+        "5" => {
+            let Some(student) = choose_student(app)? else {
+                return Ok(());
+            };
+            let Some(guardian) = choose_guardian(app, &student)? else {
+                return Ok(());
+            };
+
+            app.profile_repo.delete_guardian(&student.matricule, guardian.id)?;
+            println!("\n✓ Responsable {} supprimé.\n", guardian);
+        }
+        "6" => {
+            let query = prompt("\nNom ou téléphone du responsable: ")?;
+            if query.is_empty() {
+                println!("\n✗ Recherche vide.\n");
+                return Ok(());
+            }
+
+            let matches = app.profile_repo.search_by_guardian(&query)?;
+            if matches.is_empty() {
+                println!("\nAucun étudiant trouvé.\n");
+            } else {
+                println!("\n=== Étudiants dont un responsable correspond à « {} » ===", query);
+                views::display_guardian_matches(&matches);
+                println!();
+            }
+        }
+        "7" => {
+            let pattern = app.matricule_pattern()?;
+            let name = prompt("\nNom: ")?;
+            let surname = prompt("Prénom: ")?;
+            let class = prompt("Classe: ")?;
+            let matricule = prompt(&format!("Matricule (format {}, vide = généré): ", pattern))?;
+
+            if name.is_empty() || surname.is_empty() {
+                println!("\n✗ Le nom et le prénom sont obligatoires.\n");
+                return Ok(());
+            }
+
+            let student = Student { matricule, name, surname, class };
+            match app.student_repo.register(&student, &pattern) {
+                Ok(student) => println!(
+                    "\n✓ {} {} inscrit en {} avec le matricule {}.\n",
+                    student.name, student.surname, student.class, student.matricule
+                ),
+                Err(e) => println!("\n✗ Inscription impossible: {}\n", e),
+            }
+        }
+        "8" => {
+            println!("\nPremière ligne : Matricule;Nom;Prénom;Classe (matricule facultatif, vide = généré)");
+            let path = prompt("Chemin du fichier CSV: ")?;
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    println!("\n✗ Lecture impossible: {}\n", e);
+                    return Ok(());
+                }
+            };
+            let rows = match import::parse_students(&content) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    println!("\n✗ Fichier invalide: {}\n", e);
+                    return Ok(());
+                }
+            };
+
+            match app.student_repo.import(&rows, &app.matricule_pattern()?) {
+                Ok(students) => println!("\n✓ {} étudiants importés.\n", students.len()),
+                Err(e) => println!("\n✗ Import annulé, {}\n", e),
+            }
+        }
+        "9" => {
+            let matricule = prompt("\nMatricule de l'étudiant: ")?;
+            let Some(student) = app.student_repo.with_archived().get(&matricule)? else {
+                println!("\n✗ Étudiant non trouvé.\n");
+                return Ok(());
+            };
+            views::display_students(std::slice::from_ref(&student));
+            archives::delete(app, Archivable::Student(student.matricule))?;
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::io::{self, Write};
+use crate::app::App;
+use crate::cli::{optional, periods, prompt};
+use crate::db::repositories::repository::Repository;
+use crate::db::repositories::teacher_repository::Teacher;
+use crate::grading::stats;
+use crate::views;
+
+/// Teachers sub-menu
+pub fn show_teachers_menu() {
+    println!("\n=== Enseignants ===");
+    println!("1.  Voir les enseignants et leurs matières");
+    println!("2.  Ajouter un enseignant");
+    println!("3.  Affecter une matière à un enseignant");
+    println!("4.  Retirer l'enseignant d'une matière");
+    println!("5.  Voir les notes d'un enseignant");
+    println!("6.  Statistiques d'un enseignant");
+    println!("0.  Retour");
+    print!("\nChoisissez une option: ");
+    io::stdout().flush().unwrap();
+}
+
+/// Ask for a teacher by id, `None` if there is no such teacher
+fn choose_teacher(app: &App) -> Result<Option<Teacher>, Box<dyn std::error::Error>> {
+    let id = prompt("\nID de l'enseignant: ")?;
+    let teacher = app.teacher_repo.get(&id)?;
+    if teacher.is_none() {
+        println!("\n✗ Enseignant non trouvé.\n");
+    }
+    Ok(teacher)
+}
+
+/// Notes given in the subjects taught by `teacher`
+const TEACHER_NOTES_FILTER: &str =
+    "subject_code IN (SELECT subject_code FROM teaching_assignments WHERE teacher_id = ?1)
+     AND (?2 IS NULL OR period_id = ?2)";
+
+/// Handle teachers sub-menu commands
+pub fn handle_teachers_command(app: &mut App, choice: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match choice {
+        "1" => {
+            let teachers = app.teacher_repo.get_all()?;
+            if teachers.is_empty() {
+                println!("\nAucun enseignant trouvé.\n");
+            } else {
+                println!("\n=== Enseignants ===");
+                views::display_teachers(&teachers, &app.teacher_repo.get_subject_teachers()?);
+                println!();
+            }
+        }
+        "2" => {
+            let name = prompt("\nNom: ")?;
+            let surname = prompt("Prénom: ")?;
+            let email = optional(prompt("Email (vide = aucun): ")?);
+            let phone = optional(prompt("Téléphone (vide = aucun): ")?);
+
+            if name.is_empty() || surname.is_empty() {
+                println!("\n✗ Le nom et le prénom sont obligatoires.\n");
+                return Ok(());
+            }
+
+            let teacher = Teacher { id: 0, name, surname, email, phone };
+            app.teacher_repo.create(&teacher)?;
+            println!("\n✓ Enseignant {} ajouté.\n", teacher);
+        }
+        "3" => {
+            let Some(teacher) = choose_teacher(app)? else {
+                return Ok(());
+            };
+            let code = prompt("Code de la matière: ")?;
+            let Some(subject) = app.subject_repo.get(&code)? else {
+                println!("\n✗ Matière non trouvée.\n");
+                return Ok(());
+            };
+
+            app.teacher_repo.assign(&subject.code, teacher.id)?;
+            println!("\n✓ {} enseigne {} en {}.\n", teacher, subject.name, subject.class);
+        }
+        "4" => {
+            let code = prompt("\nCode de la matière: ")?;
+            if !app.teacher_repo.get_subject_teachers()?.contains_key(&code) {
+                println!("\n✗ Aucun enseignant n'est affecté à cette matière.\n");
+                return Ok(());
+            }
+
+            app.teacher_repo.unassign(&code)?;
+            println!("\n✓ La matière {} n'a plus d'enseignant.\n", code);
+        }
+        "5" => {
+            let Some(teacher) = choose_teacher(app)? else {
+                return Ok(());
+            };
+            let Some(choice) = periods::choose_period(app, "toutes les périodes")? else {
+                return Ok(());
+            };
+
+            let notes = app.note_repo.filter(TEACHER_NOTES_FILTER, &[&teacher.id, &choice.period_id()])?;
+            let label = match &choice.period {
+                Some(_) => choice.label(),
+                None => "toutes les périodes".to_string(),
+            };
+            println!("\n=== Notes de {} — {} ===", teacher, label);
+            if notes.is_empty() {
+                println!("Aucune note trouvée.\n");
+            } else {
+                views::display_notes(&notes);
+                println!();
+            }
+        }
+        "6" => {
+            let Some(teacher) = choose_teacher(app)? else {
+                return Ok(());
+            };
+            let Some(choice) = periods::choose_period(app, "année complète")? else {
+                return Ok(());
+            };
+
+            let subjects = app.subject_repo.filter(
+                "code IN (SELECT subject_code FROM teaching_assignments WHERE teacher_id = ?1) ORDER BY class, code",
+                &[&teacher.id],
+            )?;
+            if subjects.is_empty() {
+                println!("\n✗ {} n'enseigne aucune matière.\n", teacher);
+                return Ok(());
+            }
+
+            let notes = app.note_repo.filter(TEACHER_NOTES_FILTER, &[&teacher.id, &choice.period_id()])?;
+            let statistics = stats::by_subject(subjects, &notes);
+
+            println!("\n=== Statistiques de {} — {} ===", teacher, choice.label());
+            views::display_subject_statistics(&statistics);
+            println!();
+        }
+        "0" => {}
+        _ => {
+            println!("\n✗ Option invalide.\n");
+        }
+    }
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
-use rusqlite::{Connection, Error as SqliteError};
+use rusqlite::{Connection, Error as SqliteError, OptionalExtension};
 
 #[derive(Debug)]
 pub enum ScriptExecutionError {
@@ -11,6 +11,7 @@ pub enum ScriptExecutionError {
     TransactionBeginFailed(SqliteError),
     ScriptExecutionFailed(SqliteError),
     TransactionCommitFailed(SqliteError),
+    ForeignKeyCheckFailed(String),
 }
 
 impl fmt::Display for ScriptExecutionError {
@@ -28,6 +29,8 @@ impl fmt::Display for ScriptExecutionError {
                 write!(f, "SQL script execution failed: {e}"),
             Self::TransactionCommitFailed(e) =>
                 write!(f, "Failed to commit transaction: {e}"),
+            Self::ForeignKeyCheckFailed(table) =>
+                write!(f, "Foreign key violation in table {table}"),
         }
     }
 }
@@ -46,6 +49,10 @@ fn validate_migration_header(script: &str) -> bool {
 /// - Runs inside a transaction
 /// - Supports multiple SQL statements
 /// - Fails atomically on error
+///
+/// Only the tests load scripts this way (sql/schema.sql); migrations go
+/// through `execute_migration_script`.
+#[cfg(test)]
 pub fn execute_sql_script(
     conn_ref: Rc<RefCell<Connection>>,
     script: &str,
@@ -84,3 +91,65 @@ pub fn execute_sql_script(
     Ok(())
 }
 
+
+/// Executes a migration script with foreign keys disabled.
+///
+/// Rebuilding a table (create, copy, drop, rename) is impossible with
+/// foreign keys enforced once other tables reference it, so enforcement
+/// is suspended while the script runs, as recommended by SQLite.
+/// `PRAGMA foreign_key_check` runs before the commit: the migration is
+/// rolled back if it leaves a dangling reference.
+/// The schema version becomes `version` in the same transaction, so a
+/// migration is never recorded without being applied, nor the reverse.
+pub fn execute_migration_script(
+    conn_ref: Rc<RefCell<Connection>>,
+    script: &str,
+    version: usize,
+) -> Result<(), ScriptExecutionError> {
+    let trimmed = script.trim();
+
+    // ---- Validation ----
+    if trimmed.is_empty() {
+        return Err(ScriptExecutionError::EmptyScript);
+    }
+
+    if !validate_migration_header(trimmed) {
+        return Err(ScriptExecutionError::MissingMigrationHeader);
+    }
+
+    let mut conn = conn_ref.borrow_mut();
+
+    // Cannot be changed inside a transaction
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")
+        .map_err(ScriptExecutionError::ForeignKeyPragmaFailed)?;
+
+    let result = (|| {
+        let tx = conn
+            .transaction()
+            .map_err(ScriptExecutionError::TransactionBeginFailed)?;
+
+        tx.execute_batch(trimmed)
+            .map_err(ScriptExecutionError::ScriptExecutionFailed)?;
+
+        let violation: Option<String> = tx
+            .query_row("PRAGMA foreign_key_check", [], |row| row.get(0))
+            .optional()
+            .map_err(ScriptExecutionError::ScriptExecutionFailed)?;
+        if let Some(table) = violation {
+            return Err(ScriptExecutionError::ForeignKeyCheckFailed(table));
+        }
+
+        tx.execute_batch(&format!("PRAGMA user_version = {};", version))
+            .map_err(ScriptExecutionError::ScriptExecutionFailed)?;
+
+        tx.commit()
+            .map_err(ScriptExecutionError::TransactionCommitFailed)
+    })();
+
+    // ---- SQLite guarantees ----
+    // A migration may suspend CHECK constraints to keep legacy values
+    conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA ignore_check_constraints = OFF;")
+        .map_err(ScriptExecutionError::ForeignKeyPragmaFailed)?;
+
+    result
+}
@@ -1,7 +1,11 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 use rusqlite::Connection;
-use crate::db::executor::execute_sql_script;
+use crate::db::executor::execute_migration_script;
+
+/// Nombre de migrations appliquées par les versions qui ne suivaient pas
+/// encore la version du schéma (001_init et 002_add_admin)
+const LEGACY_SCHEMA_VERSION: usize = 2;
 
 /// Vérifie si une table existe dans la base de données
 fn table_exists(conn: &Connection, table_name: &str) -> Result<bool, rusqlite::Error> {
@@ -13,26 +17,140 @@ fn table_exists(conn: &Connection, table_name: &str) -> Result<bool, rusqlite::E
     Ok(count > 0)
 }
 
-/// Exécute les migrations uniquement si les tables n'existent pas encore
+/// Lit la version du schéma (nombre de migrations déjà appliquées)
+fn schema_version(conn: &Connection) -> Result<usize, rusqlite::Error> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    Ok(version as usize)
+}
+
+fn set_schema_version(conn: &Connection, version: usize) -> Result<(), rusqlite::Error> {
+    conn.execute_batch(&format!("PRAGMA user_version = {};", version))
+}
+
+/// Exécute les migrations qui n'ont pas encore été appliquées.
+///
+/// La version du schéma est conservée dans `PRAGMA user_version` et vaut
+/// le nombre de migrations exécutées : seules les suivantes sont lancées.
 pub fn run_migrations(conn: Rc<RefCell<Connection>>, migrations: Vec<&str>) -> Result<(), Box<dyn std::error::Error>> {
-    // Vérifier si les tables principales existent déjà
-    let tables_exist = {
-        let conn_ref = conn.borrow();
-        table_exists(&conn_ref, "students")? 
-            && table_exists(&conn_ref, "subjects")? 
-            && table_exists(&conn_ref, "notes")?
-            && table_exists(&conn_ref, "admins")?
-    };
+    let mut version = schema_version(&conn.borrow())?;
+
+    // Base créée avant le suivi des versions : les tables initiales existent déjà
+    if version == 0 {
+        let tables_exist = {
+            let conn_ref = conn.borrow();
+            table_exists(&conn_ref, "students")?
+                && table_exists(&conn_ref, "subjects")?
+                && table_exists(&conn_ref, "notes")?
+                && table_exists(&conn_ref, "admins")?
+        };
 
-    if tables_exist {
-        // Les tables existent déjà, ne pas exécuter les migrations
-        return Ok(());
+        if tables_exist {
+            version = LEGACY_SCHEMA_VERSION;
+            set_schema_version(&conn.borrow(), version)?;
+        }
     }
 
-    // Exécuter les migrations
-    for migration in migrations {
-        execute_sql_script(conn.clone(), migration)?;
+    // Exécuter les migrations restantes
+    for (index, migration) in migrations.iter().enumerate().skip(version) {
+        execute_migration_script(conn.clone(), migration, index + 1)?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::executor::execute_sql_script;
+
+    fn migrations() -> Vec<&'static str> {
+        crate::db::MIGRATIONS.to_vec()
+    }
+
+    #[test]
+    fn test_fresh_database_runs_every_migration() {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+
+        run_migrations(conn.clone(), migrations()).unwrap();
+
+        assert_eq!(schema_version(&conn.borrow()).unwrap(), migrations().len());
+        assert!(table_exists(&conn.borrow(), "settings").unwrap());
+    }
+
+    #[test]
+    fn test_legacy_database_only_runs_new_migrations() {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        for migration in &migrations()[..LEGACY_SCHEMA_VERSION] {
+            execute_sql_script(conn.clone(), migration).unwrap();
+        }
+
+        run_migrations(conn.clone(), migrations()).unwrap();
+        // Running again is a no-op
+        run_migrations(conn.clone(), migrations()).unwrap();
+
+        assert_eq!(schema_version(&conn.borrow()).unwrap(), migrations().len());
+    }
+
+    #[test]
+    fn test_failed_migration_keeps_the_schema_version() {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        run_migrations(conn.clone(), migrations()[..3].to_vec()).unwrap();
+
+        let broken = "/* Migration Version : 004 | Created On : today | Author : test */
+                      CREATE TABLE broken (id INTEGER); INSERT INTO missing VALUES (1);";
+        let mut with_broken = migrations()[..3].to_vec();
+        with_broken.push(broken);
+        assert!(run_migrations(conn.clone(), with_broken).is_err());
+
+        assert_eq!(schema_version(&conn.borrow()).unwrap(), 3);
+        assert!(!table_exists(&conn.borrow(), "broken").unwrap());
+    }
+
+    #[test]
+    fn test_classes_are_built_from_existing_data() {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        for migration in &migrations()[..LEGACY_SCHEMA_VERSION] {
+            execute_sql_script(conn.clone(), migration).unwrap();
+        }
+        conn.borrow().execute_batch(
+            "INSERT INTO students (matricule, name, surname, class) VALUES ('S001', 'Alice', 'Smith', '6ème');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES ('MATH5', 'Mathématiques', '5ème', 4);
+             INSERT INTO notes (matricule, subject_code, value) VALUES ('S001', 'MATH5', 12);",
+        ).unwrap();
+
+        run_migrations(conn.clone(), migrations()).unwrap();
+
+        let conn = conn.borrow();
+        let classes: i64 = conn.query_row("SELECT COUNT(*) FROM classes WHERE academic_year_id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(classes, 2);
+        let notes: i64 = conn.query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0)).unwrap();
+        assert_eq!(notes, 1);
+
+        // Foreign keys are enforced again after the migrations
+        assert!(conn.execute(
+            "INSERT INTO students (matricule, name, surname, class) VALUES ('S002', 'Bob', 'Jones', '6eme')",
+            [],
+        ).is_err());
+    }
+
+    #[test]
+    fn test_legacy_values_are_kept_but_new_ones_checked() {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        for migration in &migrations()[..LEGACY_SCHEMA_VERSION] {
+            execute_sql_script(conn.clone(), migration).unwrap();
+        }
+        conn.borrow().execute_batch(
+            "INSERT INTO students (matricule, name, surname, class) VALUES ('S001', 'Alice', 'Smith', '6ème');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES ('MATH6', 'Mathématiques', '6ème', 0);
+             INSERT INTO notes (matricule, subject_code, value) VALUES ('S001', 'MATH6', 25);",
+        ).unwrap();
+
+        run_migrations(conn.clone(), migrations()).unwrap();
+
+        let conn = conn.borrow();
+        let value: f64 = conn.query_row("SELECT value FROM notes", [], |row| row.get(0)).unwrap();
+        assert_eq!(value, 25.0);
+        assert!(conn.execute("INSERT INTO notes (matricule, subject_code, value) VALUES ('S001', 'MATH6', 21)", []).is_err());
+        assert!(conn.execute("UPDATE subjects SET coefficient = 256", []).is_err());
+    }
+}
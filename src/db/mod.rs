@@ -8,14 +8,34 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use rusqlite::Connection;
 
+/// Migrations du schéma, dans l'ordre d'exécution
+pub const MIGRATIONS: &[&str] = &[
+    include_str!("../../sql/migrations/001_init.sql"), // use include str to make sure migration actually exists
+    include_str!("../../sql/migrations/002_add_admin.sql"), // include admins during migration
+    include_str!("../../sql/migrations/003_add_settings.sql"),
+    include_str!("../../sql/migrations/004_add_grading_rules.sql"),
+    include_str!("../../sql/migrations/005_add_periods.sql"),
+    include_str!("../../sql/migrations/006_add_evaluations.sql"),
+    include_str!("../../sql/migrations/007_add_classes.sql"),
+    include_str!("../../sql/migrations/008_add_note_class_check.sql"),
+    include_str!("../../sql/migrations/009_add_value_constraints.sql"),
+    include_str!("../../sql/migrations/010_add_teachers.sql"),
+    include_str!("../../sql/migrations/011_add_enrolments.sql"),
+    include_str!("../../sql/migrations/012_add_absences.sql"),
+    include_str!("../../sql/migrations/013_add_student_profiles.sql"),
+    include_str!("../../sql/migrations/014_add_matricule_sequences.sql"),
+    include_str!("../../sql/migrations/015_add_warning_thresholds.sql"),
+    include_str!("../../sql/migrations/016_add_moderations.sql"),
+    include_str!("../../sql/migrations/017_add_note_history.sql"),
+    include_str!("../../sql/migrations/018_add_period_locks.sql"),
+    include_str!("../../sql/migrations/019_add_archives.sql"),
+];
+
 pub fn init_database(path: &str) -> Result<Rc<RefCell<Connection>>, Box<dyn std::error::Error>> {
     let conn = Rc::new(RefCell::new(bootstrap::open_database(path)?));
     
     // Exécuter les migrations si nécessaire
-    migrate::run_migrations(conn.clone(), vec![
-        include_str!("../../sql/migrations/001_init.sql"), // use include str to make sure migration actually exists
-        include_str!("../../sql/migrations/002_add_admin.sql") // include admins during migration
-    ])?;
+    migrate::run_migrations(conn.clone(), MIGRATIONS.to_vec())?;
     
     // Exécuter le seeder si la base de données est vide
     seeder::run_seeder(conn.clone(), include_str!("../../sql/seeders/001_initial_seed.sql"))?;
@@ -0,0 +1,307 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use crate::db::repositories::repository::Repository;
+use crate::grading::attendance::AttendanceTotals;
+use rusqlite::{params, Connection, OptionalExtension, Row, ToSql};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AbsenceKind {
+    Absence,
+    Lateness,
+}
+
+impl AbsenceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Absence => "absence",
+            Self::Lateness => "retard",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "absence" => Some(Self::Absence),
+            "retard" => Some(Self::Lateness),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AbsenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Absence => write!(f, "Absence"),
+            Self::Lateness => write!(f, "Retard"),
+        }
+    }
+}
+
+/// Absence or lateness of a student on a day, optionally in a subject
+#[derive(Debug, Clone, PartialEq)]
+pub struct Absence {
+    pub id: i64,
+    pub matricule: String,
+    /// AAAA-MM-JJ
+    pub date: String,
+    pub kind: AbsenceKind,
+    pub subject_code: Option<String>,
+    /// Hours for an absence, minutes for a lateness
+    pub duration: u32,
+    pub justified: bool,
+    pub reason: Option<String>,
+}
+
+impl Absence {
+    /// e.g. "2 h" or "15 min"
+    pub fn duration_label(&self) -> String {
+        match self.kind {
+            AbsenceKind::Absence => format!("{} h", self.duration),
+            AbsenceKind::Lateness => format!("{} min", self.duration),
+        }
+    }
+}
+
+fn absence_from_row(row: &Row) -> rusqlite::Result<Absence> {
+    let kind: String = row.get(3)?;
+    Ok(Absence {
+        id: row.get(0)?,
+        matricule: row.get(1)?,
+        date: row.get(2)?,
+        kind: AbsenceKind::parse(&kind).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                3,
+                rusqlite::types::Type::Text,
+                format!("unknown absence kind: {}", kind).into(),
+            )
+        })?,
+        subject_code: row.get(4)?,
+        duration: row.get(5)?,
+        justified: row.get(6)?,
+        reason: row.get(7)?,
+    })
+}
+
+const ABSENCE_COLUMNS: &str = "id, matricule, date, kind, subject_code, duration, justified, reason";
+
+fn insert(conn: &Connection, absence: &Absence) -> rusqlite::Result<usize> {
+    conn.execute(
+        "INSERT INTO absences (matricule, date, kind, subject_code, duration, justified, reason)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            absence.matricule,
+            absence.date,
+            absence.kind.as_str(),
+            absence.subject_code,
+            absence.duration,
+            absence.justified,
+            absence.reason
+        ],
+    )
+}
+
+pub struct AbsenceRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl AbsenceRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Record the absences of a class roll call, all or none
+    pub fn record_roll_call(&self, absences: &[Absence]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        for absence in absences {
+            insert(&tx, absence)?;
+        }
+
+        tx.commit()
+    }
+
+    /// Mark an absence as justified, `false` if there is no such absence
+    pub fn justify(&self, id: i64, reason: Option<&str>) -> rusqlite::Result<bool> {
+        let conn = self.conn.borrow_mut();
+        let updated = conn.execute(
+            "UPDATE absences SET justified = 1, reason = ?1 WHERE id = ?2",
+            params![reason, id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Absences of a student between two dates, included
+    pub fn get_by_student(&self, matricule: &str, from: &str, to: &str) -> rusqlite::Result<Vec<Absence>> {
+        self.filter(
+            "matricule = ?1 AND date BETWEEN ?2 AND ?3 ORDER BY date, id",
+            &[&matricule, &from, &to],
+        )
+    }
+
+    /// Totals of every student with an absence or a lateness between two
+    /// dates, by matricule
+    pub fn get_totals(&self, from: &str, to: &str) -> rusqlite::Result<HashMap<String, AttendanceTotals>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT matricule,
+                    COALESCE(SUM(CASE WHEN kind = 'absence' THEN duration END), 0),
+                    COALESCE(SUM(CASE WHEN kind = 'absence' AND NOT justified THEN duration END), 0),
+                    COUNT(CASE WHEN kind = 'retard' THEN 1 END),
+                    COUNT(CASE WHEN kind = 'retard' AND NOT justified THEN 1 END)
+             FROM absences
+             WHERE date BETWEEN ?1 AND ?2
+             GROUP BY matricule"
+        )?;
+
+        let totals = stmt.query_map(params![from, to], |row| {
+            Ok((
+                row.get(0)?,
+                AttendanceTotals {
+                    absence_hours: row.get(1)?,
+                    unjustified_hours: row.get(2)?,
+                    lateness: row.get(3)?,
+                    unjustified_lateness: row.get(4)?,
+                },
+            ))
+        })?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        Ok(totals)
+    }
+}
+
+impl Repository<Absence> for AbsenceRepository {
+    fn create(&self, absence: &Absence) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        insert(&conn, absence)?;
+        Ok(())
+    }
+
+    fn get_all(&self) -> rusqlite::Result<Vec<Absence>> {
+        self.filter("1 ORDER BY date, id", &[])
+    }
+
+    fn get(&self, id: &str) -> rusqlite::Result<Option<Absence>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            &format!("SELECT {} FROM absences WHERE id = ?1", ABSENCE_COLUMNS),
+            params![id],
+            absence_from_row,
+        )
+            .optional()
+    }
+
+    fn update(&self, absence: &Absence) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "UPDATE absences SET matricule = ?1, date = ?2, kind = ?3, subject_code = ?4, duration = ?5,
+                                 justified = ?6, reason = ?7
+             WHERE id = ?8",
+            params![
+                absence.matricule,
+                absence.date,
+                absence.kind.as_str(),
+                absence.subject_code,
+                absence.duration,
+                absence.justified,
+                absence.reason,
+                absence.id
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, id: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute("DELETE FROM absences WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    fn filter(&self, filter: &str, params: &[&dyn ToSql]) -> rusqlite::Result<Vec<Absence>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM absences WHERE {}", ABSENCE_COLUMNS, filter))?;
+
+        let absences = stmt.query_map(params, absence_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(absences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_repo() -> AbsenceRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10');
+             INSERT INTO students (matricule, name, surname, class) VALUES
+                ('S001', 'Alice', 'Smith', '10A'),
+                ('S002', 'Bob', 'Jones', '10A');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES ('MATH101', 'Mathematics', '10A', 4);",
+        ).unwrap();
+
+        AbsenceRepository::new(conn)
+    }
+
+    fn absence(matricule: &str, date: &str, kind: AbsenceKind, duration: u32) -> Absence {
+        Absence {
+            id: 0,
+            matricule: matricule.into(),
+            date: date.into(),
+            kind,
+            subject_code: None,
+            duration,
+            justified: false,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_roll_call_is_all_or_nothing() {
+        let repo = setup_repo();
+
+        let mut absences = vec![
+            absence("S001", "2025-10-06", AbsenceKind::Absence, 2),
+            absence("S002", "2025-10-06", AbsenceKind::Lateness, 10),
+        ];
+        absences[0].subject_code = Some("MATH101".into());
+        repo.record_roll_call(&absences).unwrap();
+        assert_eq!(repo.get_all().unwrap().len(), 2);
+
+        let invalid = vec![
+            absence("S001", "2025-10-07", AbsenceKind::Absence, 1),
+            absence("S002", "7 octobre", AbsenceKind::Absence, 1),
+        ];
+        assert!(repo.record_roll_call(&invalid).is_err());
+        assert_eq!(repo.get_all().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_totals_by_period() {
+        let repo = setup_repo();
+        repo.create(&absence("S001", "2025-10-06", AbsenceKind::Absence, 4)).unwrap();
+        repo.create(&absence("S001", "2025-10-08", AbsenceKind::Absence, 2)).unwrap();
+        repo.create(&absence("S001", "2025-10-09", AbsenceKind::Lateness, 15)).unwrap();
+        repo.create(&absence("S001", "2026-02-02", AbsenceKind::Absence, 8)).unwrap();
+
+        let first = repo.get_by_student("S001", "2025-09-01", "2025-12-19").unwrap();
+        assert_eq!(first.len(), 3);
+        assert_eq!(first[2].duration_label(), "15 min");
+        assert!(repo.justify(first[0].id, Some("Certificat médical")).unwrap());
+        assert!(!repo.justify(999, None).unwrap());
+
+        let totals = repo.get_totals("2025-09-01", "2025-12-19").unwrap();
+        assert_eq!(
+            totals["S001"],
+            AttendanceTotals { absence_hours: 6, unjustified_hours: 2, lateness: 1, unjustified_lateness: 1 }
+        );
+        assert!(!totals.contains_key("S002"));
+    }
+}
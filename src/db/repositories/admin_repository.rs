@@ -7,6 +7,8 @@ pub struct Admin {
     pub id: i64,
     pub login: String,
     pub password: String, // plain for now, hash later
+    /// Allowed to lock and reopen periods
+    pub is_super_admin: bool,
 }
 
 pub struct AdminSession {
@@ -31,25 +33,28 @@ impl AdminSession {
         let conn = self.conn.borrow();
 
         conn.query_row(
-            "SELECT id, login, password FROM admins WHERE login = ?1 AND password = ?2",
+            "SELECT id, login, password, is_super_admin FROM admins WHERE login = ?1 AND password = ?2",
             params![login, password],
             |row| {
                 Ok(Admin {
                     id: row.get(0)?,
                     login: row.get(1)?,
                     password: row.get(2)?,
+                    is_super_admin: row.get(3)?,
                 })
             },
         )
             .optional()
     }
 
-    /// Simple helper for tests / setup
+    /// Simple helper for tests / setup. Admins are otherwise created by
+    /// the seeder, so it is only compiled for the tests.
+    #[cfg(test)]
     pub fn create(&self, admin: &Admin) -> rusqlite::Result<()> {
         let conn = self.conn.borrow_mut();
         conn.execute(
-            "INSERT INTO admins (login, password) VALUES (?1, ?2)",
-            params![admin.login, admin.password],
+            "INSERT INTO admins (login, password, is_super_admin) VALUES (?1, ?2, ?3)",
+            params![admin.login, admin.password, admin.is_super_admin],
         )?;
         Ok(())
     }
@@ -60,7 +65,6 @@ mod tests {
     use super::*;
     use std::cell::RefCell;
     use std::rc::Rc;
-    use rusqlite::params;
 
     fn setup_session() -> AdminSession {
         let conn = Rc::new(RefCell::new(
@@ -78,6 +82,7 @@ mod tests {
             id: 1,
             login: "admin".into(),
             password: "secret".into(),
+            is_super_admin: false,
         }
     }
 
@@ -122,16 +127,18 @@ mod tests {
             id: 1,
             login: "admin1".into(),
             password: "pw1".into(),
+            is_super_admin: true,
         }).unwrap();
 
         session.create(&Admin {
             id: 2,
             login: "admin2".into(),
             password: "pw2".into(),
+            is_super_admin: false,
         }).unwrap();
 
-        assert!(session.login("admin1", "pw1").unwrap().is_some());
-        assert!(session.login("admin2", "pw2").unwrap().is_some());
+        assert!(session.login("admin1", "pw1").unwrap().unwrap().is_super_admin);
+        assert!(!session.login("admin2", "pw2").unwrap().unwrap().is_super_admin);
         assert!(session.login("admin1", "pw2").unwrap().is_none());
     }
 }
@@ -0,0 +1,490 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use crate::db::repositories::lock_repository::{self, PeriodLock};
+
+/// Setting key holding the number of days an archived record is kept
+/// before it can be purged
+pub const RETENTION_DAYS_SETTING: &str = "archives.retention_days";
+pub const DEFAULT_RETENTION_DAYS: u32 = 365;
+
+/// Record archived instead of being deleted
+#[derive(Debug, Clone, PartialEq)]
+pub enum Archivable {
+    Student(String),
+    Subject(String),
+    Note(i64),
+}
+
+impl fmt::Display for Archivable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Student(matricule) => write!(f, "étudiant {}", matricule),
+            Self::Subject(code) => write!(f, "matière {}", code),
+            Self::Note(id) => write!(f, "note n°{}", id),
+        }
+    }
+}
+
+impl Archivable {
+    /// Table of the record and its key column
+    fn table(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Student(_) => ("students", "matricule"),
+            Self::Subject(_) => ("subjects", "code"),
+            Self::Note(_) => ("notes", "id"),
+        }
+    }
+
+    /// Condition selecting the record's notes, bound to its key as ?1
+    pub fn notes_condition(&self) -> &'static str {
+        match self {
+            Self::Student(_) => "notes.matricule = ?1",
+            Self::Subject(_) => "notes.subject_code = ?1",
+            Self::Note(_) => "notes.id = ?1",
+        }
+    }
+
+    /// Value of the record's key column
+    pub fn key(&self) -> Value {
+        match self {
+            Self::Student(matricule) => Value::Text(matricule.clone()),
+            Self::Subject(code) => Value::Text(code.clone()),
+            Self::Note(id) => Value::Integer(*id),
+        }
+    }
+}
+
+/// Archived record, with the notes archived along with it
+#[derive(Debug, Clone)]
+pub struct ArchivedRecord {
+    pub record: Archivable,
+    /// e.g. "Alice Smith (10A)"
+    pub label: String,
+    pub archived_at: String,
+    pub notes: u32,
+}
+
+/// Records permanently deleted by a purge
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PurgeReport {
+    pub students: usize,
+    pub subjects: usize,
+    pub notes: usize,
+}
+
+/// Why a record cannot be archived, restored or purged
+#[derive(Debug)]
+pub enum ArchiveError {
+    Database(rusqlite::Error),
+    Unknown(Archivable),
+    AlreadyArchived(Archivable),
+    NotArchived(Archivable),
+    /// Some of the notes belong to a closed period
+    Locked(PeriodLock),
+    /// The student or subject of a note to restore is still archived
+    ParentArchived(Archivable),
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) =>
+                write!(f, "{e}"),
+            Self::Unknown(record) =>
+                write!(f, "{record} introuvable"),
+            Self::AlreadyArchived(record) =>
+                write!(f, "{record} : déjà archivé"),
+            Self::NotArchived(record) =>
+                write!(f, "{record} : pas archivé"),
+            Self::Locked(lock) =>
+                write!(f, "des notes appartiennent à une période close ({lock})"),
+            Self::ParentArchived(record) =>
+                write!(f, "{record} : toujours archivé, à restaurer d'abord"),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+impl From<rusqlite::Error> for ArchiveError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+/// For the `delete` of the repositories, which archive: a refusal is
+/// reported like a failed constraint
+impl From<ArchiveError> for rusqlite::Error {
+    fn from(e: ArchiveError) -> Self {
+        match e {
+            ArchiveError::Database(e) => e,
+            e => rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT),
+                Some(e.to_string()),
+            ),
+        }
+    }
+}
+
+/// Permanently delete a student with their notes. Enrolments, absences,
+/// profile and guardians go with the student. Returns the number of notes
+/// deleted.
+pub fn delete_student(conn: &Connection, matricule: &str) -> rusqlite::Result<usize> {
+    let notes = conn.execute("DELETE FROM notes WHERE matricule = ?1", params![matricule])?;
+    conn.execute("DELETE FROM students WHERE matricule = ?1", params![matricule])?;
+    Ok(notes)
+}
+
+/// Permanently delete a subject with its notes, evaluations, moderations
+/// and locks, whose lifting is kept in the audit log. Its absences are kept
+/// without a subject, and its teaching assignments go with it. Returns the
+/// number of notes deleted.
+pub fn delete_subject(conn: &Connection, code: &str) -> rusqlite::Result<usize> {
+    let notes = conn.execute(
+        "DELETE FROM notes WHERE subject_code = ?1
+            OR evaluation_id IN (SELECT id FROM evaluations WHERE subject_code = ?1)",
+        params![code],
+    )?;
+    conn.execute(
+        "DELETE FROM moderations WHERE subject_code = ?1
+            OR evaluation_id IN (SELECT id FROM evaluations WHERE subject_code = ?1)",
+        params![code],
+    )?;
+    conn.execute("DELETE FROM evaluations WHERE subject_code = ?1", params![code])?;
+    lock_repository::lift_subject_locks(conn, code)?;
+    conn.execute("UPDATE absences SET subject_code = NULL WHERE subject_code = ?1", params![code])?;
+    conn.execute("DELETE FROM subjects WHERE code = ?1", params![code])?;
+    Ok(notes)
+}
+
+/// When the record was archived, `None` if it is active
+pub fn archived_at(conn: &Connection, record: &Archivable) -> Result<Option<String>, ArchiveError> {
+    let (table, key) = record.table();
+    conn.query_row(
+        &format!("SELECT deleted_at FROM {} WHERE {} = ?1", table, key),
+        params![record.key()],
+        |row| row.get(0),
+    )
+        .optional()?
+        .ok_or_else(|| ArchiveError::Unknown(record.clone()))
+}
+
+/// Archive a record along with its active notes, within the caller's
+/// transaction. Returns the number of notes archived.
+pub fn archive_in(conn: &Connection, record: &Archivable) -> Result<usize, ArchiveError> {
+    if archived_at(conn, record)?.is_some() {
+        return Err(ArchiveError::AlreadyArchived(record.clone()));
+    }
+    let condition = format!("{} AND notes.deleted_at IS NULL", record.notes_condition());
+    if let Some(lock) = lock_repository::covering_notes(conn, &condition, params![record.key()])? {
+        return Err(ArchiveError::Locked(lock));
+    }
+
+    let (table, key) = record.table();
+    conn.execute(
+        &format!("UPDATE {} SET deleted_at = CURRENT_TIMESTAMP WHERE {} = ?1", table, key),
+        params![record.key()],
+    )?;
+    let notes = match record {
+        Archivable::Note(_) => 1,
+        _ => conn.execute(
+            &format!("UPDATE notes SET deleted_at = CURRENT_TIMESTAMP WHERE {}", condition),
+            params![record.key()],
+        )?,
+    };
+    Ok(notes)
+}
+
+fn record_from_row(row: &Row) -> rusqlite::Result<ArchivedRecord> {
+    let kind: String = row.get(0)?;
+    let key: String = row.get(1)?;
+    Ok(ArchivedRecord {
+        record: match kind.as_str() {
+            "student" => Archivable::Student(key),
+            "subject" => Archivable::Subject(key),
+            _ => Archivable::Note(key.parse().map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
+            })?),
+        },
+        label: row.get(2)?,
+        archived_at: row.get(3)?,
+        notes: row.get(4)?,
+    })
+}
+
+pub struct ArchiveRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl ArchiveRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Archive a record along with its active notes, unless some of them
+    /// belong to a closed period. Returns the number of notes archived.
+    pub fn archive(&self, record: &Archivable) -> Result<usize, ArchiveError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let notes = archive_in(&tx, record)?;
+
+        tx.commit()?;
+        Ok(notes)
+    }
+
+    /// Restore an archived record along with the notes archived with it,
+    /// except those whose student or subject is still archived. Returns the
+    /// number of notes restored.
+    pub fn restore(&self, record: &Archivable) -> Result<usize, ArchiveError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let Some(archived_at) = archived_at(&tx, record)? else {
+            return Err(ArchiveError::NotArchived(record.clone()));
+        };
+        if let Archivable::Note(id) = record {
+            let (matricule, code, student_archived, subject_archived): (String, String, bool, bool) = tx.query_row(
+                "SELECT n.matricule, n.subject_code, st.deleted_at IS NOT NULL, su.deleted_at IS NOT NULL
+                 FROM notes n
+                 JOIN students st ON st.matricule = n.matricule
+                 JOIN subjects su ON su.code = n.subject_code
+                 WHERE n.id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )?;
+            if student_archived {
+                return Err(ArchiveError::ParentArchived(Archivable::Student(matricule)));
+            }
+            if subject_archived {
+                return Err(ArchiveError::ParentArchived(Archivable::Subject(code)));
+            }
+        }
+
+        // Notes whose other parent is still archived are handed over to
+        // it, to come back along with it
+        let other_parent = match record {
+            Archivable::Student(_) => Some(("subjects", "code", "notes.subject_code")),
+            Archivable::Subject(_) => Some(("students", "matricule", "notes.matricule")),
+            Archivable::Note(_) => None,
+        };
+        let archived_with = format!("{} AND notes.deleted_at = ?2", record.notes_condition());
+        let condition = match other_parent {
+            Some((table, key, column)) => format!(
+                "{} AND {} NOT IN (SELECT {} FROM {} WHERE deleted_at IS NOT NULL)",
+                archived_with, column, key, table
+            ),
+            None => archived_with.clone(),
+        };
+        if let Some(lock) = lock_repository::covering_notes(&tx, &condition, params![record.key(), archived_at])? {
+            return Err(ArchiveError::Locked(lock));
+        }
+
+        let notes = tx.execute(
+            &format!("UPDATE notes SET deleted_at = NULL WHERE {}", condition),
+            params![record.key(), archived_at],
+        )?;
+        if let Some((table, key, column)) = other_parent {
+            tx.execute(
+                &format!(
+                    "UPDATE notes SET deleted_at = (SELECT deleted_at FROM {0} WHERE {1} = {2})
+                     WHERE {3} AND {2} IN (SELECT {1} FROM {0} WHERE deleted_at IS NOT NULL)",
+                    table, key, column, archived_with
+                ),
+                params![record.key(), archived_at],
+            )?;
+        }
+        let (table, key) = record.table();
+        tx.execute(
+            &format!("UPDATE {} SET deleted_at = NULL WHERE {} = ?1", table, key),
+            params![record.key()],
+        )?;
+
+        tx.commit()?;
+        Ok(notes)
+    }
+
+    /// Archived students, subjects and notes, latest first. Notes archived
+    /// along with their student or subject are only counted with it.
+    pub fn get_archived(&self) -> rusqlite::Result<Vec<ArchivedRecord>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT 'student', s.matricule, s.name || ' ' || s.surname || ' (' || s.class || ')', s.deleted_at,
+                    (SELECT COUNT(*) FROM notes n WHERE n.matricule = s.matricule AND n.deleted_at = s.deleted_at)
+             FROM students s WHERE s.deleted_at IS NOT NULL
+             UNION ALL
+             SELECT 'subject', su.code, su.name || ' (' || su.class || ')', su.deleted_at,
+                    (SELECT COUNT(*) FROM notes n WHERE n.subject_code = su.code AND n.deleted_at = su.deleted_at)
+             FROM subjects su WHERE su.deleted_at IS NOT NULL
+             UNION ALL
+             SELECT 'note', CAST(n.id AS TEXT), printf('%s, %s : %.2f', n.matricule, n.subject_code, n.value),
+                    n.deleted_at, 1
+             FROM notes n
+             WHERE n.deleted_at IS NOT NULL
+               AND NOT EXISTS (SELECT 1 FROM students s WHERE s.matricule = n.matricule AND s.deleted_at = n.deleted_at)
+               AND NOT EXISTS (SELECT 1 FROM subjects su WHERE su.code = n.subject_code AND su.deleted_at = n.deleted_at)
+             ORDER BY 4 DESC, 1, 2"
+        )?;
+
+        let records = stmt.query_map([], record_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(records)
+    }
+
+    /// Permanently delete the records archived more than `retention_days`
+    /// days ago, with every note of the students and subjects deleted, all
+    /// or none
+    pub fn purge(&self, retention_days: u32) -> Result<PurgeReport, ArchiveError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+        let cutoff = format!("datetime('now', '-{} days')", retention_days);
+
+        let expired = |table: &str, key: &str| -> rusqlite::Result<Vec<String>> {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT {} FROM {} WHERE deleted_at <= {}",
+                key, table, cutoff
+            ))?;
+            stmt.query_map([], |row| row.get(0))?.collect()
+        };
+        let students = expired("students", "matricule")?;
+        let subjects = expired("subjects", "code")?;
+
+        let condition = format!(
+            "notes.deleted_at <= {0}
+             OR notes.matricule IN (SELECT matricule FROM students WHERE deleted_at <= {0})
+             OR notes.subject_code IN (SELECT code FROM subjects WHERE deleted_at <= {0})",
+            cutoff
+        );
+        if let Some(lock) = lock_repository::covering_notes(&tx, &condition, [])? {
+            return Err(ArchiveError::Locked(lock));
+        }
+
+        let mut report = PurgeReport::default();
+        for code in &subjects {
+            report.notes += delete_subject(&tx, code)?;
+            report.subjects += 1;
+        }
+        for matricule in &students {
+            report.notes += delete_student(&tx, matricule)?;
+            report.students += 1;
+        }
+        report.notes += tx.execute(&format!("DELETE FROM notes WHERE deleted_at <= {}", cutoff), [])?;
+
+        tx.commit()?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_repo() -> ArchiveRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10');
+             INSERT INTO students (matricule, name, surname, class) VALUES
+                 ('S001', 'Alice', 'Smith', '10A'), ('S002', 'Bob', 'Jones', '10A');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES
+                 ('MATH101', 'Mathematics', '10A', 4), ('PHY101', 'Physics', '10A', 3);
+             INSERT INTO notes (id, matricule, subject_code, value, period_id) VALUES
+                 (1, 'S001', 'MATH101', 12, 1), (2, 'S001', 'PHY101', 14, 1),
+                 (3, 'S002', 'MATH101', 9, 1), (4, 'S002', 'PHY101', 11, 1);",
+        ).unwrap();
+
+        ArchiveRepository::new(conn)
+    }
+
+    fn archived_notes(repo: &ArchiveRepository) -> Vec<i64> {
+        let conn = repo.conn.borrow();
+        let mut stmt = conn.prepare("SELECT id FROM notes WHERE deleted_at IS NOT NULL ORDER BY id").unwrap();
+        stmt.query_map([], |row| row.get(0)).unwrap().collect::<rusqlite::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn test_archive_and_restore() {
+        let repo = setup_repo();
+        let student = Archivable::Student("S001".into());
+
+        assert_eq!(repo.archive(&Archivable::Note(3)).unwrap(), 1);
+        assert_eq!(repo.archive(&student).unwrap(), 2);
+        assert!(matches!(repo.archive(&student), Err(ArchiveError::AlreadyArchived(_))));
+        assert!(matches!(repo.archive(&Archivable::Subject("BIO".into())), Err(ArchiveError::Unknown(_))));
+        assert_eq!(archived_notes(&repo), vec![1, 2, 3]);
+
+        let archived = repo.get_archived().unwrap();
+        assert_eq!(archived.len(), 2);
+        let notes: Vec<(Archivable, u32)> = archived.into_iter().map(|a| (a.record, a.notes)).collect();
+        assert!(notes.contains(&(student.clone(), 2)));
+        assert!(notes.contains(&(Archivable::Note(3), 1)));
+
+        // A note cannot come back before its student
+        assert!(matches!(repo.restore(&Archivable::Note(1)), Err(ArchiveError::ParentArchived(_))));
+        assert_eq!(repo.restore(&student).unwrap(), 2);
+        assert!(matches!(repo.restore(&student), Err(ArchiveError::NotArchived(_))));
+        // The note archived on its own stays archived
+        assert_eq!(archived_notes(&repo), vec![3]);
+    }
+
+    #[test]
+    fn test_restore_hands_notes_over_to_archived_parent() {
+        let repo = setup_repo();
+        let student = Archivable::Student("S001".into());
+        let subject = Archivable::Subject("MATH101".into());
+        repo.archive(&subject).unwrap();
+        repo.archive(&student).unwrap();
+        assert_eq!(archived_notes(&repo), vec![1, 2, 3]);
+
+        // Note 1 waits for its student
+        assert_eq!(repo.restore(&subject).unwrap(), 1);
+        assert_eq!(archived_notes(&repo), vec![1, 2]);
+        assert_eq!(repo.restore(&student).unwrap(), 2);
+        assert!(archived_notes(&repo).is_empty());
+    }
+
+    #[test]
+    fn test_locked_notes_cannot_be_archived() {
+        let repo = setup_repo();
+        repo.conn.borrow().execute("INSERT INTO period_locks (period_id, subject_code) VALUES (1, 'PHY101')", []).unwrap();
+
+        assert!(matches!(repo.archive(&Archivable::Student("S001".into())), Err(ArchiveError::Locked(_))));
+        assert_eq!(repo.archive(&Archivable::Note(1)).unwrap(), 1);
+        assert_eq!(archived_notes(&repo), vec![1]);
+    }
+
+    #[test]
+    fn test_purge_after_retention() {
+        let repo = setup_repo();
+        repo.conn.borrow().execute_batch(
+            "INSERT INTO evaluations (id, subject_code, period_id, type_code, title, date)
+                 SELECT 1, 'PHY101', 1, code, 'Devoir', '2025-10-01' FROM evaluation_types LIMIT 1;
+             UPDATE notes SET evaluation_id = 1 WHERE id = 4;
+             INSERT INTO absences (matricule, date, kind, subject_code) VALUES ('S002', '2025-10-02', 'absence', 'PHY101');",
+        ).unwrap();
+        repo.archive(&Archivable::Subject("PHY101".into())).unwrap();
+        repo.archive(&Archivable::Note(3)).unwrap();
+
+        assert_eq!(repo.purge(30).unwrap(), PurgeReport::default());
+
+        repo.conn.borrow().execute_batch(
+            "UPDATE subjects SET deleted_at = datetime('now', '-40 days') WHERE deleted_at IS NOT NULL;
+             UPDATE notes SET deleted_at = datetime('now', '-40 days') WHERE id = 3;",
+        ).unwrap();
+        let report = repo.purge(30).unwrap();
+        assert_eq!(report, PurgeReport { students: 0, subjects: 1, notes: 3 });
+
+        let conn = repo.conn.borrow();
+        let left: Vec<i64> = conn.prepare("SELECT id FROM notes ORDER BY id").unwrap()
+            .query_map([], |row| row.get(0)).unwrap().collect::<rusqlite::Result<_>>().unwrap();
+        assert_eq!(left, vec![1]);
+        let absence_subject: Option<String> = conn.query_row("SELECT subject_code FROM absences", [], |row| row.get(0)).unwrap();
+        assert_eq!(absence_subject, None);
+    }
+}
@@ -0,0 +1,100 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use rusqlite::{params, Connection, Row};
+
+/// Action recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuditAction {
+    Lock,
+    Reopen,
+}
+
+impl AuditAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lock => "lock",
+            Self::Reopen => "reopen",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "lock" => Some(Self::Lock),
+            "reopen" => Some(Self::Reopen),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lock => write!(f, "verrouillage"),
+            Self::Reopen => write!(f, "réouverture"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub action: AuditAction,
+    /// What the action was performed on
+    pub details: String,
+    pub justification: Option<String>,
+    /// Login of the admin who performed it
+    pub performed_by: Option<String>,
+    pub performed_at: String,
+}
+
+fn entry_from_row(row: &Row) -> rusqlite::Result<AuditEntry> {
+    let action: String = row.get(1)?;
+    Ok(AuditEntry {
+        id: row.get(0)?,
+        action: AuditAction::parse(&action).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                1,
+                rusqlite::types::Type::Text,
+                format!("unknown audit action: {}", action).into(),
+            )
+        })?,
+        details: row.get(2)?,
+        justification: row.get(3)?,
+        performed_by: row.get(4)?,
+        performed_at: row.get(5)?,
+    })
+}
+
+/// Append an entry to the audit log, within the caller's transaction
+pub fn insert(conn: &Connection, entry: &AuditEntry) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO audit_log (action, details, justification, performed_by) VALUES (?1, ?2, ?3, ?4)",
+        params![entry.action.as_str(), entry.details, entry.justification, entry.performed_by],
+    )?;
+    Ok(())
+}
+
+pub struct AuditRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl AuditRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Entries of the audit log, latest first
+    pub fn get_all(&self) -> rusqlite::Result<Vec<AuditEntry>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT id, action, details, justification, performed_by, performed_at
+             FROM audit_log ORDER BY id DESC"
+        )?;
+
+        let entries = stmt.query_map([], entry_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,239 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::db::repositories::repository::Repository;
+use rusqlite::{params, Connection, OptionalExtension, Row, ToSql};
+
+/// Class that students are enrolled in and subjects are taught to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Class {
+    /// e.g. "6ème A"
+    pub code: String,
+    /// e.g. "6ème"
+    pub level: String,
+    /// e.g. "A"
+    pub section: Option<String>,
+    pub academic_year_id: Option<i64>,
+    pub head_teacher: Option<String>,
+    /// Maximum number of students, `None` if unlimited
+    pub capacity: Option<u32>,
+}
+
+/// Class with the label of its academic year and its number of students
+#[derive(Debug, Clone)]
+pub struct ClassSummary {
+    pub class: Class,
+    pub year_label: Option<String>,
+    pub headcount: u32,
+}
+
+impl ClassSummary {
+    pub fn is_full(&self) -> bool {
+        self.class.capacity.is_some_and(|capacity| self.headcount >= capacity)
+    }
+}
+
+fn class_from_row(row: &Row) -> rusqlite::Result<Class> {
+    Ok(Class {
+        code: row.get(0)?,
+        level: row.get(1)?,
+        section: row.get(2)?,
+        academic_year_id: row.get(3)?,
+        head_teacher: row.get(4)?,
+        capacity: row.get(5)?,
+    })
+}
+
+const CLASS_COLUMNS: &str = "code, level, section, academic_year_id, head_teacher, capacity";
+
+pub struct ClassRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl ClassRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Every class with the number of students enrolled in the current year
+    /// and not archived, by level then code
+    pub fn get_summaries(&self) -> rusqlite::Result<Vec<ClassSummary>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT c.code, c.level, c.section, c.academic_year_id, c.head_teacher, c.capacity,
+                    y.label,
+                    (SELECT COUNT(*) FROM enrolments e JOIN academic_years cy ON cy.id = e.academic_year_id
+                     WHERE e.class = c.code AND e.status = 'enrolled' AND cy.is_current = 1
+                       AND e.matricule IN (SELECT matricule FROM students WHERE deleted_at IS NULL))
+             FROM classes c LEFT JOIN academic_years y ON y.id = c.academic_year_id
+             ORDER BY c.level, c.code"
+        )?;
+
+        let summaries = stmt.query_map([], |row| {
+            Ok(ClassSummary {
+                class: class_from_row(row)?,
+                year_label: row.get(6)?,
+                headcount: row.get(7)?,
+            })
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(summaries)
+    }
+}
+
+impl Repository<Class> for ClassRepository {
+    fn create(&self, class: &Class) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO classes (code, level, section, academic_year_id, head_teacher, capacity)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                class.code,
+                class.level,
+                class.section,
+                class.academic_year_id,
+                class.head_teacher,
+                class.capacity
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn get_all(&self) -> rusqlite::Result<Vec<Class>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM classes ORDER BY level, code", CLASS_COLUMNS))?;
+
+        let classes = stmt.query_map([], class_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(classes)
+    }
+
+    fn get(&self, code: &str) -> rusqlite::Result<Option<Class>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            &format!("SELECT {} FROM classes WHERE code = ?1", CLASS_COLUMNS),
+            params![code],
+            class_from_row,
+        )
+            .optional()
+    }
+
+    fn update(&self, class: &Class) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "UPDATE classes SET level = ?1, section = ?2, academic_year_id = ?3, head_teacher = ?4, capacity = ?5
+             WHERE code = ?6",
+            params![
+                class.level,
+                class.section,
+                class.academic_year_id,
+                class.head_teacher,
+                class.capacity,
+                class.code
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Fails while students or subjects still belong to the class
+    fn delete(&self, code: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute("DELETE FROM classes WHERE code = ?1", params![code])?;
+        Ok(())
+    }
+
+    fn filter(&self, filter: &str, params: &[&dyn ToSql]) -> rusqlite::Result<Vec<Class>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM classes WHERE {}", CLASS_COLUMNS, filter))?;
+
+        let classes = stmt.query_map(params, class_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(classes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn setup_repo() -> ClassRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        ClassRepository::new(conn)
+    }
+
+    fn class(code: &str, capacity: Option<u32>) -> Class {
+        Class {
+            code: code.into(),
+            level: "10".into(),
+            section: Some(code[2..].into()),
+            academic_year_id: Some(1),
+            head_teacher: None,
+            capacity,
+        }
+    }
+
+    #[test]
+    fn test_create_and_update_class() {
+        let repo = setup_repo();
+        let mut class = class("10A", Some(30));
+        repo.create(&class).unwrap();
+
+        class.head_teacher = Some("M. Diallo".into());
+        class.capacity = None;
+        repo.update(&class).unwrap();
+
+        assert_eq!(repo.get("10A").unwrap().unwrap(), class);
+        assert!(repo.create(&class).is_err());
+        assert!(repo.create(&self::class("10B", Some(0))).is_err());
+    }
+
+    #[test]
+    fn test_summaries_count_students() {
+        let repo = setup_repo();
+        repo.create(&class("10A", Some(2))).unwrap();
+        repo.create(&class("10B", None)).unwrap();
+        repo.conn.borrow().execute_batch(
+            "INSERT INTO students (matricule, name, surname, class) VALUES
+             ('S001', 'Alice', 'Smith', '10A'), ('S002', 'Bob', 'Johnson', '10A')",
+        ).unwrap();
+
+        let summaries = repo.get_summaries().unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].headcount, 2);
+        assert_eq!(summaries[0].year_label.as_deref(), Some("2025-2026"));
+        assert!(summaries[0].is_full());
+        assert_eq!(summaries[1].headcount, 0);
+        assert!(!summaries[1].is_full());
+    }
+
+    #[test]
+    fn test_students_and_subjects_need_an_existing_class() {
+        let repo = setup_repo();
+        repo.create(&class("10A", None)).unwrap();
+        let conn = repo.conn.borrow();
+
+        assert!(conn.execute(
+            "INSERT INTO students (matricule, name, surname, class) VALUES ('S001', 'Alice', 'Smith', '10a')",
+            [],
+        ).is_err());
+        assert!(conn.execute(
+            "INSERT INTO subjects (code, name, class, coefficient) VALUES ('MATH101', 'Mathematics', '10C', 4)",
+            [],
+        ).is_err());
+        conn.execute(
+            "INSERT INTO students (matricule, name, surname, class) VALUES ('S001', 'Alice', 'Smith', '10A')",
+            [],
+        ).unwrap();
+        drop(conn);
+
+        // A class with students cannot be deleted
+        assert!(repo.delete("10A").is_err());
+    }
+}
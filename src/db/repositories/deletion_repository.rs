@@ -0,0 +1,339 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::db::repositories::archive_repository::{self, ArchiveError, Archivable};
+use crate::db::repositories::lock_repository::{self, PeriodLock};
+use crate::db::repositories::note_repository::Note;
+
+/// What happens to the notes of a student or subject being deleted
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeletePolicy {
+    /// Only delete it if no note refers to it
+    Refuse,
+    /// Delete its notes along with it
+    Cascade,
+    /// Hand the notes of a subject over to another subject of its class
+    Reassign(String),
+    /// Archive it with its notes instead of deleting it
+    Archive,
+}
+
+impl fmt::Display for DeletePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Refuse => write!(f, "refuser s'il reste des notes"),
+            Self::Cascade => write!(f, "supprimer aussi les notes"),
+            Self::Reassign(code) => write!(f, "transférer les notes vers la matière {}", code),
+            Self::Archive => write!(f, "archiver au lieu de supprimer"),
+        }
+    }
+}
+
+/// What became of a deleted record's notes
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeleteOutcome {
+    Deleted { notes: usize },
+    Reassigned { notes: usize, to: String },
+    Archived { notes: usize },
+}
+
+/// Why a student or subject cannot be deleted
+#[derive(Debug)]
+pub enum DeleteError {
+    Database(rusqlite::Error),
+    Unknown(Archivable),
+    /// Notes still refer to the record
+    HasNotes(Vec<Note>),
+    /// Some of the notes belong to a closed period
+    Locked(PeriodLock),
+    /// Only the notes of a subject can be handed over
+    CannotReassign(Archivable),
+    /// The subject meant to receive the notes is unknown, archived or the
+    /// one being deleted
+    InvalidTarget(String),
+    /// The subject meant to receive the notes is taught in another class
+    WrongClass { target: String, target_class: String, class: String },
+    Archive(ArchiveError),
+}
+
+impl fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) =>
+                write!(f, "{e}"),
+            Self::Unknown(record) =>
+                write!(f, "{record} introuvable"),
+            Self::HasNotes(notes) =>
+                write!(f, "{} note(s) y font encore référence", notes.len()),
+            Self::Locked(lock) =>
+                write!(f, "des notes appartiennent à une période close ({lock})"),
+            Self::CannotReassign(record) =>
+                write!(f, "les notes de l'{record} ne peuvent pas être transférées"),
+            Self::InvalidTarget(code) =>
+                write!(f, "la matière {code} ne peut pas recevoir les notes"),
+            Self::WrongClass { target, target_class, class } =>
+                write!(f, "la matière {target} est enseignée en {target_class}, pas en {class}"),
+            Self::Archive(e) =>
+                write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for DeleteError {}
+
+impl From<rusqlite::Error> for DeleteError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+impl From<ArchiveError> for DeleteError {
+    fn from(e: ArchiveError) -> Self {
+        match e {
+            ArchiveError::Database(e) => Self::Database(e),
+            ArchiveError::Unknown(record) => Self::Unknown(record),
+            ArchiveError::Locked(lock) => Self::Locked(lock),
+            e => Self::Archive(e),
+        }
+    }
+}
+
+/// Every note of the record, archived or not
+fn dependent_notes(conn: &Connection, record: &Archivable) -> rusqlite::Result<Vec<Note>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, matricule, subject_code, value, period_id, evaluation_id FROM notes
+         WHERE {} ORDER BY id",
+        record.notes_condition()
+    ))?;
+    let notes = stmt.query_map(params![record.key()], |row| {
+        Ok(Note {
+            id: row.get(0)?,
+            matricule: row.get(1)?,
+            subject_code: row.get(2)?,
+            value: row.get(3)?,
+            period_id: row.get(4)?,
+            evaluation_id: row.get(5)?,
+        })
+    })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(notes)
+}
+
+/// Hand the notes, evaluations, moderations and absences of subject `code`
+/// over to subject `target`, then delete it. Returns the number of notes
+/// handed over.
+fn reassign_subject(conn: &Connection, code: &str, target: &str) -> Result<usize, DeleteError> {
+    // The subject may be archived, the one receiving its notes may not
+    let class: String = conn.query_row("SELECT class FROM subjects WHERE code = ?1", params![code], |row| row.get(0))?;
+    let target_class: Option<String> = conn
+        .query_row(
+            "SELECT class FROM subjects WHERE code = ?1 AND deleted_at IS NULL",
+            params![target],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let target_class = match target_class {
+        Some(target_class) if target != code => target_class,
+        _ => return Err(DeleteError::InvalidTarget(target.to_string())),
+    };
+    if target_class != class {
+        return Err(DeleteError::WrongClass { target: target.to_string(), target_class, class });
+    }
+
+    if let Some(lock) = lock_repository::covering_notes(conn, "notes.subject_code = ?1", params![code])? {
+        return Err(DeleteError::Locked(lock));
+    }
+    let periods = {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT period_id FROM notes WHERE subject_code = ?1 AND period_id IS NOT NULL"
+        )?;
+        stmt.query_map(params![code], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+    for period_id in periods {
+        if let Some(lock) = lock_repository::covering(conn, period_id, target)? {
+            return Err(DeleteError::Locked(lock));
+        }
+    }
+
+    let notes = conn.execute("UPDATE notes SET subject_code = ?2 WHERE subject_code = ?1", params![code, target])?;
+    for table in ["evaluations", "moderations", "absences"] {
+        conn.execute(
+            &format!("UPDATE {} SET subject_code = ?2 WHERE subject_code = ?1", table),
+            params![code, target],
+        )?;
+    }
+    archive_repository::delete_subject(conn, code)?;
+    Ok(notes)
+}
+
+pub struct DeletionRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl DeletionRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Delete a student, active or archived, deciding the fate of their
+    /// notes by `policy`, all or nothing
+    pub fn delete_student(&self, matricule: &str, policy: &DeletePolicy) -> Result<DeleteOutcome, DeleteError> {
+        self.delete(&Archivable::Student(matricule.to_string()), policy)
+    }
+
+    /// Delete a subject, active or archived, deciding the fate of its notes
+    /// by `policy`, all or nothing
+    pub fn delete_subject(&self, code: &str, policy: &DeletePolicy) -> Result<DeleteOutcome, DeleteError> {
+        self.delete(&Archivable::Subject(code.to_string()), policy)
+    }
+
+    fn delete(&self, record: &Archivable, policy: &DeletePolicy) -> Result<DeleteOutcome, DeleteError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        archive_repository::archived_at(&tx, record)?;
+        let erase = |conn: &Connection| match record {
+            Archivable::Student(matricule) => archive_repository::delete_student(conn, matricule),
+            Archivable::Subject(code) => archive_repository::delete_subject(conn, code),
+            Archivable::Note(id) => conn.execute("DELETE FROM notes WHERE id = ?1", params![id]),
+        };
+
+        let outcome = match policy {
+            DeletePolicy::Refuse => {
+                let notes = dependent_notes(&tx, record)?;
+                if !notes.is_empty() {
+                    return Err(DeleteError::HasNotes(notes));
+                }
+                DeleteOutcome::Deleted { notes: erase(&tx)? }
+            }
+            DeletePolicy::Cascade => {
+                if let Some(lock) = lock_repository::covering_notes(&tx, record.notes_condition(), params![record.key()])? {
+                    return Err(DeleteError::Locked(lock));
+                }
+                DeleteOutcome::Deleted { notes: erase(&tx)? }
+            }
+            DeletePolicy::Reassign(target) => {
+                let Archivable::Subject(code) = record else {
+                    return Err(DeleteError::CannotReassign(record.clone()));
+                };
+                let notes = reassign_subject(&tx, code, target)?;
+                DeleteOutcome::Reassigned { notes, to: target.clone() }
+            }
+            DeletePolicy::Archive => DeleteOutcome::Archived { notes: archive_repository::archive_in(&tx, record)? },
+        };
+
+        tx.commit()?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_repo() -> DeletionRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10'), ('10B', '10');
+             INSERT INTO students (matricule, name, surname, class) VALUES
+                 ('S001', 'Alice', 'Smith', '10A'), ('S002', 'Bob', 'Jones', '10A');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES
+                 ('MATH101', 'Mathematics', '10A', 4), ('ALG101', 'Algebra', '10A', 2),
+                 ('PHY101', 'Physics', '10A', 3), ('MATH102', 'Mathematics', '10B', 4);
+             INSERT INTO notes (id, matricule, subject_code, value, period_id) VALUES
+                 (1, 'S001', 'MATH101', 12, 1), (2, 'S001', 'PHY101', 14, 1),
+                 (3, 'S002', 'MATH101', 9, 1);",
+        ).unwrap();
+
+        DeletionRepository::new(conn)
+    }
+
+    fn count(repo: &DeletionRepository, sql: &str) -> i64 {
+        repo.conn.borrow().query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_refuse_and_cascade() {
+        let repo = setup_repo();
+
+        match repo.delete_student("S001", &DeletePolicy::Refuse) {
+            Err(DeleteError::HasNotes(notes)) =>
+                assert_eq!(notes.iter().map(|n| n.id).collect::<Vec<_>>(), vec![1, 2]),
+            other => panic!("expected HasNotes, got {:?}", other),
+        }
+        assert_eq!(count(&repo, "SELECT COUNT(*) FROM students"), 2);
+        assert!(matches!(repo.delete_student("S999", &DeletePolicy::Cascade), Err(DeleteError::Unknown(_))));
+
+        assert_eq!(repo.delete_student("S001", &DeletePolicy::Cascade).unwrap(), DeleteOutcome::Deleted { notes: 2 });
+        assert_eq!(repo.delete_subject("PHY101", &DeletePolicy::Refuse).unwrap(), DeleteOutcome::Deleted { notes: 0 });
+        assert_eq!(count(&repo, "SELECT COUNT(*) FROM notes"), 1);
+        assert_eq!(count(&repo, "SELECT COUNT(*) FROM subjects WHERE code = 'PHY101'"), 0);
+    }
+
+    #[test]
+    fn test_reassign_subject_notes() {
+        let repo = setup_repo();
+        let reassign = |code: &str| DeletePolicy::Reassign(code.to_string());
+
+        assert!(matches!(repo.delete_subject("MATH101", &reassign("MATH102")), Err(DeleteError::WrongClass { .. })));
+        assert!(matches!(repo.delete_subject("MATH101", &reassign("MATH101")), Err(DeleteError::InvalidTarget(_))));
+        assert!(matches!(repo.delete_student("S001", &reassign("ALG101")), Err(DeleteError::CannotReassign(_))));
+
+        assert_eq!(
+            repo.delete_subject("MATH101", &reassign("ALG101")).unwrap(),
+            DeleteOutcome::Reassigned { notes: 2, to: "ALG101".into() }
+        );
+        assert_eq!(count(&repo, "SELECT COUNT(*) FROM notes WHERE subject_code = 'ALG101'"), 2);
+        assert_eq!(count(&repo, "SELECT COUNT(*) FROM subjects WHERE code = 'MATH101'"), 0);
+
+        // An archived subject keeps its class
+        repo.conn.borrow().execute("UPDATE subjects SET deleted_at = CURRENT_TIMESTAMP WHERE code = 'PHY101'", []).unwrap();
+        match repo.delete_subject("PHY101", &reassign("MATH102")) {
+            Err(DeleteError::WrongClass { class, .. }) => assert_eq!(class, "10A"),
+            other => panic!("expected WrongClass, got {:?}", other),
+        }
+        assert_eq!(
+            repo.delete_subject("PHY101", &reassign("ALG101")).unwrap(),
+            DeleteOutcome::Reassigned { notes: 1, to: "ALG101".into() }
+        );
+    }
+
+    #[test]
+    fn test_deleting_a_subject_lifts_its_locks_in_the_audit_log() {
+        let repo = setup_repo();
+        repo.conn.borrow().execute(
+            "INSERT INTO period_locks (period_id, class, subject_code, locked_by) VALUES (2, NULL, 'PHY101', 'admin')",
+            [],
+        ).unwrap();
+
+        assert_eq!(repo.delete_subject("PHY101", &DeletePolicy::Cascade).unwrap(), DeleteOutcome::Deleted { notes: 1 });
+        assert_eq!(count(&repo, "SELECT COUNT(*) FROM period_locks"), 0);
+        let justification: String = repo.conn.borrow().query_row(
+            "SELECT justification FROM audit_log WHERE action = 'reopen'", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(justification, "suppression de la matière PHY101");
+    }
+
+    #[test]
+    fn test_locked_notes_block_deletion() {
+        let repo = setup_repo();
+        repo.conn.borrow().execute(
+            "INSERT INTO period_locks (period_id, class, subject_code, locked_by) VALUES (1, NULL, 'PHY101', 'admin')",
+            [],
+        ).unwrap();
+
+        assert!(matches!(repo.delete_student("S001", &DeletePolicy::Cascade), Err(DeleteError::Locked(_))));
+        assert!(matches!(repo.delete_subject("PHY101", &DeletePolicy::Archive), Err(DeleteError::Locked(_))));
+        assert!(matches!(
+            repo.delete_subject("MATH101", &DeletePolicy::Reassign("PHY101".into())),
+            Err(DeleteError::Locked(_))
+        ));
+        assert_eq!(repo.delete_student("S002", &DeletePolicy::Archive).unwrap(), DeleteOutcome::Archived { notes: 1 });
+        assert_eq!(count(&repo, "SELECT COUNT(*) FROM notes"), 3);
+    }
+}
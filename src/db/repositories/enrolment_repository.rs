@@ -0,0 +1,368 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use crate::grading::promotion::{Outcome, PromotionMove};
+use crate::grading::rules::Decision;
+
+/// Move the students of a year to the class they are enrolled in
+pub const SYNC_STUDENT_CLASSES: &str =
+    "UPDATE students SET class = e.class
+     FROM enrolments e
+     WHERE e.matricule = students.matricule AND e.academic_year_id = ?1 AND e.status = 'enrolled'";
+
+/// Students enrolled in the class `?1` for the current year
+pub const ENROLLED_IN_CURRENT_YEAR: &str =
+    "matricule IN (SELECT e.matricule FROM enrolments e JOIN academic_years y ON y.id = e.academic_year_id
+                   WHERE e.class = ?1 AND e.status = 'enrolled' AND y.is_current = 1)";
+
+/// Students still at the school: their latest enrolment was not closed
+/// because they left or graduated
+pub const NOT_DEPARTED: &str =
+    "matricule NOT IN (SELECT e.matricule FROM enrolments e
+                       WHERE e.status IN ('left', 'graduated')
+                         AND e.id = (SELECT MAX(id) FROM enrolments WHERE matricule = e.matricule))";
+
+/// State of an enrolment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnrolmentStatus {
+    Enrolled,
+    /// Moved to another class during the year
+    Transferred,
+    /// Left the school
+    Left,
+    Graduated,
+}
+
+impl EnrolmentStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enrolled => "enrolled",
+            Self::Transferred => "transferred",
+            Self::Left => "left",
+            Self::Graduated => "graduated",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "enrolled" => Some(Self::Enrolled),
+            "transferred" => Some(Self::Transferred),
+            "left" => Some(Self::Left),
+            "graduated" => Some(Self::Graduated),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for EnrolmentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Enrolled => write!(f, "inscrit"),
+            Self::Transferred => write!(f, "transféré"),
+            Self::Left => write!(f, "parti"),
+            Self::Graduated => write!(f, "diplômé"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enrolment {
+    pub id: i64,
+    pub matricule: String,
+    pub class: String,
+    pub academic_year_id: i64,
+    pub status: EnrolmentStatus,
+    pub decision: Option<Decision>,
+}
+
+/// Enrolment with the label of its year and the number of notes attached to it
+#[derive(Debug, Clone)]
+pub struct EnrolmentRecord {
+    pub enrolment: Enrolment,
+    pub year_label: String,
+    pub note_count: u32,
+}
+
+fn enrolment_from_row(row: &Row) -> rusqlite::Result<Enrolment> {
+    let status: String = row.get(4)?;
+    let decision: Option<String> = row.get(5)?;
+
+    Ok(Enrolment {
+        id: row.get(0)?,
+        matricule: row.get(1)?,
+        class: row.get(2)?,
+        academic_year_id: row.get(3)?,
+        status: EnrolmentStatus::parse(&status).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                4,
+                rusqlite::types::Type::Text,
+                format!("unknown enrolment status: {}", status).into(),
+            )
+        })?,
+        decision: decision.as_deref().and_then(Decision::parse),
+    })
+}
+
+const ENROLMENT_COLUMNS: &str = "e.id, e.matricule, e.class, e.academic_year_id, e.status, e.decision";
+
+pub struct EnrolmentRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl EnrolmentRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Every enrolment of a student, oldest year first
+    pub fn get_history(&self, matricule: &str) -> rusqlite::Result<Vec<EnrolmentRecord>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, y.label, (SELECT COUNT(*) FROM notes n WHERE n.enrolment_id = e.id)
+             FROM enrolments e JOIN academic_years y ON y.id = e.academic_year_id
+             WHERE e.matricule = ?1
+             ORDER BY y.start_date, e.id",
+            ENROLMENT_COLUMNS
+        ))?;
+
+        let records = stmt.query_map(params![matricule], |row| {
+            Ok(EnrolmentRecord {
+                enrolment: enrolment_from_row(row)?,
+                year_label: row.get(6)?,
+                note_count: row.get(7)?,
+            })
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(records)
+    }
+
+    /// Active enrolment of a student in a year
+    pub fn get_current(&self, matricule: &str, year_id: i64) -> rusqlite::Result<Option<Enrolment>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM enrolments e
+                 WHERE e.matricule = ?1 AND e.academic_year_id = ?2 AND e.status = 'enrolled'",
+                ENROLMENT_COLUMNS
+            ),
+            params![matricule, year_id],
+            enrolment_from_row,
+        )
+            .optional()
+    }
+
+    /// Class a student attended in a year, the last one if they changed class
+    pub fn get_class(&self, matricule: &str, year_id: i64) -> rusqlite::Result<Option<String>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            "SELECT class FROM enrolments
+             WHERE matricule = ?1 AND academic_year_id = ?2
+             ORDER BY status = 'enrolled' DESC, id DESC
+             LIMIT 1",
+            params![matricule, year_id],
+            |row| row.get(0),
+        )
+            .optional()
+    }
+
+    /// Move a student to another class during the year. Their notes stay
+    /// attached to the previous enrolment.
+    pub fn transfer(&self, matricule: &str, year_id: i64, class: &str) -> rusqlite::Result<()> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "UPDATE enrolments SET status = 'transferred'
+             WHERE matricule = ?1 AND academic_year_id = ?2 AND status = 'enrolled'",
+            params![matricule, year_id],
+        )?;
+        tx.execute(
+            "INSERT INTO enrolments (matricule, class, academic_year_id) VALUES (?1, ?2, ?3)",
+            params![matricule, class, year_id],
+        )?;
+        tx.execute(
+            &format!("{} AND students.matricule = ?2 AND ?1 IN (SELECT id FROM academic_years WHERE is_current = 1)", SYNC_STUDENT_CLASSES),
+            params![year_id, matricule],
+        )?;
+
+        tx.commit()
+    }
+
+    /// Record that a student left the school during the year
+    pub fn leave(&self, matricule: &str, year_id: i64) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "UPDATE enrolments SET status = 'left'
+             WHERE matricule = ?1 AND academic_year_id = ?2 AND status = 'enrolled'",
+            params![matricule, year_id],
+        )?;
+        Ok(())
+    }
+
+    /// Apply year-end outcomes: record the decision on the enrolment of
+    /// `from_year`, enrol promoted students and repeaters in `to_year` and
+    /// close the enrolment of graduates and leavers. Undecided students are
+    /// left untouched.
+    pub fn promote(&self, from_year: i64, to_year: i64, moves: &[PromotionMove]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        for change in moves {
+            if change.outcome == Outcome::Undecided {
+                continue;
+            }
+
+            let status = match change.outcome {
+                Outcome::Graduated => EnrolmentStatus::Graduated,
+                Outcome::Left => EnrolmentStatus::Left,
+                _ => EnrolmentStatus::Enrolled,
+            };
+            tx.execute(
+                "UPDATE enrolments SET status = ?1, decision = ?2
+                 WHERE matricule = ?3 AND academic_year_id = ?4 AND status = 'enrolled'",
+                params![
+                    status.as_str(),
+                    change.decision.map(Decision::as_str),
+                    change.student.matricule,
+                    from_year
+                ],
+            )?;
+
+            if let Some(class) = change.next_class() {
+                tx.execute(
+                    "INSERT INTO enrolments (matricule, class, academic_year_id) VALUES (?1, ?2, ?3)",
+                    params![change.student.matricule, class, to_year],
+                )?;
+            }
+        }
+
+        // Students only change class once the new year has begun
+        tx.execute(
+            &format!("{} AND ?1 IN (SELECT id FROM academic_years WHERE is_current = 1)", SYNC_STUDENT_CLASSES),
+            params![to_year],
+        )?;
+
+        tx.commit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::repository::Repository;
+    use crate::db::repositories::student_repository::{Student, StudentRepository};
+    use crate::grading::Scope;
+
+    fn setup_repo() -> EnrolmentRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('6A', '6'), ('6B', '6'), ('5A', '5');
+             INSERT INTO students (matricule, name, surname, class) VALUES
+                ('S001', 'Alice', 'Smith', '6A'),
+                ('S002', 'Bob', 'Jones', '6A');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES
+                ('MATH6', 'Mathematics', '6A', 4),
+                ('MATH5', 'Mathematics', '5A', 4);
+             INSERT INTO notes (matricule, subject_code, value, period_id) VALUES ('S001', 'MATH6', 14, 1);
+             INSERT INTO academic_years (label, start_date, end_date, period_kind)
+                VALUES ('2026-2027', '2026-09-01', '2027-07-02', 'semestre');
+             INSERT INTO periods (academic_year_id, number, start_date, end_date)
+                VALUES (2, 1, '2026-09-01', '2027-01-29');",
+        ).unwrap();
+
+        EnrolmentRepository::new(conn)
+    }
+
+    fn promotion(matricule: &str, decision: Decision, outcome: Outcome) -> PromotionMove {
+        PromotionMove {
+            student: Student {
+                matricule: matricule.into(),
+                name: "Nom".into(),
+                surname: "Prénom".into(),
+                class: "6A".into(),
+            },
+            decision: Some(decision),
+            outcome,
+        }
+    }
+
+    fn class_of(repo: &EnrolmentRepository, matricule: &str) -> String {
+        repo.conn.borrow()
+            .query_row("SELECT class FROM students WHERE matricule = ?1", params![matricule], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new_students_are_enrolled_in_the_current_year() {
+        let repo = setup_repo();
+
+        let history = repo.get_history("S001").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].enrolment.class, "6A");
+        assert_eq!(history[0].enrolment.status, EnrolmentStatus::Enrolled);
+        assert_eq!(history[0].year_label, "2025-2026");
+        assert_eq!(history[0].note_count, 1);
+    }
+
+    #[test]
+    fn test_promotion_keeps_previous_notes() {
+        let repo = setup_repo();
+        let moves = vec![
+            promotion("S001", Decision::Admitted, Outcome::Promoted("5A".into())),
+            promotion("S002", Decision::Excluded, Outcome::Left),
+        ];
+
+        repo.promote(1, 2, &moves).unwrap();
+        // The new year has not begun yet
+        assert_eq!(class_of(&repo, "S001"), "6A");
+
+        let history = repo.get_history("S001").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].enrolment.decision, Some(Decision::Admitted));
+        assert_eq!(history[0].note_count, 1);
+        assert_eq!(history[1].enrolment.class, "5A");
+        assert_eq!(repo.get_history("S002").unwrap()[0].enrolment.status, EnrolmentStatus::Left);
+        assert!(repo.get_current("S002", 2).unwrap().is_none());
+
+        // S002 left: no longer listed, nor ranked in the new year
+        let students = StudentRepository::new(repo.conn.clone()).get_all().unwrap();
+        assert_eq!(students.iter().map(|s| s.matricule.as_str()).collect::<Vec<_>>(), ["S001"]);
+        let grading = crate::grading::service::GradingService::new(repo.conn.clone());
+        assert_eq!(grading.class_averages("6A", Scope::Year(1)).unwrap().len(), 2);
+        assert!(grading.class_averages("6A", Scope::Year(2)).unwrap().is_empty());
+        let matricules = |class: &str, scope: Scope| -> Vec<String> {
+            grading.enrolled_in_class(class, scope).unwrap().into_iter().map(|s| s.matricule).collect()
+        };
+        assert_eq!(matricules("6A", Scope::Period(1)), ["S001", "S002"]);
+        assert_eq!(matricules("5A", Scope::Period(4)), ["S001"]);
+        assert!(matricules("5A", Scope::Period(1)).is_empty());
+
+        // Notes of the new year go to the new enrolment, in the new class
+        repo.conn.borrow().execute_batch(
+            "INSERT INTO notes (matricule, subject_code, value, period_id) VALUES ('S001', 'MATH5', 12, 4);"
+        ).unwrap();
+        assert_eq!(repo.get_history("S001").unwrap()[1].note_count, 1);
+        assert!(repo.conn.borrow().execute_batch(
+            "INSERT INTO notes (matricule, subject_code, value, period_id) VALUES ('S001', 'MATH6', 12, 4);"
+        ).is_err());
+    }
+
+    #[test]
+    fn test_transfer() {
+        let repo = setup_repo();
+
+        repo.transfer("S001", 1, "6B").unwrap();
+        assert_eq!(class_of(&repo, "S001"), "6B");
+        assert_eq!(repo.get_class("S001", 1).unwrap().as_deref(), Some("6B"));
+
+        let history = repo.get_history("S001").unwrap();
+        assert_eq!(history[0].enrolment.status, EnrolmentStatus::Transferred);
+        assert_eq!(history[0].note_count, 1);
+        assert_eq!(history[1].enrolment.status, EnrolmentStatus::Enrolled);
+    }
+}
@@ -0,0 +1,241 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+/// Kind of assessment (devoir, interrogation, composition, examen...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationType {
+    pub code: String,
+    pub label: String,
+    /// Weight of the type's average within a subject average
+    pub weight: f64,
+    /// Type of the notes recorded outside any evaluation
+    pub is_default: bool,
+}
+
+/// Assessment given in a subject during a period
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub id: i64,
+    pub subject_code: String,
+    pub period_id: i64,
+    pub type_code: String,
+    pub title: String,
+    /// YYYY-MM-DD
+    pub date: String,
+    pub max_score: f64,
+    /// Weight among the evaluations of the same type
+    pub weight: f64,
+}
+
+impl Evaluation {
+    /// Convert a score out of `max_score` to a note out of 20,
+    /// `None` if the score is out of range
+    pub fn normalize(&self, score: f64) -> Option<f64> {
+        if (0.0..=self.max_score).contains(&score) {
+            Some(score * 20.0 / self.max_score)
+        } else {
+            None
+        }
+    }
+}
+
+fn type_from_row(row: &Row) -> rusqlite::Result<EvaluationType> {
+    Ok(EvaluationType {
+        code: row.get(0)?,
+        label: row.get(1)?,
+        weight: row.get(2)?,
+        is_default: row.get(3)?,
+    })
+}
+
+fn evaluation_from_row(row: &Row) -> rusqlite::Result<Evaluation> {
+    Ok(Evaluation {
+        id: row.get(0)?,
+        subject_code: row.get(1)?,
+        period_id: row.get(2)?,
+        type_code: row.get(3)?,
+        title: row.get(4)?,
+        date: row.get(5)?,
+        max_score: row.get(6)?,
+        weight: row.get(7)?,
+    })
+}
+
+const EVALUATION_COLUMNS: &str = "id, subject_code, period_id, type_code, title, date, max_score, weight";
+
+pub struct EvaluationRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl EvaluationRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    pub fn get_types(&self) -> rusqlite::Result<Vec<EvaluationType>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT code, label, weight, is_default FROM evaluation_types ORDER BY weight, code"
+        )?;
+
+        let types = stmt.query_map([], type_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(types)
+    }
+
+    /// Create the evaluation type `code` or change its label and weight
+    pub fn set_type(&self, evaluation_type: &EvaluationType) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO evaluation_types (code, label, weight) VALUES (?1, ?2, ?3)
+             ON CONFLICT(code) DO UPDATE SET label = excluded.label, weight = excluded.weight",
+            params![evaluation_type.code, evaluation_type.label, evaluation_type.weight],
+        )?;
+        Ok(())
+    }
+
+    /// Make `code` the type of the notes recorded outside any evaluation
+    pub fn set_default_type(&self, code: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "UPDATE evaluation_types SET is_default = (code = ?1)",
+            params![code],
+        )?;
+        Ok(())
+    }
+
+    pub fn create(&self, evaluation: &Evaluation) -> rusqlite::Result<i64> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO evaluations (subject_code, period_id, type_code, title, date, max_score, weight)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                evaluation.subject_code,
+                evaluation.period_id,
+                evaluation.type_code,
+                evaluation.title,
+                evaluation.date,
+                evaluation.max_score,
+                evaluation.weight
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn get(&self, id: i64) -> rusqlite::Result<Option<Evaluation>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            &format!("SELECT {} FROM evaluations WHERE id = ?1", EVALUATION_COLUMNS),
+            params![id],
+            evaluation_from_row,
+        )
+            .optional()
+    }
+
+    pub fn get_all(&self) -> rusqlite::Result<Vec<Evaluation>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM evaluations", EVALUATION_COLUMNS))?;
+
+        let evaluations = stmt.query_map([], evaluation_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(evaluations)
+    }
+
+    /// Evaluations of a subject by date, restricted to `period_id` if given
+    pub fn get_by_subject(&self, subject_code: &str, period_id: Option<i64>) -> rusqlite::Result<Vec<Evaluation>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM evaluations
+             WHERE subject_code = ?1 AND (?2 IS NULL OR period_id = ?2)
+             ORDER BY date, id",
+            EVALUATION_COLUMNS
+        ))?;
+
+        let evaluations = stmt.query_map(params![subject_code, period_id], evaluation_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(evaluations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn setup_repo() -> EvaluationRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch("INSERT INTO classes (code, level) VALUES ('10A', '10')").unwrap();
+        conn.borrow_mut().execute(
+            "INSERT INTO subjects (code, name, class, coefficient) VALUES ('MATH101', 'Mathematics', '10A', 4)",
+            [],
+        ).unwrap();
+
+        EvaluationRepository::new(conn)
+    }
+
+    fn evaluation(type_code: &str, period_id: i64, date: &str) -> Evaluation {
+        Evaluation {
+            id: 0,
+            subject_code: "MATH101".into(),
+            period_id,
+            type_code: type_code.into(),
+            title: "Contrôle".into(),
+            date: date.into(),
+            max_score: 40.0,
+            weight: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_default_types() {
+        let repo = setup_repo();
+        let types = repo.get_types().unwrap();
+
+        assert_eq!(types.len(), 4);
+        let default: Vec<&str> = types.iter().filter(|t| t.is_default).map(|t| t.code.as_str()).collect();
+        assert_eq!(default, vec!["devoir"]);
+
+        repo.set_type(&EvaluationType { code: "tp".into(), label: "Travaux pratiques".into(), weight: 10.0, is_default: false }).unwrap();
+        repo.set_default_type("tp").unwrap();
+        let types = repo.get_types().unwrap();
+        assert_eq!(types[0].code, "tp");
+        assert!(types[0].is_default);
+        assert_eq!(types.iter().filter(|t| t.is_default).count(), 1);
+    }
+
+    #[test]
+    fn test_create_and_filter_evaluations() {
+        let repo = setup_repo();
+
+        let id = repo.create(&evaluation("composition", 1, "2025-12-10")).unwrap();
+        repo.create(&evaluation("devoir", 1, "2025-10-02")).unwrap();
+        repo.create(&evaluation("devoir", 2, "2026-02-02")).unwrap();
+
+        assert_eq!(repo.get(id).unwrap().unwrap().type_code, "composition");
+        assert_eq!(repo.get_all().unwrap().len(), 3);
+
+        let first_term = repo.get_by_subject("MATH101", Some(1)).unwrap();
+        assert_eq!(first_term.len(), 2);
+        assert_eq!(first_term[0].date, "2025-10-02");
+
+        assert!(repo.create(&evaluation("inconnu", 1, "2025-10-02")).is_err());
+    }
+
+    #[test]
+    fn test_normalize_to_twenty() {
+        let evaluation = evaluation("devoir", 1, "2025-10-02");
+
+        assert_eq!(evaluation.normalize(30.0), Some(15.0));
+        assert_eq!(evaluation.normalize(40.0), Some(20.0));
+        assert_eq!(evaluation.normalize(41.0), None);
+        assert_eq!(evaluation.normalize(-1.0), None);
+    }
+}
@@ -0,0 +1,345 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use rusqlite::{params, Connection, OptionalExtension, Params, Row};
+use crate::db::repositories::admin_repository::Admin;
+use crate::db::repositories::audit_repository::{self, AuditAction, AuditEntry};
+use crate::db::repositories::period_repository::Period;
+
+/// Notes of a period covered by a lock
+#[derive(Debug, Clone, PartialEq)]
+pub enum LockScope {
+    Period,
+    Class(String),
+    Subject(String),
+}
+
+impl fmt::Display for LockScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Period => write!(f, "toutes les notes"),
+            Self::Class(class) => write!(f, "classe {}", class),
+            Self::Subject(code) => write!(f, "matière {}", code),
+        }
+    }
+}
+
+/// Lock preventing the notes of a closed period from changing
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodLock {
+    pub id: i64,
+    pub period_id: i64,
+    pub scope: LockScope,
+    pub locked_by: Option<String>,
+    pub locked_at: String,
+}
+
+impl fmt::Display for PeriodLock {
+    /// e.g. "matière MATH6, verrouillée le 2026-07-04 10:12:00"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, verrouillée le {}", self.scope, self.locked_at)
+    }
+}
+
+/// Why a period cannot be locked or reopened
+#[derive(Debug)]
+pub enum LockError {
+    Database(rusqlite::Error),
+    NotSuperAdmin,
+    AlreadyLocked,
+    UnknownLock(i64),
+    JustificationRequired,
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) =>
+                write!(f, "{e}"),
+            Self::NotSuperAdmin =>
+                write!(f, "seul un super administrateur peut verrouiller ou rouvrir une période"),
+            Self::AlreadyLocked =>
+                write!(f, "ces notes sont déjà verrouillées"),
+            Self::UnknownLock(id) =>
+                write!(f, "verrou n°{id} inconnu"),
+            Self::JustificationRequired =>
+                write!(f, "une justification est obligatoire pour rouvrir une période"),
+        }
+    }
+}
+
+impl std::error::Error for LockError {}
+
+impl From<rusqlite::Error> for LockError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+fn lock_from_row(row: &Row) -> rusqlite::Result<PeriodLock> {
+    let class: Option<String> = row.get(2)?;
+    let subject_code: Option<String> = row.get(3)?;
+    Ok(PeriodLock {
+        id: row.get(0)?,
+        period_id: row.get(1)?,
+        scope: match (class, subject_code) {
+            (_, Some(code)) => LockScope::Subject(code),
+            (Some(class), None) => LockScope::Class(class),
+            (None, None) => LockScope::Period,
+        },
+        locked_by: row.get(4)?,
+        locked_at: row.get(5)?,
+    })
+}
+
+const LOCK_COLUMNS: &str = "l.id, l.period_id, l.class, l.subject_code, l.locked_by, l.locked_at";
+
+/// Lock covering the notes of `subject_code` given during `period_id`, if any
+pub fn covering(conn: &Connection, period_id: i64, subject_code: &str) -> rusqlite::Result<Option<PeriodLock>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM period_locks l
+             WHERE l.period_id = ?1
+               AND (l.subject_code IS NULL OR l.subject_code = ?2)
+               AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = ?2))
+             ORDER BY l.id LIMIT 1",
+            LOCK_COLUMNS
+        ),
+        params![period_id, subject_code],
+        lock_from_row,
+    )
+        .optional()
+}
+
+/// First lock covering one of the notes matching `condition`, written on
+/// the columns of `notes`, if any
+pub fn covering_notes<P: Params>(conn: &Connection, condition: &str, params: P) -> rusqlite::Result<Option<PeriodLock>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM period_locks l
+             JOIN notes ON notes.period_id = l.period_id
+             WHERE (l.subject_code IS NULL OR l.subject_code = notes.subject_code)
+               AND (l.class IS NULL OR l.class = (SELECT class FROM subjects WHERE code = notes.subject_code))
+               AND ({})
+             ORDER BY l.id LIMIT 1",
+            LOCK_COLUMNS, condition
+        ),
+        params,
+        lock_from_row,
+    )
+        .optional()
+}
+
+/// Lift the locks on the notes of subject `code`, about to be deleted for
+/// good, recording each in the audit log. Returns the number of locks lifted.
+pub fn lift_subject_locks(conn: &Connection, code: &str) -> rusqlite::Result<usize> {
+    let locks = {
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM period_locks l WHERE l.subject_code = ?1", LOCK_COLUMNS))?;
+        stmt.query_map(params![code], lock_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    for lock in &locks {
+        conn.execute("DELETE FROM period_locks WHERE id = ?1", params![lock.id])?;
+        audit_repository::insert(conn, &AuditEntry {
+            id: 0,
+            action: AuditAction::Reopen,
+            details: format!("période n°{} : {}", lock.period_id, lock.scope),
+            justification: Some(format!("suppression de la matière {}", code)),
+            performed_by: None,
+            performed_at: String::new(),
+        })?;
+    }
+    Ok(locks.len())
+}
+
+pub struct LockRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl LockRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Lock the notes of `period` within `scope` on behalf of a super admin,
+    /// and record it in the audit log. Returns the id of the lock.
+    pub fn lock(&self, period: &Period, scope: &LockScope, admin: &Admin) -> Result<i64, LockError> {
+        if !admin.is_super_admin {
+            return Err(LockError::NotSuperAdmin);
+        }
+        let (class, subject_code) = match scope {
+            LockScope::Period => (None, None),
+            LockScope::Class(class) => (Some(class), None),
+            LockScope::Subject(code) => (None, Some(code)),
+        };
+
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS (SELECT 1 FROM period_locks WHERE period_id = ?1 AND class IS ?2 AND subject_code IS ?3)",
+            params![period.id, class, subject_code],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Err(LockError::AlreadyLocked);
+        }
+
+        tx.execute(
+            "INSERT INTO period_locks (period_id, class, subject_code, locked_by) VALUES (?1, ?2, ?3, ?4)",
+            params![period.id, class, subject_code, admin.login],
+        )?;
+        let id = tx.last_insert_rowid();
+        audit_repository::insert(&tx, &AuditEntry {
+            id: 0,
+            action: AuditAction::Lock,
+            details: format!("{} (période n°{}) : {}", period, period.id, scope),
+            justification: None,
+            performed_by: Some(admin.login.clone()),
+            performed_at: String::new(),
+        })?;
+
+        tx.commit()?;
+        Ok(id)
+    }
+
+    /// Lift a lock on behalf of a super admin, whose justification is kept
+    /// in the audit log. Returns the lifted lock.
+    pub fn reopen(&self, id: i64, admin: &Admin, justification: &str) -> Result<PeriodLock, LockError> {
+        if !admin.is_super_admin {
+            return Err(LockError::NotSuperAdmin);
+        }
+        if justification.trim().is_empty() {
+            return Err(LockError::JustificationRequired);
+        }
+
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let lock = tx.query_row(
+            &format!("SELECT {} FROM period_locks l WHERE l.id = ?1", LOCK_COLUMNS),
+            params![id],
+            lock_from_row,
+        )
+            .optional()?
+            .ok_or(LockError::UnknownLock(id))?;
+
+        tx.execute("DELETE FROM period_locks WHERE id = ?1", params![id])?;
+        audit_repository::insert(&tx, &AuditEntry {
+            id: 0,
+            action: AuditAction::Reopen,
+            details: format!("période n°{} : {}", lock.period_id, lock.scope),
+            justification: Some(justification.trim().to_string()),
+            performed_by: Some(admin.login.clone()),
+            performed_at: String::new(),
+        })?;
+
+        tx.commit()?;
+        Ok(lock)
+    }
+
+    /// Locks of a period
+    pub fn get_by_period(&self, period_id: i64) -> rusqlite::Result<Vec<PeriodLock>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM period_locks l WHERE l.period_id = ?1 ORDER BY l.id",
+            LOCK_COLUMNS
+        ))?;
+
+        let locks = stmt.query_map(params![period_id], lock_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(locks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::audit_repository::AuditRepository;
+    use crate::db::repositories::period_repository::PeriodKind;
+
+    fn setup() -> (LockRepository, AuditRepository) {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10'), ('10B', '10');
+             INSERT INTO students (matricule, name, surname, class) VALUES ('S001', 'Alice', 'Smith', '10A');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES
+                 ('MATH101', 'Mathematics', '10A', 4), ('PHY101', 'Physics', '10A', 3), ('MATH102', 'Mathematics', '10B', 4);
+             INSERT INTO notes (id, matricule, subject_code, value, period_id) VALUES (1, 'S001', 'MATH101', 12, 1);",
+        ).unwrap();
+
+        (LockRepository::new(conn.clone()), AuditRepository::new(conn))
+    }
+
+    fn period() -> Period {
+        Period {
+            id: 1,
+            academic_year_id: 1,
+            kind: PeriodKind::Trimester,
+            number: 1,
+            start_date: "2025-09-01".into(),
+            end_date: "2025-12-19".into(),
+        }
+    }
+
+    fn admin(is_super_admin: bool) -> Admin {
+        Admin { id: 1, login: "admin".into(), password: "admin".into(), is_super_admin }
+    }
+
+    #[test]
+    fn test_lock_scopes() {
+        let (repo, _) = setup();
+
+        assert!(matches!(repo.lock(&period(), &LockScope::Period, &admin(false)), Err(LockError::NotSuperAdmin)));
+
+        repo.lock(&period(), &LockScope::Subject("MATH101".into()), &admin(true)).unwrap();
+        assert!(covering(&repo.conn.borrow(), 1, "MATH101").unwrap().is_some());
+        assert!(covering(&repo.conn.borrow(), 1, "PHY101").unwrap().is_none());
+        assert!(covering(&repo.conn.borrow(), 2, "MATH101").unwrap().is_none());
+        assert!(matches!(
+            repo.lock(&period(), &LockScope::Subject("MATH101".into()), &admin(true)),
+            Err(LockError::AlreadyLocked)
+        ));
+
+        repo.lock(&period(), &LockScope::Class("10A".into()), &admin(true)).unwrap();
+        assert!(covering(&repo.conn.borrow(), 1, "PHY101").unwrap().is_some());
+        assert!(covering(&repo.conn.borrow(), 1, "MATH102").unwrap().is_none());
+        assert_eq!(repo.get_by_period(1).unwrap().len(), 2);
+
+        // The database refuses to change a locked note
+        let conn = repo.conn.borrow();
+        assert!(conn.execute("UPDATE notes SET value = 15 WHERE id = 1", []).is_err());
+        assert!(conn.execute("DELETE FROM notes WHERE id = 1", []).is_err());
+        assert!(conn.execute(
+            "INSERT INTO notes (matricule, subject_code, value, period_id) VALUES ('S001', 'PHY101', 10, 1)", [],
+        ).is_err());
+        conn.execute("INSERT INTO notes (matricule, subject_code, value, period_id) VALUES ('S001', 'PHY101', 10, 2)", []).unwrap();
+    }
+
+    #[test]
+    fn test_reopen_requires_justification() {
+        let (repo, audit) = setup();
+        let id = repo.lock(&period(), &LockScope::Period, &admin(true)).unwrap();
+
+        assert!(matches!(repo.reopen(id, &admin(false), "erreur"), Err(LockError::NotSuperAdmin)));
+        assert!(matches!(repo.reopen(id, &admin(true), "  "), Err(LockError::JustificationRequired)));
+        assert!(covering(&repo.conn.borrow(), 1, "MATH101").unwrap().is_some());
+
+        let lock = repo.reopen(id, &admin(true), "note de MATH101 mal reportée").unwrap();
+        assert_eq!(lock.scope, LockScope::Period);
+        assert!(covering(&repo.conn.borrow(), 1, "MATH101").unwrap().is_none());
+        assert!(matches!(repo.reopen(id, &admin(true), "encore"), Err(LockError::UnknownLock(_))));
+
+        let entries = audit.get_all().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, AuditAction::Reopen);
+        assert_eq!(entries[0].justification.as_deref(), Some("note de MATH101 mal reportée"));
+        assert_eq!(entries[1].action, AuditAction::Lock);
+        assert_eq!(entries[1].performed_by.as_deref(), Some("admin"));
+    }
+}
@@ -2,4 +2,18 @@ pub mod repository;
 pub mod student_repository;
 pub mod subject_repository;
 pub mod note_repository;
-pub mod admin_repository;
\ No newline at end of file
+pub mod admin_repository;
+pub mod settings_repository;
+pub mod rules_repository;
+pub mod period_repository;
+pub mod evaluation_repository;
+pub mod class_repository;
+pub mod teacher_repository;
+pub mod enrolment_repository;
+pub mod absence_repository;
+pub mod profile_repository;
+pub mod moderation_repository;
+pub mod lock_repository;
+pub mod audit_repository;
+pub mod archive_repository;
+pub mod deletion_repository;
@@ -0,0 +1,291 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use rusqlite::{params, Connection, Row};
+use crate::db::repositories::lock_repository::{self, PeriodLock};
+use crate::db::repositories::note_repository::{last_version_id, sign_versions};
+
+/// Why a moderation cannot be applied or reverted
+#[derive(Debug)]
+pub enum ModerationError {
+    Database(rusqlite::Error),
+    /// Some of the notes belong to a closed period
+    Locked(PeriodLock),
+}
+
+impl fmt::Display for ModerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) =>
+                write!(f, "{e}"),
+            Self::Locked(lock) =>
+                write!(f, "des notes appartiennent à une période close ({lock})"),
+        }
+    }
+}
+
+impl std::error::Error for ModerationError {}
+
+impl From<rusqlite::Error> for ModerationError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+/// Moderation applied to the notes of an evaluation or a subject
+#[derive(Debug, Clone)]
+pub struct ModerationRecord {
+    pub id: i64,
+    pub subject_code: String,
+    pub evaluation_id: Option<i64>,
+    pub period_id: Option<i64>,
+    /// e.g. "bonus de +2 point(s)"
+    pub description: String,
+    /// Login of the admin who applied it
+    pub applied_by: Option<String>,
+    pub applied_at: String,
+    pub reverted_at: Option<String>,
+    /// Number of notes it changed
+    pub notes: u32,
+}
+
+/// Note changed by a moderation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeratedNote {
+    pub note_id: i64,
+    pub original_value: f64,
+    pub moderated_value: f64,
+}
+
+fn record_from_row(row: &Row) -> rusqlite::Result<ModerationRecord> {
+    Ok(ModerationRecord {
+        id: row.get(0)?,
+        subject_code: row.get(1)?,
+        evaluation_id: row.get(2)?,
+        period_id: row.get(3)?,
+        description: row.get(4)?,
+        applied_by: row.get(5)?,
+        applied_at: row.get(6)?,
+        reverted_at: row.get(7)?,
+        notes: row.get(8)?,
+    })
+}
+
+const RECORD_COLUMNS: &str = "m.id, m.subject_code, m.evaluation_id, m.period_id, m.description, m.applied_by,
+    m.applied_at, m.reverted_at, (SELECT COUNT(*) FROM moderated_notes mn WHERE mn.moderation_id = m.id)";
+
+pub struct ModerationRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl ModerationRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Give the notes their moderated value and keep their original one,
+    /// all or none, unless one of them belongs to a closed period. Notes
+    /// left unchanged are not recorded. The new versions of the notes are
+    /// signed by the author of the moderation. Returns the id of the
+    /// moderation.
+    pub fn apply(&self, record: &ModerationRecord, notes: &[ModeratedNote]) -> Result<i64, ModerationError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        for note in notes.iter().filter(|note| note.moderated_value != note.original_value) {
+            if let Some(lock) = lock_repository::covering_notes(&tx, "notes.id = ?1", params![note.note_id])? {
+                return Err(ModerationError::Locked(lock));
+            }
+        }
+
+        tx.execute(
+            "INSERT INTO moderations (subject_code, evaluation_id, period_id, description, applied_by)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![record.subject_code, record.evaluation_id, record.period_id, record.description, record.applied_by],
+        )?;
+        let id = tx.last_insert_rowid();
+        let since = last_version_id(&tx)?;
+
+        for note in notes.iter().filter(|note| note.moderated_value != note.original_value) {
+            tx.execute(
+                "UPDATE notes SET value = ?1 WHERE id = ?2",
+                params![note.moderated_value, note.note_id],
+            )?;
+            tx.execute(
+                "INSERT INTO moderated_notes (moderation_id, note_id, original_value, moderated_value)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id, note.note_id, note.original_value, note.moderated_value],
+            )?;
+        }
+
+        let reason = format!("modulation n°{} : {}", id, record.description);
+        sign_versions(&tx, since, record.applied_by.as_deref(), Some(&reason))?;
+
+        tx.commit()?;
+        Ok(id)
+    }
+
+    /// Moderations of the notes of a subject, latest first
+    pub fn get_by_subject(&self, subject_code: &str) -> rusqlite::Result<Vec<ModerationRecord>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM moderations m WHERE m.subject_code = ?1 ORDER BY m.id DESC",
+            RECORD_COLUMNS
+        ))?;
+
+        let records = stmt.query_map(params![subject_code], record_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(records)
+    }
+
+    /// Restore the original values of the notes of a moderation, except
+    /// those changed again since, on behalf of `author`, unless one of them
+    /// belongs to a closed period. Returns the number of notes restored,
+    /// `None` if there is no such moderation or it was already reverted.
+    pub fn revert(&self, id: i64, author: Option<&str>) -> Result<Option<usize>, ModerationError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let condition = "notes.id IN (SELECT mn.note_id FROM moderated_notes mn
+                                      WHERE mn.moderation_id = ?1 AND mn.moderated_value = notes.value)";
+        if let Some(lock) = lock_repository::covering_notes(&tx, condition, params![id])? {
+            return Err(ModerationError::Locked(lock));
+        }
+
+        let reverted = tx.execute(
+            "UPDATE moderations SET reverted_at = CURRENT_TIMESTAMP WHERE id = ?1 AND reverted_at IS NULL",
+            params![id],
+        )?;
+        if reverted == 0 {
+            return Ok(None);
+        }
+
+        let since = last_version_id(&tx)?;
+        let restored = tx.execute(
+            "UPDATE notes SET value = (
+                 SELECT mn.original_value FROM moderated_notes mn
+                 WHERE mn.moderation_id = ?1 AND mn.note_id = notes.id
+             )
+             WHERE id IN (
+                 SELECT mn.note_id FROM moderated_notes mn
+                 WHERE mn.moderation_id = ?1 AND mn.moderated_value = notes.value
+             )",
+            params![id],
+        )?;
+        sign_versions(&tx, since, author, Some(&format!("annulation de la modulation n°{}", id)))?;
+
+        tx.commit()?;
+        Ok(Some(restored))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_repo() -> ModerationRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10');
+             INSERT INTO students (matricule, name, surname, class) VALUES
+                 ('S001', 'Alice', 'Smith', '10A'), ('S002', 'Bob', 'Jones', '10A');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES ('MATH101', 'Mathematics', '10A', 4);
+             INSERT INTO notes (id, matricule, subject_code, value, period_id) VALUES
+                 (1, 'S001', 'MATH101', 8, 1), (2, 'S002', 'MATH101', 20, 1);",
+        ).unwrap();
+
+        ModerationRepository::new(conn)
+    }
+
+    fn value(repo: &ModerationRepository, id: i64) -> f64 {
+        repo.conn.borrow().query_row("SELECT value FROM notes WHERE id = ?1", [id], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_apply_and_revert() {
+        let repo = setup_repo();
+        let record = ModerationRecord {
+            id: 0,
+            subject_code: "MATH101".into(),
+            evaluation_id: None,
+            period_id: Some(1),
+            description: "bonus de +2 point(s)".into(),
+            applied_by: Some("admin".into()),
+            applied_at: String::new(),
+            reverted_at: None,
+            notes: 0,
+        };
+        let notes = vec![
+            ModeratedNote { note_id: 1, original_value: 8.0, moderated_value: 10.0 },
+            ModeratedNote { note_id: 2, original_value: 20.0, moderated_value: 20.0 },
+        ];
+
+        let id = repo.apply(&record, &notes).unwrap();
+        assert_eq!(value(&repo, 1), 10.0);
+        let applied = repo.get_by_subject("MATH101").unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].notes, 1);
+        assert!(applied[0].reverted_at.is_none());
+
+        let reason: String = repo.conn.borrow().query_row(
+            "SELECT reason FROM note_history WHERE note_id = 1 AND version = 2", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(reason, format!("modulation n°{} : bonus de +2 point(s)", id));
+
+        assert_eq!(repo.revert(id, None).unwrap(), Some(1));
+        assert_eq!(value(&repo, 1), 8.0);
+        assert!(repo.get_by_subject("MATH101").unwrap()[0].reverted_at.is_some());
+        assert_eq!(repo.revert(id, None).unwrap(), None);
+        assert_eq!(repo.revert(99, None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_revert_keeps_notes_changed_since() {
+        let repo = setup_repo();
+        let record = ModerationRecord {
+            id: 0,
+            subject_code: "MATH101".into(),
+            evaluation_id: None,
+            period_id: None,
+            description: "bonus de +1 point(s)".into(),
+            applied_by: None,
+            applied_at: String::new(),
+            reverted_at: None,
+            notes: 0,
+        };
+        let id = repo.apply(&record, &[ModeratedNote { note_id: 1, original_value: 8.0, moderated_value: 9.0 }]).unwrap();
+        repo.conn.borrow().execute("UPDATE notes SET value = 12 WHERE id = 1", []).unwrap();
+
+        assert_eq!(repo.revert(id, None).unwrap(), Some(0));
+        assert_eq!(value(&repo, 1), 12.0);
+    }
+
+    #[test]
+    fn test_closed_period_refuses_moderation() {
+        let repo = setup_repo();
+        let record = ModerationRecord {
+            id: 0,
+            subject_code: "MATH101".into(),
+            evaluation_id: None,
+            period_id: None,
+            description: "bonus de +1 point(s)".into(),
+            applied_by: None,
+            applied_at: String::new(),
+            reverted_at: None,
+            notes: 0,
+        };
+        let changes = [ModeratedNote { note_id: 1, original_value: 8.0, moderated_value: 9.0 }];
+        let id = repo.apply(&record, &changes).unwrap();
+        repo.conn.borrow().execute("INSERT INTO period_locks (period_id, subject_code) VALUES (1, 'MATH101')", []).unwrap();
+
+        assert!(matches!(repo.apply(&record, &changes), Err(ModerationError::Locked(_))));
+        assert!(matches!(repo.revert(id, None), Err(ModerationError::Locked(_))));
+        assert_eq!(value(&repo, 1), 9.0);
+        assert!(repo.get_by_subject("MATH101").unwrap().iter().all(|m| m.reverted_at.is_none()));
+        assert_eq!(repo.get_by_subject("MATH101").unwrap().len(), 1);
+    }
+}
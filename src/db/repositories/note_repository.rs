@@ -1,54 +1,485 @@
 use std::cell::RefCell;
+use std::fmt;
 use std::rc::Rc;
-use crate::db::repositories::repository::Repository;
+use crate::db::repositories::archive_repository::{self, Archivable};
+use crate::db::repositories::lock_repository::{self, PeriodLock};
+use crate::db::repositories::repository::{not_archived, Repository};
+use crate::grading::scale::{GradeError, GradeScale};
 use rusqlite::{params, Connection, OptionalExtension, ToSql};
 
+/// Setting key holding the number of days during which a note can be
+/// changed without giving a reason
+pub const FREE_EDIT_DAYS_SETTING: &str = "notes.free_edit_days";
+pub const DEFAULT_FREE_EDIT_DAYS: u32 = 7;
+
 #[derive(Debug, Clone)]
 pub struct Note {
     pub id: i64,
     pub value: f64,
     pub matricule: String,
     pub subject_code: String,
+    /// Term the note was given in
+    pub period_id: Option<i64>,
+    /// Evaluation the note was obtained in, if it was recorded through one
+    pub evaluation_id: Option<i64>,
+}
+
+/// Note given in a subject that is not taught in the student's class
+#[derive(Debug, Clone)]
+pub struct MisplacedNote {
+    pub note: Note,
+    /// Class of the student the year the note was given
+    pub student_class: String,
+    pub subject_class: String,
+}
+
+/// Value of a note with the class, subject and school year it was given in
+#[derive(Debug, Clone)]
+pub struct PlacedNote {
+    pub value: f64,
+    pub class: String,
+    /// Name of the subject, shared by the parallel classes of a level
+    pub subject_name: String,
+    pub academic_year_id: i64,
+    pub year_label: String,
+    pub period_id: i64,
+}
+
+/// Value of a note at one point of its history
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteVersion {
+    pub version: u32,
+    /// `None` for the version recorded with the note
+    pub previous_value: Option<f64>,
+    pub value: f64,
+    pub changed_by: Option<String>,
+    /// `None` for notes recorded before their history was kept
+    pub changed_at: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// Who changes a note, and why
+#[derive(Debug, Clone, Default)]
+pub struct NoteChange {
+    pub author: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// Why a note cannot be recorded or changed
+#[derive(Debug)]
+pub enum NoteError {
+    Database(rusqlite::Error),
+    UnknownNote(i64),
+    UnknownVersion { note_id: i64, version: u32 },
+    UnknownStudent(String),
+    UnknownSubject(String),
+    InvalidValue(GradeError),
+    /// The note was recorded more than `days` days ago
+    ReasonRequired { days: u32 },
+    /// The period the note belongs to was closed
+    Locked(PeriodLock),
+    /// The subject is taught in another class than the student's
+    WrongClass {
+        subject_code: String,
+        subject_class: String,
+        student_class: String,
+    },
+}
+
+impl fmt::Display for NoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) =>
+                write!(f, "{e}"),
+            Self::UnknownNote(id) =>
+                write!(f, "note n°{id} inconnue"),
+            Self::UnknownVersion { note_id, version } =>
+                write!(f, "la note n°{note_id} n'a pas de version {version}"),
+            Self::UnknownStudent(matricule) =>
+                write!(f, "étudiant {matricule} inconnu"),
+            Self::UnknownSubject(code) =>
+                write!(f, "matière {code} inconnue"),
+            Self::InvalidValue(e) =>
+                write!(f, "{e}"),
+            Self::ReasonRequired { days } =>
+                write!(f, "un motif est obligatoire pour modifier une note enregistrée il y a plus de {days} jours"),
+            Self::Locked(lock) =>
+                write!(f, "la période est close ({lock})"),
+            Self::WrongClass { subject_code, subject_class, student_class } =>
+                write!(f, "la matière {subject_code} est enseignée en {subject_class}, pas en {student_class}"),
+        }
+    }
+}
+
+impl std::error::Error for NoteError {}
+
+impl From<rusqlite::Error> for NoteError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+/// For the `create` and `update` of the repository: a refusal is reported
+/// like a failed constraint
+impl From<NoteError> for rusqlite::Error {
+    fn from(e: NoteError) -> Self {
+        match e {
+            NoteError::Database(e) => e,
+            e => rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT),
+                Some(e.to_string()),
+            ),
+        }
+    }
+}
+
+/// Refuse to touch the notes of `subject_code` during a locked period
+fn check_unlocked(conn: &Connection, period_id: Option<i64>, subject_code: &str) -> Result<(), NoteError> {
+    if let Some(period_id) = period_id
+        && let Some(lock) = lock_repository::covering(conn, period_id, subject_code)?
+    {
+        return Err(NoteError::Locked(lock));
+    }
+    Ok(())
+}
+
+/// Id of the latest note version, to sign the versions written after it
+pub fn last_version_id(conn: &Connection) -> rusqlite::Result<i64> {
+    conn.query_row("SELECT COALESCE(MAX(id), 0) FROM note_history", [], |row| row.get(0))
+}
+
+/// Record who wrote the note versions that came after `since`, and why
+pub fn sign_versions(conn: &Connection, since: i64, author: Option<&str>, reason: Option<&str>) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE note_history SET changed_by = ?1, reason = ?2 WHERE id > ?3",
+        params![author, reason, since],
+    )?;
+    Ok(())
 }
 
 pub struct NoteRepository {
     conn: Rc<RefCell<Connection>>,
+    include_archived: bool,
 }
 
 impl NoteRepository {
     pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
-        NoteRepository { conn }
+        NoteRepository { conn, include_archived: false }
+    }
+
+    /// The same repository, listing the archived notes too
+    pub fn with_archived(&self) -> Self {
+        NoteRepository { conn: self.conn.clone(), include_archived: true }
+    }
+
+    /// Check that the value fits the grading scale, that the student and
+    /// the subject exist, that the subject is taught in the class the
+    /// student attended during the note's school year and that its period
+    /// is not locked
+    pub fn validate(&self, note: &Note, scale: &GradeScale) -> Result<(), NoteError> {
+        scale.check(note.value).map_err(NoteError::InvalidValue)?;
+        let conn = self.conn.borrow();
+
+        // Same lookup as the notes_class_check_insert trigger: the
+        // enrolment of the year of the period, or of the current year
+        let student_class: String = conn.query_row(
+            "SELECT COALESCE(
+                 (SELECT e.class FROM enrolments e
+                  WHERE e.matricule = s.matricule
+                    AND e.academic_year_id = COALESCE(
+                        (SELECT academic_year_id FROM periods WHERE id = ?2),
+                        (SELECT id FROM academic_years WHERE is_current = 1))
+                  ORDER BY e.status = 'enrolled' DESC, e.id DESC
+                  LIMIT 1),
+                 s.class)
+             FROM students s WHERE s.matricule = ?1 AND s.deleted_at IS NULL",
+            params![note.matricule, note.period_id],
+            |row| row.get(0),
+        )
+            .optional()?
+            .ok_or_else(|| NoteError::UnknownStudent(note.matricule.clone()))?;
+        let subject_class: String = conn.query_row(
+            "SELECT class FROM subjects WHERE code = ?1 AND deleted_at IS NULL",
+            params![note.subject_code],
+            |row| row.get(0),
+        )
+            .optional()?
+            .ok_or_else(|| NoteError::UnknownSubject(note.subject_code.clone()))?;
+
+        if subject_class != student_class {
+            return Err(NoteError::WrongClass {
+                subject_code: note.subject_code.clone(),
+                subject_class,
+                student_class,
+            });
+        }
+        check_unlocked(&conn, note.period_id, &note.subject_code)
+    }
+
+    /// Validate then store a note
+    pub fn record(&self, note: &Note, scale: &GradeScale) -> Result<(), NoteError> {
+        self.validate(note, scale)?;
+        self.create(note)?;
+        Ok(())
+    }
+
+    /// Give a note a new value, kept as a new version of the note. A reason
+    /// is required once the note was recorded more than `free_edit_days`
+    /// days ago, or on an unknown date.
+    pub fn change_value(
+        &self,
+        id: i64,
+        value: f64,
+        change: &NoteChange,
+        scale: &GradeScale,
+        free_edit_days: u32,
+    ) -> Result<(), NoteError> {
+        scale.check(value).map_err(NoteError::InvalidValue)?;
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let (current, age, period_id, subject_code): (f64, Option<f64>, Option<i64>, String) = tx.query_row(
+            &format!(
+                "SELECT n.value, julianday('now') - julianday(h.changed_at), n.period_id, n.subject_code
+                 FROM notes n LEFT JOIN note_history h ON h.note_id = n.id AND h.version = 1
+                 WHERE n.id = ?1 AND {}",
+                not_archived("n", self.include_archived)
+            ),
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+            .optional()?
+            .ok_or(NoteError::UnknownNote(id))?;
+        check_unlocked(&tx, period_id, &subject_code)?;
+
+        let reason = change.reason.as_deref().filter(|reason| !reason.trim().is_empty());
+        if reason.is_none() && age.is_none_or(|age| age > free_edit_days as f64) {
+            return Err(NoteError::ReasonRequired { days: free_edit_days });
+        }
+        if value == current {
+            return Ok(());
+        }
+
+        let since = last_version_id(&tx)?;
+        tx.execute("UPDATE notes SET value = ?1 WHERE id = ?2", params![value, id])?;
+        sign_versions(&tx, since, change.author.as_deref(), reason)?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Give a note back the value of one of its versions, as a new version
+    pub fn revert(
+        &self,
+        id: i64,
+        version: u32,
+        change: &NoteChange,
+        scale: &GradeScale,
+        free_edit_days: u32,
+    ) -> Result<(), NoteError> {
+        let value = self
+            .get_history(id)?
+            .into_iter()
+            .find(|v| v.version == version)
+            .ok_or(NoteError::UnknownVersion { note_id: id, version })?
+            .value;
+        self.change_value(id, value, change, scale, free_edit_days)
     }
 
-    pub fn get_by_student(&self, student_matricule: &str) -> rusqlite::Result<Vec<Note>> {
+    /// Versions of a note, oldest first
+    pub fn get_history(&self, id: i64) -> rusqlite::Result<Vec<NoteVersion>> {
         let conn = self.conn.borrow();
         let mut stmt = conn.prepare(
-            "SELECT id, matricule, subject_code, value FROM notes WHERE matricule = ?1"
+            "SELECT version, previous_value, new_value, changed_by, changed_at, reason
+             FROM note_history WHERE note_id = ?1 ORDER BY version"
         )?;
-        let notes = stmt.query_map([student_matricule], |row| {
+
+        let versions = stmt.query_map(params![id], |row| {
+            Ok(NoteVersion {
+                version: row.get(0)?,
+                previous_value: row.get(1)?,
+                value: row.get(2)?,
+                changed_by: row.get(3)?,
+                changed_at: row.get(4)?,
+                reason: row.get(5)?,
+            })
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(versions)
+    }
+
+    /// Notes whose subject is not taught in the class the student attended
+    /// the year the note was given, e.g. after the subject changed class
+    pub fn get_misplaced(&self) -> rusqlite::Result<Vec<MisplacedNote>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT n.id, n.value, n.matricule, n.subject_code, n.period_id, n.evaluation_id, n.class, su.class
+             FROM (
+                 -- Class of the enrolment the note was given in, as the notes_class_check triggers
+                 SELECT notes.*, COALESCE(
+                     (SELECT e.class FROM enrolments e WHERE e.id = notes.enrolment_id AND e.matricule = notes.matricule),
+                     st.class) AS class
+                 FROM notes JOIN students st ON st.matricule = notes.matricule
+             ) n
+             JOIN subjects su ON su.code = n.subject_code
+             WHERE n.class <> su.class AND {}
+             ORDER BY n.class, n.matricule, n.subject_code",
+            not_archived("n", self.include_archived)
+        ))?;
+
+        let notes = stmt.query_map([], |row| {
+            Ok(MisplacedNote {
+                note: Note {
+                    id: row.get(0)?,
+                    value: row.get(1)?,
+                    matricule: row.get(2)?,
+                    subject_code: row.get(3)?,
+                    period_id: row.get(4)?,
+                    evaluation_id: row.get(5)?,
+                },
+                student_class: row.get(6)?,
+                subject_class: row.get(7)?,
+            })
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(notes)
+    }
+
+    /// Notes attached to no period, recorded before periods existed. They
+    /// count in no average until they are attached to one.
+    pub fn get_without_period(&self) -> rusqlite::Result<Vec<Note>> {
+        self.filter("period_id IS NULL ORDER BY matricule, subject_code, id", &[])
+    }
+
+    /// Attach every note without a period to `period_id`, unless the period
+    /// is locked for one of their subjects. Returns the number of notes
+    /// attached.
+    pub fn attach_to_period(&self, period_id: i64) -> Result<usize, NoteError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let subjects = {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT DISTINCT subject_code FROM notes WHERE period_id IS NULL AND {}",
+                not_archived("notes", self.include_archived)
+            ))?;
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        for subject_code in &subjects {
+            check_unlocked(&tx, Some(period_id), subject_code)?;
+        }
+        let attached = tx.execute(
+            &format!(
+                "UPDATE notes SET period_id = ?1 WHERE period_id IS NULL AND {}",
+                not_archived("notes", self.include_archived)
+            ),
+            params![period_id],
+        )?;
+
+        tx.commit()?;
+        Ok(attached)
+    }
+
+    /// Notes whose value does not fit the grading scale, including legacy
+    /// values out of 0 to 20 or that are not numbers
+    pub fn get_out_of_scale(&self, scale: &GradeScale) -> rusqlite::Result<Vec<(Note, GradeError)>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, CASE WHEN typeof(value) IN ('real', 'integer') THEN value END,
+                    matricule, subject_code, period_id, evaluation_id
+             FROM notes WHERE {} ORDER BY id",
+            not_archived("notes", self.include_archived)
+        ))?;
+
+        let notes = stmt.query_map([], |row| {
+            Ok(Note {
+                id: row.get(0)?,
+                value: row.get::<_, Option<f64>>(1)?.unwrap_or(f64::NAN),
+                matricule: row.get(2)?,
+                subject_code: row.get(3)?,
+                period_id: row.get(4)?,
+                evaluation_id: row.get(5)?,
+            })
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(notes
+            .into_iter()
+            .filter_map(|note| scale.check(note.value).err().map(|e| (note, e)))
+            .collect())
+    }
+
+    /// Notes of a student, restricted to `period_id` if given
+    pub fn get_by_student(&self, student_matricule: &str, period_id: Option<i64>) -> rusqlite::Result<Vec<Note>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, matricule, subject_code, value, period_id, evaluation_id FROM notes
+             WHERE matricule = ?1 AND (?2 IS NULL OR period_id = ?2) AND {}",
+            not_archived("notes", self.include_archived)
+        ))?;
+        let notes = stmt.query_map(params![student_matricule, period_id], |row| {
             Ok(Note {
                 id: row.get(0)?,
                 matricule: row.get(1)?,
                 subject_code: row.get(2)?,
                 value: row.get(3)?,
+                period_id: row.get(4)?,
+                evaluation_id: row.get(5)?,
             })
         })?
             .collect::<rusqlite::Result<Vec<_>>>()?;
         Ok(notes)
     }
 
-    pub fn get_by_subject(&self, subject_code: &str) -> rusqlite::Result<Vec<Note>> {
+    /// Notes given in a subject, restricted to `period_id` if given
+    pub fn get_by_subject(&self, subject_code: &str, period_id: Option<i64>) -> rusqlite::Result<Vec<Note>> {
         let conn = self.conn.borrow();
 
-        let mut stmt = conn.prepare(
-            "SELECT id, matricule, subject_code, value FROM notes WHERE subject_code = ?1"
-        )?;
-        let notes = stmt.query_map([subject_code], |row| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, matricule, subject_code, value, period_id, evaluation_id FROM notes
+             WHERE subject_code = ?1 AND (?2 IS NULL OR period_id = ?2) AND {}",
+            not_archived("notes", self.include_archived)
+        ))?;
+        let notes = stmt.query_map(params![subject_code, period_id], |row| {
             Ok(Note {
                 id: row.get(0)?,
                 matricule: row.get(1)?,
                 subject_code: row.get(2)?,
                 value: row.get(3)?,
+                period_id: row.get(4)?,
+                evaluation_id: row.get(5)?,
+            })
+        })?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(notes)
+    }
+
+    /// Notes given in the classes of `level` during a term, oldest school
+    /// year first
+    pub fn get_by_level(&self, level: &str) -> rusqlite::Result<Vec<PlacedNote>> {
+        let conn = self.conn.borrow();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT n.value, su.class, su.name, y.id, y.label, n.period_id
+             FROM notes n
+             JOIN subjects su ON su.code = n.subject_code
+             JOIN classes c ON c.code = su.class
+             JOIN periods p ON p.id = n.period_id
+             JOIN academic_years y ON y.id = p.academic_year_id
+             WHERE c.level = ?1 AND {}
+             ORDER BY y.start_date, su.class, su.name",
+            not_archived("n", self.include_archived)
+        ))?;
+        let notes = stmt.query_map(params![level], |row| {
+            Ok(PlacedNote {
+                value: row.get(0)?,
+                class: row.get(1)?,
+                subject_name: row.get(2)?,
+                academic_year_id: row.get(3)?,
+                year_label: row.get(4)?,
+                period_id: row.get(5)?,
             })
         })?.collect::<rusqlite::Result<Vec<_>>>()?;
         Ok(notes)
@@ -56,21 +487,24 @@ impl NoteRepository {
 }
 
 impl  Repository<Note> for NoteRepository {
+    /// Store a note as is, unless its period is locked
     fn create(&self, note: &Note) -> rusqlite::Result<()> {
         let conn = self.conn.borrow_mut();
+        check_unlocked(&conn, note.period_id, &note.subject_code)?;
 
         conn.execute(
-            "INSERT INTO notes (value, matricule, subject_code) VALUES (?1, ?2, ?3)",
-            params![note.value, note.matricule, note.subject_code]
+            "INSERT INTO notes (value, matricule, subject_code, period_id, evaluation_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![note.value, note.matricule, note.subject_code, note.period_id, note.evaluation_id]
         )?;
         Ok(())
     }
 
     fn get_all(&self) -> rusqlite::Result<Vec<Note>> {
         let conn = self.conn.borrow();
-        let mut stmt = conn.prepare(
-            "SELECT id, value, matricule, subject_code FROM notes"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, value, matricule, subject_code, period_id, evaluation_id FROM notes WHERE {}",
+            not_archived("notes", self.include_archived)
+        ))?;
 
         let notes = stmt.query_map([], |row| {
             Ok(Note {
@@ -78,6 +512,8 @@ impl  Repository<Note> for NoteRepository {
                 value: row.get(1)?,
                 matricule: row.get(2)?,
                 subject_code: row.get(3)?,
+                period_id: row.get(4)?,
+                evaluation_id: row.get(5)?,
             })
         })?
             .collect::<rusqlite::Result<Vec<Note>>>()?;
@@ -89,41 +525,59 @@ impl  Repository<Note> for NoteRepository {
         let conn = self.conn.borrow();
 
         conn.query_row(
-            "SELECT id, value, matricule, subject_code FROM notes WHERE id = ?1",
+            &format!(
+                "SELECT id, value, matricule, subject_code, period_id, evaluation_id FROM notes WHERE id = ?1 AND {}",
+                not_archived("notes", self.include_archived)
+            ),
             params![id],
             |row| Ok(Note{
                 id: row.get(0)?,
                 value: row.get(1)?,
                 matricule: row.get(2)?,
                 subject_code: row.get(3)?,
+                period_id: row.get(4)?,
+                evaluation_id: row.get(5)?,
             })
         ).optional()
     }
 
+    /// Overwrite a note, unless it leaves or enters a locked period
     fn update(&self, item: &Note) -> rusqlite::Result<()> {
         let conn = self.conn.borrow_mut();
+        let current: Option<(Option<i64>, String)> = conn
+            .query_row(
+                "SELECT period_id, subject_code FROM notes WHERE id = ?1",
+                params![item.id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        if let Some((period_id, subject_code)) = current {
+            check_unlocked(&conn, period_id, &subject_code)?;
+        }
+        check_unlocked(&conn, item.period_id, &item.subject_code)?;
 
         conn.execute(
-            "UPDATE notes SET value=?1, matricule=?2, subject_code=?3 WHERE id = ?4",
-            params![item.value, item.matricule, item.subject_code, item.id]
+            "UPDATE notes SET value=?1, matricule=?2, subject_code=?3, period_id=?4, evaluation_id=?5 WHERE id = ?6",
+            params![item.value, item.matricule, item.subject_code, item.period_id, item.evaluation_id, item.id]
         )?;
         Ok(())
     }
 
+    /// Archive the note: only a purge removes a note for good
     fn delete(&self, id: &str) -> rusqlite::Result<()> {
-        let conn = self.conn.borrow_mut();
-        conn.execute(
-            "DELETE FROM notes WHERE id = ?1",
-            params![id]
-        )?;
-        Ok(())
+        let id = id.parse().map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+        archive_repository::archive_in(&tx, &Archivable::Note(id))?;
+        tx.commit()
     }
 
     fn filter(&self, filter: &str, params: &[&dyn ToSql]) -> rusqlite::Result<Vec<Note>> {
         let conn = self.conn.borrow();
 
         let query = format!(
-            "SELECT id, value, matricule, subject_code FROM notes WHERE {}",
+            "SELECT id, value, matricule, subject_code, period_id, evaluation_id FROM notes WHERE {} AND {}",
+            not_archived("notes", self.include_archived),
             filter
         );
 
@@ -134,6 +588,8 @@ impl  Repository<Note> for NoteRepository {
                 value: row.get(1)?,
                 matricule: row.get(2)?,
                 subject_code: row.get(3)?,
+                period_id: row.get(4)?,
+                evaluation_id: row.get(5)?,
             })
         })?
             .collect::<rusqlite::Result<Vec<Note>>>()?;
@@ -148,12 +604,19 @@ mod tests {
     use super::*;
     use std::rc::Rc;
     use std::cell::RefCell;
+    use crate::db::repositories::archive_repository::ArchiveError;
+    use crate::db::repositories::enrolment_repository::EnrolmentRepository;
+    use crate::db::repositories::student_repository::Student;
+    use crate::grading::promotion::{Outcome, PromotionMove};
+    use crate::grading::rules::Decision;
 
     fn setup_repo() -> NoteRepository {
         let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
         let schema = include_str!("../../../sql/schema.sql");
         crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
 
+        conn.borrow().execute_batch("INSERT INTO classes (code, level) VALUES ('10A', '10'), ('10B', '10')").unwrap();
+
         // Insert all students referenced in sample_notes
         conn.borrow_mut().execute(
             "INSERT INTO students (matricule, name, surname, class) VALUES (?1, ?2, ?3, ?4)",
@@ -188,18 +651,24 @@ mod tests {
                 value: 15f64,
                 matricule: "S001".into(),
                 subject_code: "MATH101".into(),
+                period_id: Some(1),
+                evaluation_id: None,
             },
             Note {
                 id: 2,
                 value: 12f64,
                 matricule: "S002".into(),
                 subject_code: "PHY101".into(),
+                period_id: Some(1),
+                evaluation_id: None,
             },
             Note {
                 id: 3,
                 value: 18f64,
                 matricule: "S001".into(),
                 subject_code: "PHY101".into(),
+                period_id: Some(2),
+                evaluation_id: None,
             },
         ]
     }
@@ -250,6 +719,8 @@ mod tests {
         repo.delete(&note.id.to_string()).unwrap();
         let deleted = repo.get(&note.id.to_string()).unwrap();
         assert!(deleted.is_none());
+        // Archived, not removed
+        assert!(repo.with_archived().get(&note.id.to_string()).unwrap().is_some());
     }
 
     #[test]
@@ -272,6 +743,19 @@ mod tests {
         assert_eq!(filtered.len(), 0);
     }
 
+    #[test]
+    fn test_notes_by_period() {
+        let repo = setup_repo();
+        for note in sample_notes() {
+            repo.create(&note).unwrap();
+        }
+
+        assert_eq!(repo.get_by_student("S001", None).unwrap().len(), 2);
+        assert_eq!(repo.get_by_student("S001", Some(2)).unwrap().len(), 1);
+        assert_eq!(repo.get_by_subject("PHY101", Some(1)).unwrap()[0].matricule, "S002");
+        assert!(repo.get_by_subject("MATH101", Some(3)).unwrap().is_empty());
+    }
+
     #[test]
     fn test_multiple_creates() {
         let repo = setup_repo();
@@ -284,5 +768,270 @@ mod tests {
         let all = repo.get_all().unwrap();
         assert_eq!(all.len(), 3);
     }
-}
 
+    #[test]
+    fn test_note_subject_must_be_taught_in_student_class() {
+        let repo = setup_repo();
+        repo.conn.borrow().execute(
+            "INSERT INTO subjects (code, name, class, coefficient) VALUES ('ENG101', 'English', '10B', 2)",
+            [],
+        ).unwrap();
+        let mut note = sample_notes()[0].clone();
+        note.subject_code = "ENG101".into();
+
+        let scale = GradeScale::default();
+        assert!(matches!(repo.validate(&note, &scale), Err(NoteError::WrongClass { .. })));
+        assert!(matches!(repo.record(&note, &scale), Err(NoteError::WrongClass { .. })));
+        // The database rejects it too
+        assert!(repo.create(&note).is_err());
+
+        note.matricule = "S999".into();
+        assert!(matches!(repo.validate(&note, &scale), Err(NoteError::UnknownStudent(_))));
+
+        repo.record(&sample_notes()[0], &scale).unwrap();
+        let mut moved = sample_notes()[0].clone();
+        moved.subject_code = "ENG101".into();
+        assert!(repo.update(&moved).is_err());
+    }
+
+    #[test]
+    fn test_note_class_follows_the_enrolment_of_its_year() {
+        let repo = setup_repo();
+        repo.conn.borrow().execute_batch(
+            "INSERT INTO subjects (code, name, class, coefficient) VALUES ('ENG101', 'English', '10B', 2);
+             INSERT INTO academic_years (id, label, start_date, end_date, period_kind)
+                 VALUES (2, '2026-2027', '2026-09-01', '2027-07-02', 'semestre');
+             INSERT INTO periods (id, academic_year_id, number, start_date, end_date)
+                 VALUES (10, 2, 1, '2026-09-01', '2027-01-29');
+             INSERT INTO enrolments (matricule, class, academic_year_id) VALUES ('S001', '10B', 2);",
+        ).unwrap();
+        let scale = GradeScale::default();
+        let note = |subject_code: &str, period_id: i64| Note {
+            period_id: Some(period_id),
+            subject_code: subject_code.into(),
+            ..sample_notes()[0].clone()
+        };
+
+        // S001 attends 10A this year and 10B next year
+        repo.record(&note("MATH101", 1), &scale).unwrap();
+        repo.record(&note("ENG101", 10), &scale).unwrap();
+        assert!(matches!(repo.record(&note("ENG101", 1), &scale), Err(NoteError::WrongClass { .. })));
+        assert!(matches!(repo.record(&note("MATH101", 10), &scale), Err(NoteError::WrongClass { .. })));
+
+        // Once promoted, notes of the past year are still checked against it
+        repo.conn.borrow().execute("UPDATE students SET class = '10B' WHERE matricule = 'S001'", []).unwrap();
+        repo.record(&note("MATH101", 1), &scale).unwrap();
+        assert!(matches!(repo.record(&note("ENG101", 1), &scale), Err(NoteError::WrongClass { .. })));
+    }
+
+    #[test]
+    fn test_misplaced_notes() {
+        let repo = setup_repo();
+        for note in sample_notes() {
+            repo.create(&note).unwrap();
+        }
+        assert!(repo.get_misplaced().unwrap().is_empty());
+
+        // PHY101 moves to 10B after S001 and S002 were graded in it
+        repo.conn.borrow().execute("UPDATE subjects SET class = '10B' WHERE code = 'PHY101'", []).unwrap();
+
+        let misplaced = repo.get_misplaced().unwrap();
+        assert_eq!(misplaced.len(), 2);
+        assert_eq!(misplaced[0].note.matricule, "S001");
+        assert_eq!(misplaced[1].note.matricule, "S002");
+        assert_eq!(misplaced[1].student_class, "10A");
+        assert_eq!(misplaced[1].subject_class, "10B");
+    }
+
+    #[test]
+    fn test_past_year_notes_are_not_misplaced_after_promotion() {
+        let repo = setup_repo();
+        for note in sample_notes() {
+            repo.create(&note).unwrap();
+        }
+        repo.conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('11A', '11');
+             INSERT INTO academic_years (id, label, start_date, end_date, period_kind)
+                 VALUES (2, '2026-2027', '2026-09-01', '2027-07-02', 'semestre');
+             UPDATE academic_years SET is_current = (id = 2);",
+        ).unwrap();
+
+        let promotion = PromotionMove {
+            student: Student { matricule: "S001".into(), name: "Alice".into(), surname: "Smith".into(), class: "10A".into() },
+            decision: Some(Decision::Admitted),
+            outcome: Outcome::Promoted("11A".into()),
+        };
+        EnrolmentRepository::new(repo.conn.clone()).promote(1, 2, &[promotion]).unwrap();
+        let class: String = repo.conn.borrow()
+            .query_row("SELECT class FROM students WHERE matricule = 'S001'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(class, "11A");
+
+        assert!(repo.get_misplaced().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_attach_notes_without_period() {
+        let repo = setup_repo();
+        for mut note in sample_notes() {
+            note.period_id = None;
+            repo.create(&note).unwrap();
+        }
+        assert_eq!(repo.get_without_period().unwrap().len(), 3);
+        assert!(repo.get_by_student("S001", Some(1)).unwrap().is_empty());
+
+        repo.conn.borrow().execute(
+            "INSERT INTO period_locks (period_id, subject_code) VALUES (1, 'PHY101')", [],
+        ).unwrap();
+        assert!(matches!(repo.attach_to_period(1), Err(NoteError::Locked(_))));
+        assert_eq!(repo.get_without_period().unwrap().len(), 3);
+
+        assert_eq!(repo.attach_to_period(2).unwrap(), 3);
+        assert!(repo.get_without_period().unwrap().is_empty());
+        assert_eq!(repo.get_by_student("S001", Some(2)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_values_outside_the_scale() {
+        use crate::grading::scale::ScaleStep;
+
+        let repo = setup_repo();
+        let mut note = sample_notes()[0].clone();
+
+        note.value = 25.0;
+        assert!(matches!(repo.record(&note, &GradeScale::default()), Err(NoteError::InvalidValue(_))));
+        // The database rejects it too
+        assert!(repo.create(&note).is_err());
+        note.value = -1.0;
+        assert!(repo.create(&note).is_err());
+
+        note.value = 12.25;
+        repo.record(&note, &GradeScale::default()).unwrap();
+        let half_points = GradeScale::new(0.0, 20.0, ScaleStep::HalfPoint).unwrap();
+        assert!(repo.get_out_of_scale(&GradeScale::default()).unwrap().is_empty());
+
+        let out_of_scale = repo.get_out_of_scale(&half_points).unwrap();
+        assert_eq!(out_of_scale.len(), 1);
+        assert!(matches!(out_of_scale[0].1, GradeError::OffStep { .. }));
+    }
+
+    #[test]
+    fn test_note_history_and_revert() {
+        let repo = setup_repo();
+        repo.create(&sample_notes()[0]).unwrap();
+        let scale = GradeScale::default();
+        let change = NoteChange { author: Some("admin".into()), reason: None };
+
+        repo.change_value(1, 13.0, &change, &scale, DEFAULT_FREE_EDIT_DAYS).unwrap();
+        // Unsigned changes are kept too
+        let mut note = repo.get("1").unwrap().unwrap();
+        note.value = 14.0;
+        repo.update(&note).unwrap();
+
+        let history = repo.get_history(1).unwrap();
+        let values: Vec<(Option<f64>, f64)> = history.iter().map(|v| (v.previous_value, v.value)).collect();
+        assert_eq!(values, vec![(None, 15.0), (Some(15.0), 13.0), (Some(13.0), 14.0)]);
+        assert_eq!(history[1].changed_by.as_deref(), Some("admin"));
+        assert_eq!(history[2].changed_by, None);
+
+        repo.revert(1, 1, &change, &scale, DEFAULT_FREE_EDIT_DAYS).unwrap();
+        assert_eq!(repo.get("1").unwrap().unwrap().value, 15.0);
+        assert_eq!(repo.get_history(1).unwrap().len(), 4);
+
+        assert!(matches!(repo.revert(1, 9, &change, &scale, 7), Err(NoteError::UnknownVersion { .. })));
+        assert!(matches!(repo.change_value(9, 10.0, &change, &scale, 7), Err(NoteError::UnknownNote(9))));
+        assert!(matches!(repo.change_value(1, 25.0, &change, &scale, 7), Err(NoteError::InvalidValue(_))));
+    }
+
+    #[test]
+    fn test_reason_required_after_deadline() {
+        let repo = setup_repo();
+        repo.create(&sample_notes()[0]).unwrap();
+        repo.conn.borrow().execute("UPDATE note_history SET changed_at = datetime('now', '-10 days')", []).unwrap();
+        let scale = GradeScale::default();
+
+        let mut change = NoteChange { author: None, reason: Some(" ".into()) };
+        assert!(matches!(repo.change_value(1, 12.0, &change, &scale, 7), Err(NoteError::ReasonRequired { days: 7 })));
+        repo.change_value(1, 12.0, &change, &scale, 30).unwrap();
+
+        change.reason = Some("erreur de report".into());
+        repo.change_value(1, 11.0, &change, &scale, 7).unwrap();
+        assert_eq!(repo.get_history(1).unwrap()[2].reason.as_deref(), Some("erreur de report"));
+
+        // Notes older than their history have no known date: past the deadline
+        repo.conn.borrow().execute("UPDATE note_history SET changed_at = NULL WHERE version = 1", []).unwrap();
+        change.reason = None;
+        assert!(matches!(repo.change_value(1, 12.0, &change, &scale, 30), Err(NoteError::ReasonRequired { days: 30 })));
+    }
+
+    #[test]
+    fn test_history_outlives_the_note() {
+        let repo = setup_repo();
+        repo.create(&sample_notes()[0]).unwrap();
+        repo.conn.borrow().execute("DELETE FROM notes WHERE id = 1", []).unwrap();
+
+        assert_eq!(repo.get_history(1).unwrap().len(), 1);
+        repo.create(&sample_notes()[0]).unwrap();
+        assert_eq!(repo.get_history(2).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_locked_period_refuses_changes() {
+        let repo = setup_repo();
+        for note in sample_notes() {
+            repo.create(&note).unwrap();
+        }
+        repo.conn.borrow().execute(
+            "INSERT INTO period_locks (period_id, subject_code) VALUES (1, 'MATH101')", [],
+        ).unwrap();
+        let scale = GradeScale::default();
+        let change = NoteChange::default();
+
+        assert!(matches!(repo.record(&sample_notes()[0], &scale), Err(NoteError::Locked(_))));
+        assert!(matches!(repo.change_value(1, 10.0, &change, &scale, 7), Err(NoteError::Locked(_))));
+        let mut note = repo.get("1").unwrap().unwrap();
+        note.value = 10.0;
+        let error = repo.update(&note).unwrap_err().to_string();
+        assert!(error.starts_with("la période est close"), "{}", error);
+        // Nor can a note be moved into the locked period
+        let mut note = repo.get("2").unwrap().unwrap();
+        note.subject_code = "MATH101".into();
+        assert!(repo.update(&note).unwrap_err().to_string().starts_with("la période est close"));
+        assert!(repo.create(&sample_notes()[0]).unwrap_err().to_string().starts_with("la période est close"));
+        assert!(matches!(
+            archive_repository::archive_in(&repo.conn.borrow(), &Archivable::Note(1)),
+            Err(ArchiveError::Locked(_))
+        ));
+        assert_eq!(repo.get("1").unwrap().unwrap().value, 15.0);
+
+        // Other subjects and periods stay open
+        repo.change_value(2, 10.0, &change, &scale, 7).unwrap();
+        archive_repository::archive_in(&repo.conn.borrow(), &Archivable::Note(3)).unwrap();
+        assert!(repo.get("3").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_notes_by_level() {
+        let repo = setup_repo();
+        for note in sample_notes() {
+            repo.create(&note).unwrap();
+        }
+        repo.conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('11A', '11');
+             INSERT INTO students (matricule, name, surname, class) VALUES ('S003', 'Carol', 'White', '10B');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES ('MATH102', 'Mathematics', '10B', 4);
+             INSERT INTO notes (matricule, subject_code, value, period_id) VALUES ('S003', 'MATH102', 9, 1);",
+        ).unwrap();
+
+        let notes = repo.get_by_level("10").unwrap();
+        assert_eq!(notes.len(), 4);
+        let maths: Vec<&str> = notes
+            .iter()
+            .filter(|n| n.subject_name == "Mathematics")
+            .map(|n| n.class.as_str())
+            .collect();
+        assert_eq!(maths, vec!["10A", "10B"]);
+        assert_eq!(notes[0].year_label, "2025-2026");
+        assert!(repo.get_by_level("11").unwrap().is_empty());
+    }
+}
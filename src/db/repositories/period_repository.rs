@@ -0,0 +1,281 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use crate::db::repositories::enrolment_repository::SYNC_STUDENT_CLASSES;
+
+/// How a school year is divided
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PeriodKind {
+    Trimester,
+    Semester,
+}
+
+impl PeriodKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trimester => "trimestre",
+            Self::Semester => "semestre",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "trimestre" => Some(Self::Trimester),
+            "semestre" => Some(Self::Semester),
+            _ => None,
+        }
+    }
+
+    /// Number of periods in a school year
+    pub fn count(self) -> u32 {
+        match self {
+            Self::Trimester => 3,
+            Self::Semester => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcademicYear {
+    pub id: i64,
+    /// e.g. "2025-2026"
+    pub label: String,
+    /// Dates are stored as YYYY-MM-DD
+    pub start_date: String,
+    pub end_date: String,
+    pub period_kind: PeriodKind,
+    pub is_current: bool,
+}
+
+/// Term (trimestre or semestre) of an academic year
+#[derive(Debug, Clone, PartialEq)]
+pub struct Period {
+    pub id: i64,
+    pub academic_year_id: i64,
+    pub kind: PeriodKind,
+    pub number: u32,
+    pub start_date: String,
+    pub end_date: String,
+}
+
+impl fmt::Display for Period {
+    /// e.g. "1er trimestre" or "2e semestre"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suffix = if self.number == 1 { "er" } else { "e" };
+        write!(f, "{}{} {}", self.number, suffix, self.kind.as_str())
+    }
+}
+
+fn kind_from_row(row: &Row, index: usize) -> rusqlite::Result<PeriodKind> {
+    let value: String = row.get(index)?;
+    PeriodKind::parse(&value).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            index,
+            rusqlite::types::Type::Text,
+            format!("unknown period kind: {}", value).into(),
+        )
+    })
+}
+
+fn year_from_row(row: &Row) -> rusqlite::Result<AcademicYear> {
+    Ok(AcademicYear {
+        id: row.get(0)?,
+        label: row.get(1)?,
+        start_date: row.get(2)?,
+        end_date: row.get(3)?,
+        period_kind: kind_from_row(row, 4)?,
+        is_current: row.get(5)?,
+    })
+}
+
+fn period_from_row(row: &Row) -> rusqlite::Result<Period> {
+    Ok(Period {
+        id: row.get(0)?,
+        academic_year_id: row.get(1)?,
+        kind: kind_from_row(row, 2)?,
+        number: row.get(3)?,
+        start_date: row.get(4)?,
+        end_date: row.get(5)?,
+    })
+}
+
+const YEAR_COLUMNS: &str = "id, label, start_date, end_date, period_kind, is_current";
+
+const PERIOD_QUERY: &str =
+    "SELECT p.id, p.academic_year_id, y.period_kind, p.number, p.start_date, p.end_date
+     FROM periods p JOIN academic_years y ON y.id = p.academic_year_id";
+
+pub struct PeriodRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl PeriodRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Academic years, the most recent first
+    pub fn get_academic_years(&self) -> rusqlite::Result<Vec<AcademicYear>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM academic_years ORDER BY start_date DESC",
+            YEAR_COLUMNS
+        ))?;
+
+        let years = stmt.query_map([], year_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(years)
+    }
+
+    pub fn get_current_year(&self) -> rusqlite::Result<Option<AcademicYear>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            &format!("SELECT {} FROM academic_years WHERE is_current = 1", YEAR_COLUMNS),
+            [],
+            year_from_row,
+        )
+            .optional()
+    }
+
+    /// Create an academic year with its periods and return its id.
+    /// Nothing is stored if the year or one of its periods is invalid.
+    pub fn create_academic_year(&self, year: &AcademicYear, periods: &[Period]) -> rusqlite::Result<i64> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "INSERT INTO academic_years (label, start_date, end_date, period_kind, is_current)
+             VALUES (?1, ?2, ?3, ?4, 0)",
+            params![year.label, year.start_date, year.end_date, year.period_kind.as_str()],
+        )?;
+        let year_id = tx.last_insert_rowid();
+
+        for period in periods {
+            tx.execute(
+                "INSERT INTO periods (academic_year_id, number, start_date, end_date) VALUES (?1, ?2, ?3, ?4)",
+                params![year_id, period.number, period.start_date, period.end_date],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(year_id)
+    }
+
+    /// Make `year_id` the current academic year. Students move to the class
+    /// they are enrolled in for that year.
+    pub fn set_current_year(&self, year_id: i64) -> rusqlite::Result<()> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "UPDATE academic_years SET is_current = (id = ?1)",
+            params![year_id],
+        )?;
+        tx.execute(SYNC_STUDENT_CLASSES, params![year_id])?;
+
+        tx.commit()
+    }
+
+    pub fn get_period(&self, period_id: i64) -> rusqlite::Result<Option<Period>> {
+        let conn = self.conn.borrow();
+        conn.query_row(&format!("{} WHERE p.id = ?1", PERIOD_QUERY), params![period_id], period_from_row)
+            .optional()
+    }
+
+    /// Periods of an academic year, in chronological order
+    pub fn get_periods(&self, year_id: i64) -> rusqlite::Result<Vec<Period>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE p.academic_year_id = ?1 ORDER BY p.number",
+            PERIOD_QUERY
+        ))?;
+
+        let periods = stmt.query_map(params![year_id], period_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(periods)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn setup_repo() -> PeriodRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        PeriodRepository::new(conn)
+    }
+
+    fn year(label: &str, start_date: &str, end_date: &str) -> AcademicYear {
+        AcademicYear {
+            id: 0,
+            label: label.into(),
+            start_date: start_date.into(),
+            end_date: end_date.into(),
+            period_kind: PeriodKind::Semester,
+            is_current: false,
+        }
+    }
+
+    fn period(number: u32, start_date: &str, end_date: &str) -> Period {
+        Period {
+            id: 0,
+            academic_year_id: 0,
+            kind: PeriodKind::Semester,
+            number,
+            start_date: start_date.into(),
+            end_date: end_date.into(),
+        }
+    }
+
+    #[test]
+    fn test_default_year_has_three_terms() {
+        let repo = setup_repo();
+
+        let current = repo.get_current_year().unwrap().unwrap();
+        assert_eq!(current.label, "2025-2026");
+        assert_eq!(current.period_kind, PeriodKind::Trimester);
+
+        let periods = repo.get_periods(current.id).unwrap();
+        assert_eq!(periods.len(), 3);
+        assert_eq!(periods[0].to_string(), "1er trimestre");
+        assert_eq!(periods[2].to_string(), "3e trimestre");
+    }
+
+    #[test]
+    fn test_create_year_and_switch_current() {
+        let repo = setup_repo();
+
+        let periods = vec![period(1, "2026-09-01", "2027-01-29"), period(2, "2027-02-01", "2027-07-02")];
+        let id = repo.create_academic_year(&year("2026-2027", "2026-09-01", "2027-07-02"), &periods).unwrap();
+        repo.set_current_year(id).unwrap();
+
+        let current = repo.get_current_year().unwrap().unwrap();
+        assert_eq!(current.id, id);
+        assert_eq!(repo.get_academic_years().unwrap().len(), 2);
+
+        let periods = repo.get_periods(id).unwrap();
+        assert_eq!(periods[1].to_string(), "2e semestre");
+        assert_eq!(periods[1].academic_year_id, id);
+    }
+
+    #[test]
+    fn test_invalid_dates_are_rejected() {
+        let repo = setup_repo();
+
+        assert!(repo.create_academic_year(&year("2027-2028", "2028-07-01", "2027-09-01"), &[]).is_err());
+        assert!(repo.create_academic_year(&year("2028-2029", "septembre", "2029-07-01"), &[]).is_err());
+
+        // A year is not stored without its periods
+        let periods = vec![period(1, "2029-09-01", "2030-02-30")];
+        assert!(repo.create_academic_year(&year("2029-2030", "2029-09-01", "2030-07-01"), &periods).is_err());
+        assert_eq!(repo.get_academic_years().unwrap().len(), 1);
+    }
+}
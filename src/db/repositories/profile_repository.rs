@@ -0,0 +1,359 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use crate::db::repositories::student_repository::Student;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+impl Gender {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Male => "M",
+            Self::Female => "F",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "M" => Some(Self::Male),
+            "F" => Some(Self::Female),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Male => write!(f, "Masculin"),
+            Self::Female => write!(f, "Féminin"),
+        }
+    }
+}
+
+/// Civil status and contact details of a student
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StudentProfile {
+    pub matricule: String,
+    /// AAAA-MM-JJ
+    pub birth_date: Option<String>,
+    pub birth_place: Option<String>,
+    pub gender: Option<Gender>,
+    pub photo_path: Option<String>,
+    pub address: Option<String>,
+}
+
+impl StudentProfile {
+    /// Empty profile of a student who has none yet
+    pub fn new(matricule: &str) -> Self {
+        Self { matricule: matricule.to_string(), ..Default::default() }
+    }
+
+    /// e.g. "2013-04-02 à Douala", `None` if neither is known
+    pub fn birth_label(&self) -> Option<String> {
+        match (&self.birth_date, &self.birth_place) {
+            (Some(date), Some(place)) => Some(format!("{} à {}", date, place)),
+            (Some(date), None) => Some(date.clone()),
+            (None, Some(place)) => Some(format!("à {}", place)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Parent or tutor of a student
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guardian {
+    pub id: i64,
+    pub matricule: String,
+    pub name: String,
+    /// e.g. "père", "mère", "tuteur"
+    pub relation: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+}
+
+impl Guardian {
+    /// Phone and email, e.g. "699 00 11 22, jean@example.com"
+    pub fn contact(&self) -> String {
+        [&self.phone, &self.email]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Guardian and their contact, e.g. "Jean Ndzi (père) — 699 00 11 22"
+    pub fn label(&self) -> String {
+        match self.contact().as_str() {
+            "" => self.to_string(),
+            contact => format!("{} — {}", self, contact),
+        }
+    }
+}
+
+impl fmt::Display for Guardian {
+    /// e.g. "Jean Ndzi (père)"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.relation)
+    }
+}
+
+fn profile_from_row(row: &Row) -> rusqlite::Result<StudentProfile> {
+    let gender: Option<String> = row.get(3)?;
+    Ok(StudentProfile {
+        matricule: row.get(0)?,
+        birth_date: row.get(1)?,
+        birth_place: row.get(2)?,
+        gender: gender.as_deref().and_then(Gender::parse),
+        photo_path: row.get(4)?,
+        address: row.get(5)?,
+    })
+}
+
+fn guardian_from_row(row: &Row) -> rusqlite::Result<Guardian> {
+    Ok(Guardian {
+        id: row.get(0)?,
+        matricule: row.get(1)?,
+        name: row.get(2)?,
+        relation: row.get(3)?,
+        phone: row.get(4)?,
+        email: row.get(5)?,
+    })
+}
+
+const PROFILE_COLUMNS: &str = "matricule, birth_date, birth_place, gender, photo_path, address";
+const GUARDIAN_COLUMNS: &str = "id, matricule, name, relation, phone, email";
+
+/// Phone number without the separators people type between digits
+fn compact_phone(phone: &str) -> String {
+    phone.chars().filter(|c| !matches!(c, ' ' | '.' | '-')).collect()
+}
+
+pub struct ProfileRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl ProfileRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    pub fn get_profile(&self, matricule: &str) -> rusqlite::Result<Option<StudentProfile>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            &format!("SELECT {} FROM student_profiles WHERE matricule = ?1", PROFILE_COLUMNS),
+            params![matricule],
+            profile_from_row,
+        )
+            .optional()
+    }
+
+    /// Profile of every student who has one, by matricule
+    pub fn get_profiles(&self) -> rusqlite::Result<HashMap<String, StudentProfile>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM student_profiles", PROFILE_COLUMNS))?;
+
+        let profiles = stmt.query_map([], |row| {
+            let profile = profile_from_row(row)?;
+            Ok((profile.matricule.clone(), profile))
+        })?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        Ok(profiles)
+    }
+
+    /// Create the profile of the student or replace it
+    pub fn save_profile(&self, profile: &StudentProfile) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO student_profiles (matricule, birth_date, birth_place, gender, photo_path, address)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(matricule) DO UPDATE SET
+                birth_date = excluded.birth_date,
+                birth_place = excluded.birth_place,
+                gender = excluded.gender,
+                photo_path = excluded.photo_path,
+                address = excluded.address",
+            params![
+                profile.matricule,
+                profile.birth_date,
+                profile.birth_place,
+                profile.gender.map(Gender::as_str),
+                profile.photo_path,
+                profile.address
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_guardians(&self, matricule: &str) -> rusqlite::Result<Vec<Guardian>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            &format!("SELECT {} FROM guardians WHERE matricule = ?1 ORDER BY id", GUARDIAN_COLUMNS)
+        )?;
+
+        let guardians = stmt.query_map(params![matricule], guardian_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(guardians)
+    }
+
+    /// Guardians of every student, by matricule
+    pub fn get_all_guardians(&self) -> rusqlite::Result<HashMap<String, Vec<Guardian>>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM guardians ORDER BY id", GUARDIAN_COLUMNS))?;
+
+        let mut guardians: HashMap<String, Vec<Guardian>> = HashMap::new();
+        for guardian in stmt.query_map([], guardian_from_row)? {
+            let guardian = guardian?;
+            guardians.entry(guardian.matricule.clone()).or_default().push(guardian);
+        }
+
+        Ok(guardians)
+    }
+
+    pub fn add_guardian(&self, guardian: &Guardian) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO guardians (matricule, name, relation, phone, email) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![guardian.matricule, guardian.name, guardian.relation, guardian.phone, guardian.email],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_guardian(&self, guardian: &Guardian) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "UPDATE guardians SET name = ?1, relation = ?2, phone = ?3, email = ?4 WHERE id = ?5",
+            params![guardian.name, guardian.relation, guardian.phone, guardian.email, guardian.id],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a guardian of the student, `false` if they have no such guardian
+    pub fn delete_guardian(&self, matricule: &str, id: i64) -> rusqlite::Result<bool> {
+        let conn = self.conn.borrow_mut();
+        let deleted = conn.execute(
+            "DELETE FROM guardians WHERE id = ?1 AND matricule = ?2",
+            params![id, matricule],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Students with a guardian whose name contains `query`, or whose phone
+    /// number contains it once spaces, dots and dashes are ignored
+    pub fn search_by_guardian(&self, query: &str) -> rusqlite::Result<Vec<(Student, Guardian)>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT s.matricule, s.name, s.surname, s.class,
+                    g.id, g.matricule, g.name, g.relation, g.phone, g.email
+             FROM guardians g JOIN students s ON s.matricule = g.matricule
+             WHERE g.name LIKE '%' || ?1 || '%'
+                OR (?2 <> '' AND REPLACE(REPLACE(REPLACE(g.phone, ' ', ''), '.', ''), '-', '') LIKE '%' || ?2 || '%')
+             ORDER BY s.name, s.surname, g.id"
+        )?;
+
+        let results = stmt.query_map(params![query, compact_phone(query)], |row| {
+            Ok((
+                Student {
+                    matricule: row.get(0)?,
+                    name: row.get(1)?,
+                    surname: row.get(2)?,
+                    class: row.get(3)?,
+                },
+                Guardian {
+                    id: row.get(4)?,
+                    matricule: row.get(5)?,
+                    name: row.get(6)?,
+                    relation: row.get(7)?,
+                    phone: row.get(8)?,
+                    email: row.get(9)?,
+                },
+            ))
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_repo() -> ProfileRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10');
+             INSERT INTO students (matricule, name, surname, class) VALUES
+                ('S001', 'Alice', 'Smith', '10A'),
+                ('S002', 'Bob', 'Jones', '10A');",
+        ).unwrap();
+
+        ProfileRepository::new(conn)
+    }
+
+    fn guardian(matricule: &str, name: &str, phone: Option<&str>) -> Guardian {
+        Guardian {
+            id: 0,
+            matricule: matricule.into(),
+            name: name.into(),
+            relation: "mère".into(),
+            phone: phone.map(str::to_string),
+            email: None,
+        }
+    }
+
+    #[test]
+    fn test_save_profile() {
+        let repo = setup_repo();
+        assert!(repo.get_profile("S001").unwrap().is_none());
+
+        let mut profile = StudentProfile::new("S001");
+        profile.birth_date = Some("2010-05-14".into());
+        profile.gender = Some(Gender::Female);
+        repo.save_profile(&profile).unwrap();
+
+        profile.birth_place = Some("Douala".into());
+        repo.save_profile(&profile).unwrap();
+        let saved = repo.get_profile("S001").unwrap().unwrap();
+        assert_eq!(saved, profile);
+        assert_eq!(saved.birth_label().unwrap(), "2010-05-14 à Douala");
+
+        profile.birth_date = Some("14 mai 2010".into());
+        assert!(repo.save_profile(&profile).is_err());
+        assert_eq!(repo.get_profiles().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_by_guardian_name_or_phone() {
+        let repo = setup_repo();
+        repo.add_guardian(&guardian("S001", "Marie Smith", Some("+237 699-00-11-22"))).unwrap();
+        repo.add_guardian(&guardian("S002", "Claire Jones", Some("677 12 34 56"))).unwrap();
+        repo.add_guardian(&guardian("S002", "Paul Jones", None)).unwrap();
+
+        let by_name = repo.search_by_guardian("jones").unwrap();
+        assert_eq!(by_name.len(), 2);
+        assert!(by_name.iter().all(|(student, _)| student.matricule == "S002"));
+
+        let by_phone = repo.search_by_guardian("699 001").unwrap();
+        assert_eq!(by_phone.len(), 1);
+        assert_eq!(by_phone[0].1.to_string(), "Marie Smith (mère)");
+        assert!(repo.search_by_guardian("555").unwrap().is_empty());
+
+        let guardians = repo.get_guardians("S002").unwrap();
+        assert!(!repo.delete_guardian("S001", guardians[0].id).unwrap());
+        assert!(repo.delete_guardian("S002", guardians[0].id).unwrap());
+        assert_eq!(repo.get_all_guardians().unwrap()["S002"].len(), 1);
+    }
+}
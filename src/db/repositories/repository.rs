@@ -1,3 +1,5 @@
+// Not every operation is exposed in the CLI yet
+#[allow(dead_code)]
 pub trait Repository<T> {
     fn create(&self, item: &T) -> rusqlite::Result<()>;
     fn get_all(&self) -> rusqlite::Result<Vec<T>>;
@@ -7,3 +9,13 @@ pub trait Repository<T> {
 
     fn filter(&self, filter: &str, params: &[&dyn rusqlite::ToSql]) -> rusqlite::Result<Vec<T>>;
 }
+
+/// Condition hiding the archived rows of `table` (or of the table aliased
+/// `table`), unless `include_archived`
+pub fn not_archived(table: &str, include_archived: bool) -> String {
+    if include_archived {
+        "TRUE".to_string()
+    } else {
+        format!("{}.deleted_at IS NULL", table)
+    }
+}
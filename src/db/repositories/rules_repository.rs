@@ -0,0 +1,187 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rusqlite::{params, Connection};
+use crate::grading::rules::{DecisionRules, GradingRules, KeySubjectRule, MentionRule};
+
+pub struct RulesRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl RulesRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Mention rules, from the highest threshold to the lowest
+    pub fn get_mention_rules(&self) -> rusqlite::Result<Vec<MentionRule>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT id, label, min_average FROM mention_rules ORDER BY min_average DESC"
+        )?;
+
+        let rules = stmt.query_map([], |row| {
+            Ok(MentionRule {
+                id: row.get(0)?,
+                label: row.get(1)?,
+                min_average: row.get(2)?,
+            })
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rules)
+    }
+
+    /// Create the mention `label` or change its threshold
+    pub fn set_mention_rule(&self, label: &str, min_average: f64) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO mention_rules (label, min_average) VALUES (?1, ?2)
+             ON CONFLICT(label) DO UPDATE SET min_average = excluded.min_average",
+            params![label, min_average],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_mention_rule(&self, label: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute("DELETE FROM mention_rules WHERE label = ?1", params![label])?;
+        Ok(())
+    }
+
+    pub fn get_decision_rules(&self) -> rusqlite::Result<DecisionRules> {
+        let key_subjects = self.get_key_subject_rules()?;
+        let conn = self.conn.borrow();
+
+        conn.query_row(
+            "SELECT admission_average, exclusion_average, failing_subject_average, max_failed_subjects
+             FROM decision_rules WHERE id = 1",
+            [],
+            |row| Ok(DecisionRules {
+                admission_average: row.get(0)?,
+                exclusion_average: row.get(1)?,
+                failing_subject_average: row.get(2)?,
+                max_failed_subjects: row.get(3)?,
+                key_subjects,
+            })
+        )
+    }
+
+    /// Store the thresholds of `rules` (key subjects are stored separately)
+    pub fn update_decision_rules(&self, rules: &DecisionRules) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "UPDATE decision_rules SET admission_average = ?1, exclusion_average = ?2,
+             failing_subject_average = ?3, max_failed_subjects = ?4 WHERE id = 1",
+            params![
+                rules.admission_average,
+                rules.exclusion_average,
+                rules.failing_subject_average,
+                rules.max_failed_subjects
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_key_subject_rules(&self) -> rusqlite::Result<Vec<KeySubjectRule>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT subject_prefix, min_average FROM key_subject_rules ORDER BY subject_prefix"
+        )?;
+
+        let rules = stmt.query_map([], |row| {
+            Ok(KeySubjectRule {
+                subject_prefix: row.get(0)?,
+                min_average: row.get(1)?,
+            })
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rules)
+    }
+
+    /// Create the key subject rule for `subject_prefix` or change its minimum
+    pub fn set_key_subject_rule(&self, rule: &KeySubjectRule) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO key_subject_rules (subject_prefix, min_average) VALUES (?1, ?2)
+             ON CONFLICT(subject_prefix) DO UPDATE SET min_average = excluded.min_average",
+            params![rule.subject_prefix, rule.min_average],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_key_subject_rule(&self, subject_prefix: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "DELETE FROM key_subject_rules WHERE subject_prefix = ?1",
+            params![subject_prefix],
+        )?;
+        Ok(())
+    }
+
+    /// All the rules needed to grant mentions and decisions
+    pub fn get_grading_rules(&self) -> rusqlite::Result<GradingRules> {
+        Ok(GradingRules {
+            mentions: self.get_mention_rules()?,
+            decisions: self.get_decision_rules()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn setup_repo() -> RulesRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        RulesRepository::new(conn)
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let repo = setup_repo();
+        let rules = repo.get_grading_rules().unwrap();
+
+        assert_eq!(rules.mentions.len(), 6);
+        assert_eq!(rules.mentions[0].label, "Excellent");
+        assert_eq!(rules.decisions.admission_average, 10.0);
+        assert_eq!(rules.decisions.key_subjects.len(), 2);
+    }
+
+    #[test]
+    fn test_set_and_delete_mention_rule() {
+        let repo = setup_repo();
+
+        repo.set_mention_rule("Excellent", 17.5).unwrap();
+        repo.set_mention_rule("Félicitations", 19.0).unwrap();
+        repo.delete_mention_rule("Insuffisant").unwrap();
+
+        let mentions = repo.get_mention_rules().unwrap();
+        assert_eq!(mentions.len(), 6);
+        assert_eq!(mentions[0].label, "Félicitations");
+        assert_eq!(mentions[1].min_average, 17.5);
+    }
+
+    #[test]
+    fn test_update_decision_rules() {
+        let repo = setup_repo();
+        let mut rules = repo.get_decision_rules().unwrap();
+
+        rules.admission_average = 11.0;
+        rules.max_failed_subjects = 2;
+        repo.update_decision_rules(&rules).unwrap();
+        repo.set_key_subject_rule(&KeySubjectRule { subject_prefix: "ANG".into(), min_average: 9.0 }).unwrap();
+        repo.delete_key_subject_rule("FR").unwrap();
+
+        let updated = repo.get_decision_rules().unwrap();
+        assert_eq!(updated.admission_average, 11.0);
+        assert_eq!(updated.max_failed_subjects, 2);
+        let prefixes: Vec<&str> = updated.key_subjects.iter().map(|k| k.subject_prefix.as_str()).collect();
+        assert_eq!(prefixes, vec!["ANG", "MATH"]);
+    }
+}
@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub struct SettingsRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl SettingsRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Value of the setting `key`, if it is defined
+    pub fn get(&self, key: &str) -> rusqlite::Result<Option<String>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+            .optional()
+    }
+
+    /// Create or replace the setting `key`
+    pub fn set(&self, key: &str, value: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn setup_repo() -> SettingsRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        SettingsRepository::new(conn)
+    }
+
+    #[test]
+    fn test_default_settings() {
+        let repo = setup_repo();
+
+        let value = repo.get("grading.missing_notes").unwrap();
+        assert_eq!(value.as_deref(), Some("exclude"));
+        assert!(repo.get("unknown").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_set_creates_and_replaces() {
+        let repo = setup_repo();
+
+        repo.set("custom.key", "1").unwrap();
+        repo.set("custom.key", "2").unwrap();
+        repo.set("grading.missing_notes", "zero").unwrap();
+
+        assert_eq!(repo.get("custom.key").unwrap().as_deref(), Some("2"));
+        assert_eq!(repo.get("grading.missing_notes").unwrap().as_deref(), Some("zero"));
+    }
+}
@@ -1,6 +1,11 @@
 use std::rc::Rc;
 use std::cell::RefCell;
-use crate::db::repositories::repository::Repository;
+use std::fmt;
+use crate::db::repositories::archive_repository::{archive_in, Archivable};
+use crate::db::repositories::enrolment_repository::NOT_DEPARTED;
+use crate::db::repositories::repository::{not_archived, Repository};
+use crate::students::import::ImportRow;
+use crate::students::matricule::MatriculePattern;
 use rusqlite::{params, Connection, OptionalExtension, ToSql};
 
 #[derive(Debug, Clone)]
@@ -11,34 +16,176 @@ pub struct Student {
     pub class: String,
 }
 
+/// Why a student cannot be registered
+#[derive(Debug)]
+pub enum StudentError {
+    Database(rusqlite::Error),
+    UnknownClass(String),
+    /// The matricule does not follow the configured pattern
+    InvalidMatricule { matricule: String, pattern: String },
+    DuplicateMatricule(String),
+    /// There is no current school year to take `{YEAR}` from
+    NoCurrentYear,
+    /// Error on a line of an import file
+    AtLine { line: usize, error: Box<StudentError> },
+}
+
+impl fmt::Display for StudentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) =>
+                write!(f, "{e}"),
+            Self::UnknownClass(class) =>
+                write!(f, "classe {class} inconnue"),
+            Self::InvalidMatricule { matricule, pattern } =>
+                write!(f, "le matricule {matricule} ne respecte pas le format {pattern}"),
+            Self::DuplicateMatricule(matricule) =>
+                write!(f, "le matricule {matricule} est déjà attribué"),
+            Self::NoCurrentYear =>
+                write!(f, "aucune année scolaire en cours"),
+            Self::AtLine { line, error } =>
+                write!(f, "ligne {line} : {error}"),
+        }
+    }
+}
+
+impl std::error::Error for StudentError {}
+
+impl From<rusqlite::Error> for StudentError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+fn insert(conn: &Connection, student: &Student) -> rusqlite::Result<usize> {
+    conn.execute(
+        "INSERT INTO students (matricule, name, surname, class) VALUES (?1, ?2, ?3, ?4)",
+        params![student.matricule, student.name, student.surname, student.class]
+    )
+}
+
+fn exists(conn: &Connection, matricule: &str) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM students WHERE matricule = ?1)",
+        params![matricule],
+        |row| row.get(0),
+    )
+}
+
+/// Next free matricule of `pattern` for a student of `level`. The sequence
+/// number is taken from `matricule_sequences` so that two sessions never
+/// hand out the same one; numbers already used by hand are skipped.
+fn next_matricule(conn: &Connection, pattern: &MatriculePattern, level: &str) -> Result<String, StudentError> {
+    let year: String = conn
+        .query_row(
+            "SELECT substr(start_date, 1, 4) FROM academic_years WHERE is_current = 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or(StudentError::NoCurrentYear)?;
+    let scope = pattern.scope(&year, level);
+
+    loop {
+        let sequence: u32 = conn.query_row(
+            "INSERT INTO matricule_sequences (scope, last_value) VALUES (?1, 1)
+             ON CONFLICT(scope) DO UPDATE SET last_value = last_value + 1
+             RETURNING last_value",
+            params![scope],
+            |row| row.get(0),
+        )?;
+        let matricule = pattern.format(&year, level, sequence);
+        if !exists(conn, &matricule)? {
+            return Ok(matricule);
+        }
+    }
+}
+
+/// Insert `student`, generating their matricule when it is empty and
+/// checking it against `pattern` otherwise
+fn register_in(conn: &Connection, student: &Student, pattern: &MatriculePattern) -> Result<Student, StudentError> {
+    let level: String = conn
+        .query_row("SELECT level FROM classes WHERE code = ?1", params![student.class], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| StudentError::UnknownClass(student.class.clone()))?;
+
+    let matricule = if student.matricule.is_empty() {
+        next_matricule(conn, pattern, &level)?
+    } else if !pattern.matches(&student.matricule) {
+        return Err(StudentError::InvalidMatricule {
+            matricule: student.matricule.clone(),
+            pattern: pattern.to_string(),
+        });
+    } else if exists(conn, &student.matricule)? {
+        return Err(StudentError::DuplicateMatricule(student.matricule.clone()));
+    } else {
+        student.matricule.clone()
+    };
+
+    let student = Student { matricule, ..student.clone() };
+    insert(conn, &student)?;
+    Ok(student)
+}
+
 pub struct StudentRepository {
-    conn: Rc<RefCell<Connection>>
+    conn: Rc<RefCell<Connection>>,
+    include_archived: bool,
 }
 
 impl StudentRepository {
     pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
-        Self { conn }
+        Self { conn, include_archived: false }
+    }
+
+    /// The same repository, listing the archived students too
+    pub fn with_archived(&self) -> Self {
+        Self { conn: self.conn.clone(), include_archived: true }
+    }
+
+    /// Register a new student, generating their matricule from `pattern`
+    /// when it is empty. Returns the student as recorded.
+    pub fn register(&self, student: &Student, pattern: &MatriculePattern) -> Result<Student, StudentError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let student = register_in(&tx, student, pattern)?;
+
+        tx.commit()?;
+        Ok(student)
     }
 
-    //todo: implement relationships from a student see his notes in diff subjects
+    /// Register the students of an import file, all or none
+    pub fn import(&self, rows: &[ImportRow], pattern: &MatriculePattern) -> Result<Vec<Student>, StudentError> {
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+
+        let students = rows
+            .iter()
+            .map(|row| {
+                register_in(&tx, &row.student, pattern)
+                    .map_err(|error| StudentError::AtLine { line: row.line, error: Box::new(error) })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        tx.commit()?;
+        Ok(students)
+    }
 }
 
 impl Repository<Student> for StudentRepository {
     fn create(&self, student: &Student) -> rusqlite::Result<()> {
         let conn = self.conn.borrow_mut();
-
-        conn.execute(
-           "INSERT INTO students (matricule, name, surname, class) VALUES (?1, ?2, ?3, ?4)",
-           params![student.matricule, student.name, student.surname, student.class]
-        )?;
+        insert(&conn, student)?;
         Ok(())
     }
 
     fn get_all(&self) -> rusqlite::Result<Vec<Student>> {
         let conn = self.conn.borrow();
-        let mut stmt = conn.prepare(
-            "SELECT matricule, name, surname, class FROM students"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT matricule, name, surname, class FROM students WHERE {} AND {}",
+            not_archived("students", self.include_archived),
+            NOT_DEPARTED
+        ))?;
 
         let students = stmt.query_map([], |row| {
             Ok(Student {
@@ -56,7 +203,10 @@ impl Repository<Student> for StudentRepository {
     fn get(&self, matricule: &str) -> rusqlite::Result<Option<Student>> {
         let conn = self.conn.borrow();
         conn.query_row(
-            "SELECT matricule, name, surname, class FROM students WHERE matricule = ?1",
+            &format!(
+                "SELECT matricule, name, surname, class FROM students WHERE matricule = ?1 AND {}",
+                not_archived("students", self.include_archived)
+            ),
             params![matricule],
             |row| Ok(Student {
                 matricule: row.get(0)?,
@@ -76,20 +226,21 @@ impl Repository<Student> for StudentRepository {
         Ok(())
     }
 
+    /// Archive the student with their notes: only a purge or a deletion
+    /// with its notes removes a student for good
     fn delete(&self, matricule: &str) -> rusqlite::Result<()> {
-        let conn = self.conn.borrow_mut();
-        conn.execute(
-            "DELETE FROM students WHERE matricule=?1",
-            params![matricule],
-        )?;
-        Ok(())
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+        archive_in(&tx, &Archivable::Student(matricule.to_string()))?;
+        tx.commit()
     }
 
     fn filter(&self, filter: &str, params: &[&dyn ToSql]) -> rusqlite::Result<Vec<Student>> {
         let conn = self.conn.borrow();
 
         let query = format!(
-            "SELECT matricule, name, surname, class FROM students WHERE {}",
+            "SELECT matricule, name, surname, class FROM students WHERE {} AND {}",
+            not_archived("students", self.include_archived),
             filter
         );
 
@@ -119,6 +270,9 @@ mod tests {
         let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
         let schema = include_str!("../../../sql/schema.sql");
         crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10'), ('10B', '10'), ('11A', '11')",
+        ).unwrap();
 
         StudentRepository::new(conn)
     }
@@ -167,6 +321,24 @@ mod tests {
         assert_eq!(filtered.len(), 1);
     }
 
+    #[test]
+    fn test_archived_students_are_hidden() {
+        let repo = setup_repo();
+        for student in sample_students() {
+            repo.create(&student).unwrap();
+        }
+        repo.conn.borrow().execute("UPDATE students SET deleted_at = CURRENT_TIMESTAMP WHERE matricule = 'S001'", []).unwrap();
+
+        assert_eq!(repo.get_all().unwrap().len(), 2);
+        assert!(repo.get("S001").unwrap().is_none());
+        assert_eq!(repo.filter("class = ?1", &[&"10A"]).unwrap().len(), 1);
+
+        let all = repo.with_archived();
+        assert_eq!(all.get_all().unwrap().len(), 3);
+        assert!(all.get("S001").unwrap().is_some());
+        assert_eq!(all.filter("class = ?1", &[&"10A"]).unwrap().len(), 2);
+    }
+
     #[test]
     fn test_update_student() {
         let repo = setup_repo();
@@ -191,6 +363,9 @@ mod tests {
         repo.delete(&student.matricule).unwrap();
         let deleted = repo.get(&student.matricule).unwrap();
         assert!(deleted.is_none());
+        // Archived, not removed
+        assert!(repo.with_archived().get(&student.matricule).unwrap().is_some());
+        assert!(repo.delete(&student.matricule).is_err());
     }
 
     #[test]
@@ -221,4 +396,52 @@ mod tests {
         let all = repo.get_all().unwrap();
         assert_eq!(all.len(), 3);
     }
+
+    fn new_student(matricule: &str, class: &str) -> Student {
+        Student {
+            matricule: matricule.into(),
+            name: "Martin".into(),
+            surname: "Lucas".into(),
+            class: class.into(),
+        }
+    }
+
+    #[test]
+    fn test_register_generates_matricules() {
+        let repo = setup_repo();
+        let pattern = MatriculePattern::parse("{YEAR}-{LEVEL}-{SEQ:3}").unwrap();
+
+        repo.register(&new_student("2025-10-002", "10A"), &pattern).unwrap();
+        let first = repo.register(&new_student("", "10A"), &pattern).unwrap();
+        let second = repo.register(&new_student("", "10B"), &pattern).unwrap();
+        let other_level = repo.register(&new_student("", "11A"), &pattern).unwrap();
+        assert_eq!(first.matricule, "2025-10-001");
+        assert_eq!(second.matricule, "2025-10-003");
+        assert_eq!(other_level.matricule, "2025-11-001");
+
+        assert!(matches!(
+            repo.register(&new_student("S001", "10A"), &pattern),
+            Err(StudentError::InvalidMatricule { .. })
+        ));
+        assert!(matches!(
+            repo.register(&new_student("2025-10-001", "10A"), &pattern),
+            Err(StudentError::DuplicateMatricule(_))
+        ));
+        assert!(matches!(repo.register(&new_student("", "12A"), &pattern), Err(StudentError::UnknownClass(_))));
+    }
+
+    #[test]
+    fn test_import_is_all_or_nothing() {
+        let repo = setup_repo();
+        let pattern = MatriculePattern::parse("E{SEQ:4}").unwrap();
+        let row = |line, matricule, class| ImportRow { line, student: new_student(matricule, class) };
+
+        let error = repo.import(&[row(2, "", "10A"), row(3, "E0001", "10A")], &pattern).unwrap_err();
+        assert_eq!(error.to_string(), "ligne 3 : le matricule E0001 est déjà attribué");
+        assert!(repo.get_all().unwrap().is_empty());
+
+        let students = repo.import(&[row(2, "", "10A"), row(3, "", "10B")], &pattern).unwrap();
+        assert_eq!(students[1].matricule, "E0002");
+        assert_eq!(repo.get_all().unwrap().len(), 2);
+    }
 }
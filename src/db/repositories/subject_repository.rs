@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
-use crate::db::repositories::repository::Repository;
+use crate::db::repositories::archive_repository::{archive_in, Archivable};
+use crate::db::repositories::repository::{not_archived, Repository};
 use rusqlite::{params, Connection, OptionalExtension, ToSql};
 
 #[derive(Debug, Clone)]
@@ -12,15 +13,37 @@ pub struct Subject {
 }
 
 pub struct SubjectRepository {
-    conn: Rc<RefCell<Connection>>
+    conn: Rc<RefCell<Connection>>,
+    include_archived: bool,
 }
 
 impl SubjectRepository {
     pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
-        Self { conn }
+        Self { conn, include_archived: false }
+    }
+
+    /// The same repository, listing the archived subjects too
+    pub fn with_archived(&self) -> Self {
+        Self { conn: self.conn.clone(), include_archived: true }
     }
 
     //todo: implement relationships from a subject see students' notes
+
+    /// Code, name and coefficient of the subjects whose coefficient is out
+    /// of 1 to 255, left over from before the constraint existed
+    pub fn get_invalid_coefficients(&self) -> rusqlite::Result<Vec<(String, String, i64)>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT code, name, coefficient FROM subjects
+             WHERE typeof(coefficient) <> 'integer' OR coefficient NOT BETWEEN 1 AND 255
+             ORDER BY code"
+        )?;
+
+        let subjects = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(subjects)
+    }
 }
 
 impl Repository<Subject> for SubjectRepository {
@@ -37,9 +60,10 @@ impl Repository<Subject> for SubjectRepository {
     fn get_all(&self) -> rusqlite::Result<Vec<Subject>> {
         let conn = self.conn.borrow();
 
-        let mut stmt = conn.prepare(
-            "SELECT code, name, class, coefficient FROM subjects"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT code, name, class, coefficient FROM subjects WHERE {}",
+            not_archived("subjects", self.include_archived)
+        ))?;
 
         let subjects = stmt.query_map([], |row| {
             Ok(Subject {
@@ -58,7 +82,10 @@ impl Repository<Subject> for SubjectRepository {
         let conn = self.conn.borrow();
 
         conn.query_row(
-            "SELECT code, name, class, coefficient FROM subjects WHERE code = ?1",
+            &format!(
+                "SELECT code, name, class, coefficient FROM subjects WHERE code = ?1 AND {}",
+                not_archived("subjects", self.include_archived)
+            ),
             params![code],
             |row| Ok(Subject {
                 code: row.get(0)?,
@@ -79,21 +106,21 @@ impl Repository<Subject> for SubjectRepository {
         Ok(())
     }
 
+    /// Archive the subject with its notes: only a purge or a deletion with
+    /// its notes removes a subject for good
     fn delete(&self, code: &str) -> rusqlite::Result<()> {
-        let conn = self.conn.borrow_mut();
-
-        conn.execute(
-            "DELETE FROM subjects WHERE code = ?1",
-            params![code]
-        )?;
-        Ok(())
+        let mut conn = self.conn.borrow_mut();
+        let tx = conn.transaction()?;
+        archive_in(&tx, &Archivable::Subject(code.to_string()))?;
+        tx.commit()
     }
 
     fn filter(&self, filter: &str, params: &[&dyn ToSql]) -> rusqlite::Result<Vec<Subject>> {
         let conn = self.conn.borrow();
 
         let query = format!(
-            "SELECT code, name, class, coefficient FROM subjects WHERE {}",
+            "SELECT code, name, class, coefficient FROM subjects WHERE {} AND {}",
+            not_archived("subjects", self.include_archived),
             filter
         );
 
@@ -122,6 +149,9 @@ mod tests {
         let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
         let schema = include_str!("../../../sql/schema.sql"); // make sure this exists
         crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10'), ('10B', '10')",
+        ).unwrap();
 
         SubjectRepository::new(conn)
     }
@@ -194,6 +224,8 @@ mod tests {
         repo.delete(&subject.code).unwrap();
         let deleted = repo.get(&subject.code).unwrap();
         assert!(deleted.is_none());
+        // Archived, not removed
+        assert!(repo.with_archived().get(&subject.code).unwrap().is_some());
     }
 
     #[test]
@@ -224,5 +256,23 @@ mod tests {
         let all = repo.get_all().unwrap();
         assert_eq!(all.len(), 3);
     }
-}
 
+    #[test]
+    fn test_coefficient_constraint() {
+        let repo = setup_repo();
+        let mut subject = sample_subjects()[0].clone();
+        subject.coefficient = 0;
+        assert!(repo.create(&subject).is_err());
+
+        // Legacy value written before the constraint existed
+        repo.conn.borrow().execute_batch(
+            "PRAGMA ignore_check_constraints = ON;
+             INSERT INTO subjects (code, name, class, coefficient) VALUES ('ART101', 'Art', '10A', 300);
+             PRAGMA ignore_check_constraints = OFF;",
+        ).unwrap();
+        repo.create(&sample_subjects()[1]).unwrap();
+
+        let invalid = repo.get_invalid_coefficients().unwrap();
+        assert_eq!(invalid, vec![("ART101".to_string(), "Art".to_string(), 300)]);
+    }
+}
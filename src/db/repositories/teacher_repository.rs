@@ -0,0 +1,224 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use crate::db::repositories::repository::Repository;
+use rusqlite::{params, Connection, OptionalExtension, Row, ToSql};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Teacher {
+    pub id: i64,
+    pub name: String,
+    pub surname: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+impl fmt::Display for Teacher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.surname)
+    }
+}
+
+fn teacher_from_row(row: &Row) -> rusqlite::Result<Teacher> {
+    Ok(Teacher {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        surname: row.get(2)?,
+        email: row.get(3)?,
+        phone: row.get(4)?,
+    })
+}
+
+const TEACHER_COLUMNS: &str = "id, name, surname, email, phone";
+
+pub struct TeacherRepository {
+    conn: Rc<RefCell<Connection>>,
+}
+
+impl TeacherRepository {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Make `teacher_id` the teacher of the subject, replacing the previous one
+    pub fn assign(&self, subject_code: &str, teacher_id: i64) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO teaching_assignments (subject_code, teacher_id) VALUES (?1, ?2)
+             ON CONFLICT(subject_code) DO UPDATE SET teacher_id = excluded.teacher_id",
+            params![subject_code, teacher_id],
+        )?;
+        Ok(())
+    }
+
+    /// Leave the subject without a teacher
+    pub fn unassign(&self, subject_code: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "DELETE FROM teaching_assignments WHERE subject_code = ?1",
+            params![subject_code],
+        )?;
+        Ok(())
+    }
+
+    /// Teacher of each assigned subject, by subject code
+    pub fn get_subject_teachers(&self) -> rusqlite::Result<HashMap<String, Teacher>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(
+            "SELECT a.subject_code, t.id, t.name, t.surname, t.email, t.phone
+             FROM teaching_assignments a JOIN teachers t ON t.id = a.teacher_id"
+        )?;
+
+        let teachers = stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                Teacher {
+                    id: row.get(1)?,
+                    name: row.get(2)?,
+                    surname: row.get(3)?,
+                    email: row.get(4)?,
+                    phone: row.get(5)?,
+                },
+            ))
+        })?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        Ok(teachers)
+    }
+}
+
+impl Repository<Teacher> for TeacherRepository {
+    fn create(&self, teacher: &Teacher) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "INSERT INTO teachers (name, surname, email, phone) VALUES (?1, ?2, ?3, ?4)",
+            params![teacher.name, teacher.surname, teacher.email, teacher.phone],
+        )?;
+        Ok(())
+    }
+
+    fn get_all(&self) -> rusqlite::Result<Vec<Teacher>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM teachers ORDER BY name, surname", TEACHER_COLUMNS))?;
+
+        let teachers = stmt.query_map([], teacher_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(teachers)
+    }
+
+    fn get(&self, id: &str) -> rusqlite::Result<Option<Teacher>> {
+        let conn = self.conn.borrow();
+        conn.query_row(
+            &format!("SELECT {} FROM teachers WHERE id = ?1", TEACHER_COLUMNS),
+            params![id],
+            teacher_from_row,
+        )
+            .optional()
+    }
+
+    fn update(&self, teacher: &Teacher) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute(
+            "UPDATE teachers SET name = ?1, surname = ?2, email = ?3, phone = ?4 WHERE id = ?5",
+            params![teacher.name, teacher.surname, teacher.email, teacher.phone, teacher.id],
+        )?;
+        Ok(())
+    }
+
+    /// Also removes the teacher's assignments
+    fn delete(&self, id: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.borrow_mut();
+        conn.execute("DELETE FROM teachers WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    fn filter(&self, filter: &str, params: &[&dyn ToSql]) -> rusqlite::Result<Vec<Teacher>> {
+        let conn = self.conn.borrow();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM teachers WHERE {}", TEACHER_COLUMNS, filter))?;
+
+        let teachers = stmt.query_map(params, teacher_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(teachers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn setup_repo() -> TeacherRepository {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('10A', '10');
+             INSERT INTO subjects (code, name, class, coefficient) VALUES
+                ('MATH101', 'Mathematics', '10A', 4),
+                ('PHY101', 'Physics', '10A', 3);",
+        ).unwrap();
+
+        TeacherRepository::new(conn)
+    }
+
+    fn teacher(name: &str, surname: &str) -> Teacher {
+        Teacher {
+            id: 0,
+            name: name.into(),
+            surname: surname.into(),
+            email: None,
+            phone: None,
+        }
+    }
+
+    #[test]
+    fn test_create_and_update_teacher() {
+        let repo = setup_repo();
+        repo.create(&teacher("Awa", "Diallo")).unwrap();
+
+        let mut created = repo.get_all().unwrap().remove(0);
+        assert_eq!(created.to_string(), "Awa Diallo");
+
+        created.email = Some("awa.diallo@nschool.test".into());
+        repo.update(&created).unwrap();
+        assert_eq!(repo.get(&created.id.to_string()).unwrap().unwrap(), created);
+    }
+
+    #[test]
+    fn test_assignments() {
+        let repo = setup_repo();
+        repo.create(&teacher("Awa", "Diallo")).unwrap();
+        repo.create(&teacher("Jean", "Sow")).unwrap();
+        let teachers = repo.get_all().unwrap();
+
+        repo.assign("MATH101", teachers[0].id).unwrap();
+        repo.assign("PHY101", teachers[0].id).unwrap();
+        // A subject has a single teacher
+        repo.assign("PHY101", teachers[1].id).unwrap();
+
+        let assigned = repo.get_subject_teachers().unwrap();
+        assert_eq!(assigned.len(), 2);
+        assert_eq!(assigned["MATH101"].surname, "Diallo");
+        assert_eq!(assigned["PHY101"].surname, "Sow");
+
+        repo.unassign("PHY101").unwrap();
+        assert!(!repo.get_subject_teachers().unwrap().contains_key("PHY101"));
+        assert!(repo.assign("UNKNOWN", teachers[0].id).is_err());
+    }
+
+    #[test]
+    fn test_deleting_a_teacher_removes_assignments() {
+        let repo = setup_repo();
+        repo.create(&teacher("Awa", "Diallo")).unwrap();
+        let id = repo.get_all().unwrap()[0].id;
+        repo.assign("MATH101", id).unwrap();
+
+        repo.delete(&id.to_string()).unwrap();
+        assert!(repo.get_subject_teachers().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt;
+
+/// Setting keys holding the attendance alert thresholds
+pub const MAX_UNJUSTIFIED_HOURS_SETTING: &str = "attendance.max_unjustified_hours";
+pub const MAX_LATENESS_SETTING: &str = "attendance.max_lateness";
+
+/// Absences and lateness of a student over a term or a year
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AttendanceTotals {
+    /// Hours of absence, justified or not
+    pub absence_hours: u32,
+    pub unjustified_hours: u32,
+    pub lateness: u32,
+    pub unjustified_lateness: u32,
+}
+
+impl fmt::Display for AttendanceTotals {
+    /// e.g. "6 h d'absence dont 2 h non justifiées, 3 retards"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} h d'absence dont {} h non justifiées, {} retards",
+            self.absence_hours, self.unjustified_hours, self.lateness
+        )
+    }
+}
+
+/// Limits above which a student is flagged for excessive absences
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttendanceThresholds {
+    pub max_unjustified_hours: u32,
+    pub max_lateness: u32,
+}
+
+impl Default for AttendanceThresholds {
+    fn default() -> Self {
+        Self { max_unjustified_hours: 10, max_lateness: 5 }
+    }
+}
+
+impl AttendanceThresholds {
+    /// Reasons to flag a student, empty if no threshold is exceeded
+    pub fn alerts(&self, totals: &AttendanceTotals) -> Vec<String> {
+        let mut alerts = Vec::new();
+
+        if totals.unjustified_hours > self.max_unjustified_hours {
+            alerts.push(format!(
+                "{} h d'absence non justifiées (maximum {})",
+                totals.unjustified_hours, self.max_unjustified_hours
+            ));
+        }
+        if totals.lateness > self.max_lateness {
+            alerts.push(format!("{} retards (maximum {})", totals.lateness, self.max_lateness));
+        }
+
+        alerts
+    }
+}
+
+impl fmt::Display for AttendanceThresholds {
+    /// e.g. "10 h non justifiées, 5 retards"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} h non justifiées, {} retards", self.max_unjustified_hours, self.max_lateness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alerts_above_thresholds() {
+        let thresholds = AttendanceThresholds::default();
+        let mut totals = AttendanceTotals { absence_hours: 14, unjustified_hours: 10, lateness: 5, unjustified_lateness: 2 };
+        assert!(thresholds.alerts(&totals).is_empty());
+
+        totals.unjustified_hours = 11;
+        totals.lateness = 6;
+        let alerts = thresholds.alerts(&totals);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[1], "6 retards (maximum 5)");
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+use crate::db::repositories::note_repository::PlacedNote;
+use crate::grading::stats::{self, NoteSummary};
+
+/// Fewest notes a group, and the other groups together, need for a
+/// harshness verdict
+pub const MIN_NOTES: usize = 5;
+
+/// Gap to the other groups, in standard errors, from which it is deemed
+/// significant (about 5 % risk of a gap arising by chance)
+pub const SIGNIFICANCE: f64 = 1.96;
+
+/// Label of the comparison of all the subjects together
+pub const ALL_SUBJECTS: &str = "Toutes matières";
+
+/// How a group grades compared to the others
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Harsher,
+    MoreLenient,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Harsher => write!(f, "plus sévère"),
+            Self::MoreLenient => write!(f, "plus indulgent"),
+        }
+    }
+}
+
+/// Notes of a class or a school year, compared to those of the other groups
+#[derive(Debug, Clone)]
+pub struct GroupComparison {
+    /// Class code or school year label
+    pub label: String,
+    pub summary: NoteSummary,
+    /// Mean of the group minus the mean of the notes of the other groups
+    pub gap: Option<f64>,
+    /// Gap in standard errors of the difference of the two means
+    pub z_score: Option<f64>,
+    /// Set when the gap is significant
+    pub severity: Option<Severity>,
+}
+
+/// Groups compared in one subject
+#[derive(Debug, Clone)]
+pub struct SubjectComparison {
+    pub subject: String,
+    pub groups: Vec<GroupComparison>,
+}
+
+/// Compare each group of notes to the notes of all the other groups.
+/// Groups without notes are left out; a verdict needs [`MIN_NOTES`] notes
+/// on both sides and a gap of [`SIGNIFICANCE`] standard errors.
+pub fn compare(groups: &[(String, Vec<f64>)]) -> Vec<GroupComparison> {
+    groups
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (label, values))| {
+            let summary = stats::summarize(values)?;
+            let others: Vec<f64> = groups
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .flat_map(|(_, (_, values))| values.iter().copied())
+                .collect();
+            let others = stats::summarize(&others);
+
+            let gap = others.as_ref().map(|others| summary.mean - others.mean);
+            let z_score = others.as_ref().and_then(|others| {
+                let error = (summary.std_dev.powi(2) / summary.count as f64
+                    + others.std_dev.powi(2) / others.count as f64)
+                    .sqrt();
+                (summary.count >= MIN_NOTES && others.count >= MIN_NOTES && error > 0.0)
+                    .then(|| (summary.mean - others.mean) / error)
+            });
+            let severity = z_score.filter(|z| z.abs() >= SIGNIFICANCE).map(|z| {
+                if z < 0.0 { Severity::Harsher } else { Severity::MoreLenient }
+            });
+
+            Some(GroupComparison { label: label.clone(), summary, gap, z_score, severity })
+        })
+        .collect()
+}
+
+/// Values of `notes` grouped by `key`, in the order the keys first appear
+fn grouped<'a>(notes: impl Iterator<Item = &'a PlacedNote>, key: impl Fn(&PlacedNote) -> &str) -> Vec<(String, Vec<f64>)> {
+    let mut groups: Vec<(String, Vec<f64>)> = Vec::new();
+    for note in notes {
+        match groups.iter_mut().find(|(label, _)| label == key(note)) {
+            Some((_, values)) => values.push(note.value),
+            None => groups.push((key(note).to_string(), vec![note.value])),
+        }
+    }
+    groups
+}
+
+/// Compare the groups given by `group` (class or school year), all
+/// subjects together first, then subject by subject
+pub fn by_subject(notes: &[PlacedNote], group: impl Fn(&PlacedNote) -> &str) -> Vec<SubjectComparison> {
+    let mut comparisons = vec![SubjectComparison {
+        subject: ALL_SUBJECTS.to_string(),
+        groups: compare(&grouped(notes.iter(), &group)),
+    }];
+
+    for (subject, _) in grouped(notes.iter(), |note| &note.subject_name) {
+        let subject_notes = notes.iter().filter(|note| note.subject_name == subject);
+        comparisons.push(SubjectComparison {
+            groups: compare(&grouped(subject_notes, &group)),
+            subject,
+        });
+    }
+
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(class: &str, subject: &str, value: f64) -> PlacedNote {
+        PlacedNote {
+            value,
+            class: class.into(),
+            subject_name: subject.into(),
+            academic_year_id: 1,
+            year_label: "2025-2026".into(),
+            period_id: 1,
+        }
+    }
+
+    #[test]
+    fn test_compare_flags_significant_gaps() {
+        let groups = vec![
+            ("6A".to_string(), vec![12.0, 13.0, 11.0, 12.5, 11.5, 12.0]),
+            ("6B".to_string(), vec![12.5, 11.0, 13.0, 12.0, 11.5, 12.0]),
+            ("6C".to_string(), vec![7.0, 8.0, 6.5, 7.5, 8.5, 7.0]),
+            ("6D".to_string(), vec![]),
+            ("6E".to_string(), vec![12.0]),
+        ];
+
+        let comparison = compare(&groups);
+        assert_eq!(comparison.len(), 4);
+        assert_eq!(comparison[2].label, "6C");
+        assert_eq!(comparison[2].severity, Some(Severity::Harsher));
+        assert!(comparison[2].gap.unwrap() < -4.0);
+        assert_eq!(comparison[0].severity, Some(Severity::MoreLenient));
+        // Too few notes for a verdict
+        assert!(comparison[3].z_score.is_none());
+        assert!(comparison[3].gap.is_some());
+    }
+
+    #[test]
+    fn test_by_subject() {
+        let notes = vec![
+            note("6A", "Maths", 12.0),
+            note("6A", "Français", 14.0),
+            note("6B", "Maths", 8.0),
+        ];
+
+        let comparisons = by_subject(&notes, |note| &note.class);
+        let subjects: Vec<&str> = comparisons.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec![ALL_SUBJECTS, "Maths", "Français"]);
+        assert_eq!(comparisons[0].groups[0].summary.mean, 13.0);
+        assert_eq!(comparisons[1].groups[1].gap, Some(-4.0));
+        assert_eq!(comparisons[2].groups.len(), 1);
+        assert_eq!(comparisons[2].groups[0].gap, None);
+    }
+}
@@ -0,0 +1,340 @@
+pub mod service;
+pub mod attendance;
+pub mod comparison;
+pub mod moderation;
+pub mod promotion;
+pub mod ranking;
+pub mod rules;
+pub mod scale;
+pub mod stats;
+pub mod warnings;
+pub mod weights;
+
+use std::fmt;
+use crate::db::repositories::note_repository::Note;
+use crate::db::repositories::student_repository::Student;
+use crate::db::repositories::subject_repository::Subject;
+use crate::grading::weights::NoteWeights;
+
+/// Setting key holding the missing notes policy
+pub const MISSING_NOTES_SETTING: &str = "grading.missing_notes";
+
+/// How a subject without any note is taken into account
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingNotePolicy {
+    /// The subject is left out of the average
+    Exclude,
+    /// The subject counts as 0 with its full coefficient
+    CountAsZero,
+}
+
+impl MissingNotePolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exclude => "exclude",
+            Self::CountAsZero => "zero",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exclude" => Some(Self::Exclude),
+            "zero" => Some(Self::CountAsZero),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MissingNotePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exclude => write!(f, "exclues de la moyenne"),
+            Self::CountAsZero => write!(f, "comptées comme zéro"),
+        }
+    }
+}
+
+/// Part of the school year averages are computed on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    /// A single term, by period id
+    Period(i64),
+    /// The whole academic year, by academic year id
+    Year(i64),
+}
+
+impl Scope {
+    /// End-of-year decisions are only taken on annual averages
+    pub fn is_annual(self) -> bool {
+        matches!(self, Self::Year(_))
+    }
+}
+
+/// Rules applied when computing averages
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradingPolicy {
+    pub missing_notes: MissingNotePolicy,
+    /// Number of decimals kept on subject and general averages
+    pub decimals: u32,
+}
+
+impl Default for GradingPolicy {
+    fn default() -> Self {
+        Self {
+            missing_notes: MissingNotePolicy::Exclude,
+            decimals: 2,
+        }
+    }
+}
+
+/// Round half away from zero to `decimals` decimals
+pub fn round(value: f64, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals as i32);
+    (value * factor).round() / factor
+}
+
+/// Average of a student in one subject
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectAverage {
+    pub subject_code: String,
+    pub coefficient: u8,
+    /// Average of the notes in the subject, `None` if there is no note
+    pub average: Option<f64>,
+    /// Whether the subject takes part in the general average
+    pub counted: bool,
+}
+
+impl SubjectAverage {
+    /// average × coefficient, for counted subjects
+    pub fn points(&self) -> Option<f64> {
+        if self.counted {
+            Some(self.average.unwrap_or(0.0) * self.coefficient as f64)
+        } else {
+            None
+        }
+    }
+}
+
+/// Weighted average of a student: Σ note × coef / Σ coef
+#[derive(Debug, Clone, PartialEq)]
+pub struct StudentAverage {
+    pub matricule: String,
+    pub subjects: Vec<SubjectAverage>,
+    pub total_coefficients: u32,
+    pub total_points: f64,
+    pub average: Option<f64>,
+}
+
+/// Plain average of several values
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Compute the weighted average of a student over the `subjects` of
+/// their class. Multiple notes in a subject are averaged first, following
+/// the evaluation `weights`.
+pub fn student_average(
+    matricule: &str,
+    subjects: &[Subject],
+    notes: &[Note],
+    policy: &GradingPolicy,
+    weights: &NoteWeights,
+) -> StudentAverage {
+    let mut averages = Vec::with_capacity(subjects.len());
+    let mut total_coefficients = 0u32;
+    let mut total_points = 0f64;
+
+    for subject in subjects {
+        let subject_notes: Vec<&Note> = notes
+            .iter()
+            .filter(|n| n.matricule == matricule && n.subject_code == subject.code)
+            .collect();
+
+        let average = weights.subject_mean(&subject_notes).map(|avg| round(avg, policy.decimals));
+        let counted = average.is_some() || policy.missing_notes == MissingNotePolicy::CountAsZero;
+
+        let subject_average = SubjectAverage {
+            subject_code: subject.code.clone(),
+            coefficient: subject.coefficient,
+            average,
+            counted,
+        };
+
+        if let Some(points) = subject_average.points() {
+            total_coefficients += subject.coefficient as u32;
+            total_points += points;
+        }
+
+        averages.push(subject_average);
+    }
+
+    let average = if total_coefficients > 0 {
+        Some(round(total_points / total_coefficients as f64, policy.decimals))
+    } else {
+        None
+    };
+
+    StudentAverage {
+        matricule: matricule.to_string(),
+        subjects: averages,
+        total_coefficients,
+        total_points,
+        average,
+    }
+}
+
+/// Weighted averages of every student of a class, in the order of `students`
+pub fn class_averages(
+    students: &[Student],
+    subjects: &[Subject],
+    notes: &[Note],
+    policy: &GradingPolicy,
+    weights: &NoteWeights,
+) -> Vec<StudentAverage> {
+    students
+        .iter()
+        .map(|student| student_average(&student.matricule, subjects, notes, policy, weights))
+        .collect()
+}
+
+/// Annual average of a student from their term averages, computed over
+/// the same subjects. Subject averages are the mean of the terms in which
+/// they exist, and the general average is weighted from them as for a
+/// term, so that it is always the total of the points over the total of
+/// the coefficients.
+pub fn annual_average(matricule: &str, terms: &[StudentAverage], policy: &GradingPolicy) -> StudentAverage {
+    let subject_count = terms.first().map_or(0, |t| t.subjects.len());
+    let mut averages = Vec::with_capacity(subject_count);
+    let mut total_coefficients = 0u32;
+    let mut total_points = 0f64;
+
+    for index in 0..subject_count {
+        let first = &terms[0].subjects[index];
+        let values: Vec<f64> = terms.iter().filter_map(|t| t.subjects[index].average).collect();
+
+        let subject_average = SubjectAverage {
+            subject_code: first.subject_code.clone(),
+            coefficient: first.coefficient,
+            average: mean(&values).map(|avg| round(avg, policy.decimals)),
+            counted: terms.iter().any(|t| t.subjects[index].counted),
+        };
+
+        if let Some(points) = subject_average.points() {
+            total_coefficients += subject_average.coefficient as u32;
+            total_points += points;
+        }
+
+        averages.push(subject_average);
+    }
+
+    let average = if total_coefficients > 0 {
+        Some(round(total_points / total_coefficients as f64, policy.decimals))
+    } else {
+        None
+    };
+
+    StudentAverage {
+        matricule: matricule.to_string(),
+        subjects: averages,
+        total_coefficients,
+        total_points,
+        average,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subject(code: &str, coefficient: u8) -> Subject {
+        Subject {
+            code: code.into(),
+            name: code.into(),
+            class: "10A".into(),
+            coefficient,
+        }
+    }
+
+    fn note(subject_code: &str, value: f64) -> Note {
+        Note {
+            id: 0,
+            value,
+            matricule: "S001".into(),
+            subject_code: subject_code.into(),
+            period_id: Some(1),
+            evaluation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(round(12.345, 2), 12.35);
+        assert_eq!(round(12.344, 2), 12.34);
+        assert_eq!(round(13.0 / 3.0, 2), 4.33);
+    }
+
+    #[test]
+    fn test_multiple_notes_are_averaged_first() {
+        let subjects = vec![subject("MATH", 4), subject("ART", 1)];
+        let notes = vec![note("MATH", 10.0), note("MATH", 14.0), note("MATH", 18.0), note("ART", 7.0)];
+
+        let result = student_average("S001", &subjects, &notes, &GradingPolicy::default(), &NoteWeights::default());
+
+        assert_eq!(result.subjects[0].average, Some(14.0));
+        assert_eq!(result.total_coefficients, 5);
+        assert_eq!(result.total_points, 63.0);
+        assert_eq!(result.average, Some(12.6));
+    }
+
+    #[test]
+    fn test_missing_notes_policies() {
+        let subjects = vec![subject("MATH", 3), subject("LAT", 1)];
+        let notes = vec![note("MATH", 12.0)];
+
+        let excluded = student_average("S001", &subjects, &notes, &GradingPolicy::default(), &NoteWeights::default());
+        assert_eq!(excluded.average, Some(12.0));
+        assert!(!excluded.subjects[1].counted);
+
+        let policy = GradingPolicy { missing_notes: MissingNotePolicy::CountAsZero, decimals: 2 };
+        let zero = student_average("S001", &subjects, &notes, &policy, &NoteWeights::default());
+        assert_eq!(zero.average, Some(9.0));
+        assert_eq!(zero.subjects[1].points(), Some(0.0));
+    }
+
+    #[test]
+    fn test_no_notes_means_no_average() {
+        let result = student_average("S001", &[subject("MATH", 2)], &[], &GradingPolicy::default(), &NoteWeights::default());
+        assert_eq!(result.average, None);
+    }
+
+    #[test]
+    fn test_annual_average_is_weighted_from_term_subject_averages() {
+        let subjects = vec![subject("MATH", 2), subject("ART", 1)];
+        let policy = GradingPolicy::default();
+        let terms = vec![
+            student_average("S001", &subjects, &[note("MATH", 10.0), note("ART", 16.0)], &policy, &NoteWeights::default()),
+            student_average("S001", &subjects, &[note("MATH", 14.0)], &policy, &NoteWeights::default()),
+            student_average("S001", &subjects, &[], &policy, &NoteWeights::default()),
+        ];
+
+        let annual = annual_average("S001", &terms, &policy);
+
+        assert_eq!(annual.subjects[0].average, Some(12.0));
+        assert_eq!(annual.subjects[1].average, Some(16.0));
+        assert_eq!(annual.total_points, 40.0);
+        assert_eq!(annual.total_coefficients, 3);
+        assert_eq!(annual.average, Some(13.33));
+        assert_eq!(annual_average("S001", &[], &policy).average, None);
+    }
+
+    #[test]
+    fn test_policy_parsing() {
+        assert_eq!(MissingNotePolicy::parse("zero"), Some(MissingNotePolicy::CountAsZero));
+        assert_eq!(MissingNotePolicy::parse(MissingNotePolicy::Exclude.as_str()), Some(MissingNotePolicy::Exclude));
+        assert_eq!(MissingNotePolicy::parse("other"), None);
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+use crate::grading::scale::GradeScale;
+
+/// Transformation of the notes of an evaluation or a subject, e.g. when a
+/// composition turned out too hard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Moderation {
+    /// note × factor + offset
+    Linear { factor: f64, offset: f64 },
+    /// Points added to every note
+    Bonus(f64),
+    /// Notes multiplied by the factor that brings their mean to the target
+    TargetMean(f64),
+}
+
+impl fmt::Display for Moderation {
+    /// e.g. "bonus de +1.5 point(s)"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear { factor, offset } =>
+                write!(f, "barème linéaire (note × {} {:+})", factor, offset),
+            Self::Bonus(points) =>
+                write!(f, "bonus de {:+} point(s)", points),
+            Self::TargetMean(target) =>
+                write!(f, "courbe vers une moyenne de {}", target),
+        }
+    }
+}
+
+impl Moderation {
+    /// Moderated value of each note, fitted to the `scale`
+    pub fn apply(&self, values: &[f64], scale: &GradeScale) -> Vec<f64> {
+        let (factor, offset) = match *self {
+            Self::Linear { factor, offset } => (factor, offset),
+            Self::Bonus(points) => (1.0, points),
+            Self::TargetMean(target) => (curve_factor(values, target, scale), 0.0),
+        };
+        let transform = |value: f64| value * factor + offset;
+        values.iter().map(|value| scale.fit(transform(*value))).collect()
+    }
+}
+
+/// Factor bringing the mean of the notes, once multiplied and capped to the
+/// scale, to `target`. Found by bisection since capping makes it nonlinear.
+fn curve_factor(values: &[f64], target: f64, scale: &GradeScale) -> f64 {
+    let mean_with = |factor: f64| {
+        values.iter().map(|value| (value * factor).clamp(scale.min, scale.max)).sum::<f64>() / values.len() as f64
+    };
+
+    let (mut low, mut high) = (0.0, 1.0);
+    while mean_with(high) < target && high < 1e6 {
+        low = high;
+        high *= 2.0;
+    }
+    for _ in 0..60 {
+        let middle = (low + high) / 2.0;
+        if mean_with(middle) < target {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    high
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::scale::ScaleStep;
+
+    #[test]
+    fn test_linear_and_bonus_stay_within_the_scale() {
+        let scale = GradeScale::default();
+        let values = [4.0, 10.0, 19.0];
+
+        assert_eq!(Moderation::Linear { factor: 1.2, offset: 1.0 }.apply(&values, &scale), vec![5.8, 13.0, 20.0]);
+        assert_eq!(Moderation::Bonus(-5.0).apply(&values, &scale), vec![0.0, 5.0, 14.0]);
+
+        let half = GradeScale::new(0.0, 20.0, ScaleStep::HalfPoint).unwrap();
+        assert_eq!(Moderation::Bonus(1.3).apply(&values, &half), vec![5.5, 11.5, 20.0]);
+    }
+
+    #[test]
+    fn test_curve_to_target_mean() {
+        let scale = GradeScale::default();
+
+        let curved = Moderation::TargetMean(10.0).apply(&[4.0, 6.0, 8.0, 10.0, 12.0], &scale);
+        assert_eq!(curved, vec![5.0, 7.5, 10.0, 12.5, 15.0]);
+
+        // The best note is capped at 20, the others make up for it
+        let curved = Moderation::TargetMean(16.0).apply(&[8.0, 16.0, 18.0], &scale);
+        assert_eq!(curved[2], 20.0);
+        assert!((curved.iter().sum::<f64>() / 3.0 - 16.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use crate::db::repositories::student_repository::Student;
+use crate::grading::StudentAverage;
+use crate::grading::rules::{Decision, GradingRules};
+
+/// What becomes of a student at the end of the year
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Admitted, enrolled in the given class for the next year
+    Promoted(String),
+    /// Enrolled again in the given class
+    Repeating(String),
+    /// Admitted in the last class of the school
+    Graduated,
+    /// Excluded, leaves the school
+    Left,
+    /// No annual average, the student is left aside
+    Undecided,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Promoted(class) => write!(f, "Passe en {}", class),
+            Self::Repeating(class) => write!(f, "Redouble la {}", class),
+            Self::Graduated => write!(f, "Diplômé"),
+            Self::Left => write!(f, "Quitte l'établissement"),
+            Self::Undecided => write!(f, "Sans décision"),
+        }
+    }
+}
+
+/// Year-end decision and outcome of a student
+#[derive(Debug, Clone)]
+pub struct PromotionMove {
+    pub student: Student,
+    pub decision: Option<Decision>,
+    pub outcome: Outcome,
+}
+
+impl PromotionMove {
+    /// Class of the student for the next year, `None` if they are not enrolled again
+    pub fn next_class(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Promoted(class) | Outcome::Repeating(class) => Some(class),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of every student of `class` from their annual average: admitted
+/// students move to `next_class` (or graduate without one), repeaters stay
+/// in `class` and excluded students leave.
+pub fn plan(
+    class: &str,
+    next_class: Option<&str>,
+    averages: &[(Student, StudentAverage)],
+    rules: &GradingRules,
+) -> Vec<PromotionMove> {
+    averages
+        .iter()
+        .map(|(student, average)| {
+            let decision = rules.decide(average).map(|outcome| outcome.decision);
+            let outcome = match (decision, next_class) {
+                (Some(Decision::Admitted), Some(next)) => Outcome::Promoted(next.to_string()),
+                (Some(Decision::Admitted), None) => Outcome::Graduated,
+                (Some(Decision::Repeat), _) => Outcome::Repeating(class.to_string()),
+                (Some(Decision::Excluded), _) => Outcome::Left,
+                (None, _) => Outcome::Undecided,
+            };
+
+            PromotionMove { student: student.clone(), decision, outcome }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::rules::DecisionRules;
+
+    fn average(value: Option<f64>) -> (Student, StudentAverage) {
+        let student = Student {
+            matricule: "S001".into(),
+            name: "Nom".into(),
+            surname: "Prénom".into(),
+            class: "6A".into(),
+        };
+        let average = StudentAverage {
+            matricule: "S001".into(),
+            subjects: Vec::new(),
+            total_coefficients: 0,
+            total_points: 0.0,
+            average: value,
+        };
+        (student, average)
+    }
+
+    #[test]
+    fn test_outcomes_follow_decisions() {
+        let rules = GradingRules { mentions: Vec::new(), decisions: DecisionRules::default() };
+        let averages = [average(Some(14.0)), average(Some(8.0)), average(Some(5.0)), average(None)];
+
+        let moves = plan("6A", Some("5A"), &averages, &rules);
+        assert_eq!(moves[0].outcome, Outcome::Promoted("5A".into()));
+        assert_eq!(moves[1].outcome, Outcome::Repeating("6A".into()));
+        assert_eq!(moves[1].next_class(), Some("6A"));
+        assert_eq!(moves[2].outcome, Outcome::Left);
+        assert_eq!(moves[2].decision, Some(Decision::Excluded));
+        assert_eq!(moves[3].outcome, Outcome::Undecided);
+
+        let last_year = plan("3A", None, &averages[..1], &rules);
+        assert_eq!(last_year[0].outcome, Outcome::Graduated);
+        assert_eq!(last_year[0].next_class(), None);
+    }
+}
@@ -0,0 +1,205 @@
+use std::cmp::Ordering;
+use std::fmt;
+use crate::db::repositories::student_repository::Student;
+use crate::grading::{Scope, StudentAverage};
+use crate::grading::rules::{Decision, GradingRules};
+
+/// Position of a student among `out_of` students
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rank {
+    pub position: usize,
+    /// Another student shares the same position
+    pub ex_aequo: bool,
+    pub out_of: usize,
+}
+
+impl fmt::Display for Rank {
+    /// French notation, e.g. "1er sur 25" or "5e ex aequo sur 25"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suffix = if self.position == 1 { "er" } else { "e" };
+        if self.ex_aequo {
+            write!(f, "{}{} ex aequo sur {}", self.position, suffix, self.out_of)
+        } else {
+            write!(f, "{}{} sur {}", self.position, suffix, self.out_of)
+        }
+    }
+}
+
+/// Rank scores from highest to lowest, returned in the input order.
+///
+/// Equal scores share the same rank and the next rank is skipped
+/// (12, 15, 12 → 2e ex aequo, 1er, 2e ex aequo). Missing scores are not ranked.
+pub fn competition_ranks(scores: &[Option<f64>]) -> Vec<Option<Rank>> {
+    scores
+        .iter()
+        .map(|score| {
+            score.map(|value| {
+                let better = scores.iter().flatten().filter(|other| **other > value).count();
+                let same = scores.iter().flatten().filter(|other| **other == value).count();
+                Rank {
+                    position: better + 1,
+                    ex_aequo: same > 1,
+                    out_of: scores.len(),
+                }
+            })
+        })
+        .collect()
+}
+
+/// One line of a class merit list
+#[derive(Debug, Clone)]
+pub struct MeritEntry {
+    pub student: Student,
+    pub average: Option<f64>,
+    pub rank: Option<Rank>,
+    pub mention: Option<String>,
+    /// End-of-year decision, only given on annual class merit lists
+    pub decision: Option<Decision>,
+}
+
+fn by_rank(a: &Option<Rank>, b: &Option<Rank>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.position.cmp(&b.position),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Ordered merit list of a class from the students' weighted averages
+pub fn merit_list(averages: &[(Student, StudentAverage)], rules: &GradingRules, scope: Scope) -> Vec<MeritEntry> {
+    let scores: Vec<Option<f64>> = averages.iter().map(|(_, a)| a.average).collect();
+    let ranks = competition_ranks(&scores);
+
+    let mut entries: Vec<MeritEntry> = averages
+        .iter()
+        .zip(ranks)
+        .map(|((student, average), rank)| MeritEntry {
+            student: student.clone(),
+            average: average.average,
+            rank,
+            mention: average.average.and_then(|a| rules.mention(a)).map(str::to_string),
+            decision: if scope.is_annual() {
+                rules.decide(average).map(|outcome| outcome.decision)
+            } else {
+                None
+            },
+        })
+        .collect();
+
+    entries.sort_by(|a, b| by_rank(&a.rank, &b.rank));
+    entries
+}
+
+/// Ordered ranking of a class in a single subject
+pub fn subject_ranking(
+    subject_code: &str,
+    averages: &[(Student, StudentAverage)],
+    rules: &GradingRules,
+) -> Vec<MeritEntry> {
+    let scores: Vec<Option<f64>> = averages
+        .iter()
+        .map(|(_, a)| {
+            a.subjects
+                .iter()
+                .find(|s| s.subject_code == subject_code)
+                .and_then(|s| s.average)
+        })
+        .collect();
+    let ranks = competition_ranks(&scores);
+
+    let mut entries: Vec<MeritEntry> = averages
+        .iter()
+        .zip(scores)
+        .zip(ranks)
+        .map(|(((student, _), score), rank)| MeritEntry {
+            student: student.clone(),
+            average: score,
+            rank,
+            mention: score.and_then(|a| rules.mention(a)).map(str::to_string),
+            decision: None,
+        })
+        .collect();
+
+    entries.sort_by(|a, b| by_rank(&a.rank, &b.rank));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::SubjectAverage;
+    use crate::grading::rules::{DecisionRules, MentionRule};
+
+    fn rules() -> GradingRules {
+        GradingRules {
+            mentions: vec![
+                MentionRule { id: 1, label: "Bien".into(), min_average: 14.0 },
+                MentionRule { id: 2, label: "Passable".into(), min_average: 10.0 },
+            ],
+            decisions: DecisionRules::default(),
+        }
+    }
+
+    fn entry(matricule: &str, average: Option<f64>, math: Option<f64>) -> (Student, StudentAverage) {
+        (
+            Student {
+                matricule: matricule.into(),
+                name: matricule.into(),
+                surname: "Test".into(),
+                class: "10A".into(),
+            },
+            StudentAverage {
+                matricule: matricule.into(),
+                subjects: vec![SubjectAverage {
+                    subject_code: "MATH".into(),
+                    coefficient: 1,
+                    average: math,
+                    counted: math.is_some(),
+                }],
+                total_coefficients: 1,
+                total_points: 0.0,
+                average,
+            },
+        )
+    }
+
+    #[test]
+    fn test_competition_ranks_share_ties() {
+        let ranks = competition_ranks(&[Some(12.0), Some(15.0), Some(12.0), Some(9.5), None]);
+
+        assert_eq!(ranks[1], Some(Rank { position: 1, ex_aequo: false, out_of: 5 }));
+        assert_eq!(ranks[0], Some(Rank { position: 2, ex_aequo: true, out_of: 5 }));
+        assert_eq!(ranks[2], ranks[0]);
+        assert_eq!(ranks[3].unwrap().position, 4);
+        assert_eq!(ranks[4], None);
+    }
+
+    #[test]
+    fn test_rank_display() {
+        assert_eq!(Rank { position: 1, ex_aequo: false, out_of: 42 }.to_string(), "1er sur 42");
+        assert_eq!(Rank { position: 5, ex_aequo: false, out_of: 42 }.to_string(), "5e sur 42");
+        assert_eq!(Rank { position: 2, ex_aequo: true, out_of: 42 }.to_string(), "2e ex aequo sur 42");
+    }
+
+    #[test]
+    fn test_merit_list_is_ordered() {
+        let averages = vec![
+            entry("S001", Some(11.0), Some(18.0)),
+            entry("S002", None, None),
+            entry("S003", Some(14.5), Some(9.0)),
+        ];
+
+        let list = merit_list(&averages, &rules(), Scope::Year(1));
+        let order: Vec<&str> = list.iter().map(|e| e.student.matricule.as_str()).collect();
+        assert_eq!(order, vec!["S003", "S001", "S002"]);
+        assert_eq!(list[0].mention.as_deref(), Some("Bien"));
+        assert_eq!(list[0].decision, Some(Decision::Admitted));
+        assert!(list[2].rank.is_none());
+        assert!(merit_list(&averages, &rules(), Scope::Period(1))[0].decision.is_none());
+
+        let ranking = subject_ranking("MATH", &averages, &rules());
+        assert_eq!(ranking[0].student.matricule, "S001");
+        assert_eq!(ranking[0].average, Some(18.0));
+    }
+}
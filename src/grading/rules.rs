@@ -0,0 +1,231 @@
+use std::fmt;
+use crate::grading::StudentAverage;
+
+/// Mention granted to averages of at least `min_average`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MentionRule {
+    pub id: i64,
+    pub label: String,
+    pub min_average: f64,
+}
+
+/// Minimum average required in the subjects whose code starts with `subject_prefix`
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySubjectRule {
+    pub subject_prefix: String,
+    pub min_average: f64,
+}
+
+/// Thresholds of the end-of-year decision
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionRules {
+    /// Minimum general average to be admitted
+    pub admission_average: f64,
+    /// Students below this general average are excluded
+    pub exclusion_average: f64,
+    /// A subject is failed below this average
+    pub failing_subject_average: f64,
+    /// Maximum number of failed subjects to be admitted
+    pub max_failed_subjects: u32,
+    pub key_subjects: Vec<KeySubjectRule>,
+}
+
+impl Default for DecisionRules {
+    fn default() -> Self {
+        Self {
+            admission_average: 10.0,
+            exclusion_average: 7.0,
+            failing_subject_average: 10.0,
+            max_failed_subjects: 3,
+            key_subjects: Vec::new(),
+        }
+    }
+}
+
+/// End-of-year decision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decision {
+    Admitted,
+    Repeat,
+    Excluded,
+}
+
+impl Decision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Admitted => "admis",
+            Self::Repeat => "redouble",
+            Self::Excluded => "exclu",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "admis" => Some(Self::Admitted),
+            "redouble" => Some(Self::Repeat),
+            "exclu" => Some(Self::Excluded),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Admitted => write!(f, "Admis"),
+            Self::Repeat => write!(f, "Redouble"),
+            Self::Excluded => write!(f, "Exclu"),
+        }
+    }
+}
+
+/// Decision with the reasons that prevented admission
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionOutcome {
+    pub decision: Decision,
+    pub reasons: Vec<String>,
+}
+
+/// Mentions and decision rules configured by the school
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradingRules {
+    pub mentions: Vec<MentionRule>,
+    pub decisions: DecisionRules,
+}
+
+impl GradingRules {
+    /// Mention of the highest rule whose threshold is reached
+    pub fn mention(&self, average: f64) -> Option<&str> {
+        self.mentions
+            .iter()
+            .filter(|rule| average >= rule.min_average)
+            .max_by(|a, b| a.min_average.total_cmp(&b.min_average))
+            .map(|rule| rule.label.as_str())
+    }
+
+    /// End-of-year decision for a student, `None` without any average
+    pub fn decide(&self, average: &StudentAverage) -> Option<DecisionOutcome> {
+        let general = average.average?;
+        let rules = &self.decisions;
+
+        if general < rules.exclusion_average {
+            return Some(DecisionOutcome {
+                decision: Decision::Excluded,
+                reasons: vec![format!("moyenne générale inférieure à {:.2}", rules.exclusion_average)],
+            });
+        }
+
+        let mut reasons = Vec::new();
+
+        if general < rules.admission_average {
+            reasons.push(format!("moyenne générale inférieure à {:.2}", rules.admission_average));
+        }
+
+        let failed = average
+            .subjects
+            .iter()
+            .filter(|s| s.counted && s.average.unwrap_or(0.0) < rules.failing_subject_average)
+            .count() as u32;
+        if failed > rules.max_failed_subjects {
+            reasons.push(format!("{} matières en échec (maximum {})", failed, rules.max_failed_subjects));
+        }
+
+        for key in &rules.key_subjects {
+            for subject in average.subjects.iter().filter(|s| s.subject_code.starts_with(&key.subject_prefix)) {
+                if let Some(value) = subject.average
+                    && value < key.min_average
+                {
+                    reasons.push(format!(
+                        "{:.2} en {} (minimum {:.2})",
+                        value, subject.subject_code, key.min_average
+                    ));
+                }
+            }
+        }
+
+        let decision = if reasons.is_empty() { Decision::Admitted } else { Decision::Repeat };
+        Some(DecisionOutcome { decision, reasons })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::SubjectAverage;
+
+    fn rules() -> GradingRules {
+        let mentions = [("Excellent", 18.0), ("Très Bien", 16.0), ("Bien", 14.0), ("Passable", 10.0), ("Insuffisant", 0.0)]
+            .iter()
+            .enumerate()
+            .map(|(id, (label, min))| MentionRule { id: id as i64, label: label.to_string(), min_average: *min })
+            .collect();
+
+        GradingRules {
+            mentions,
+            decisions: DecisionRules {
+                admission_average: 10.0,
+                exclusion_average: 7.0,
+                failing_subject_average: 10.0,
+                max_failed_subjects: 1,
+                key_subjects: vec![KeySubjectRule { subject_prefix: "MATH".into(), min_average: 8.0 }],
+            },
+        }
+    }
+
+    fn average(general: f64, subjects: &[(&str, f64)]) -> StudentAverage {
+        StudentAverage {
+            matricule: "S001".into(),
+            subjects: subjects
+                .iter()
+                .map(|(code, value)| SubjectAverage {
+                    subject_code: code.to_string(),
+                    coefficient: 1,
+                    average: Some(*value),
+                    counted: true,
+                })
+                .collect(),
+            total_coefficients: subjects.len() as u32,
+            total_points: 0.0,
+            average: Some(general),
+        }
+    }
+
+    #[test]
+    fn test_mentions() {
+        let rules = rules();
+        assert_eq!(rules.mention(18.5), Some("Excellent"));
+        assert_eq!(rules.mention(16.0), Some("Très Bien"));
+        assert_eq!(rules.mention(12.0), Some("Passable"));
+        assert_eq!(rules.mention(3.0), Some("Insuffisant"));
+    }
+
+    #[test]
+    fn test_admitted() {
+        let outcome = rules().decide(&average(12.0, &[("MATH6", 11.0), ("FR6", 9.0)])).unwrap();
+        assert_eq!(outcome.decision, Decision::Admitted);
+        assert!(outcome.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_repeat_on_key_subject_or_failures() {
+        let key = rules().decide(&average(12.0, &[("MATH6", 7.5), ("FR6", 16.5)])).unwrap();
+        assert_eq!(key.decision, Decision::Repeat);
+        assert_eq!(key.reasons.len(), 1);
+
+        let failures = rules().decide(&average(11.0, &[("ART6", 9.0), ("MUS6", 8.0), ("FR6", 16.0)])).unwrap();
+        assert_eq!(failures.decision, Decision::Repeat);
+
+        let low = rules().decide(&average(9.0, &[("FR6", 9.0)])).unwrap();
+        assert_eq!(low.decision, Decision::Repeat);
+    }
+
+    #[test]
+    fn test_excluded() {
+        let outcome = rules().decide(&average(6.5, &[("MATH6", 6.5)])).unwrap();
+        assert_eq!(outcome.decision, Decision::Excluded);
+
+        let mut none = average(6.5, &[]);
+        none.average = None;
+        assert!(rules().decide(&none).is_none());
+    }
+}
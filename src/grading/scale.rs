@@ -0,0 +1,193 @@
+use std::fmt;
+
+/// Setting keys holding the grading scale
+pub const SCALE_MIN_SETTING: &str = "grading.scale_min";
+pub const SCALE_MAX_SETTING: &str = "grading.scale_max";
+pub const SCALE_STEP_SETTING: &str = "grading.scale_step";
+
+/// Notes are stored out of 20, whatever the scale
+pub const MAX_NOTE: f64 = 20.0;
+
+/// Granularity of the notes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScaleStep {
+    Any,
+    HalfPoint,
+    QuarterPoint,
+}
+
+impl ScaleStep {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::HalfPoint => "0.5",
+            Self::QuarterPoint => "0.25",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "any" => Some(Self::Any),
+            "0.5" => Some(Self::HalfPoint),
+            "0.25" => Some(Self::QuarterPoint),
+            _ => None,
+        }
+    }
+
+    /// Size of a step, `None` if any value is allowed
+    pub fn size(self) -> Option<f64> {
+        match self {
+            Self::Any => None,
+            Self::HalfPoint => Some(0.5),
+            Self::QuarterPoint => Some(0.25),
+        }
+    }
+}
+
+impl fmt::Display for ScaleStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "libre"),
+            Self::HalfPoint => write!(f, "au demi-point"),
+            Self::QuarterPoint => write!(f, "au quart de point"),
+        }
+    }
+}
+
+/// Why a value is not a valid note
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradeError {
+    NotANumber,
+    OutOfRange { value: f64, min: f64, max: f64 },
+    OffStep { value: f64, step: ScaleStep },
+}
+
+impl fmt::Display for GradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotANumber =>
+                write!(f, "la note n'est pas un nombre"),
+            Self::OutOfRange { value, min, max } =>
+                write!(f, "la note {value} n'est pas comprise entre {min} et {max}"),
+            Self::OffStep { value, step } =>
+                write!(f, "la note {value} n'est pas arrondie {step}"),
+        }
+    }
+}
+
+impl std::error::Error for GradeError {}
+
+/// Bounds and granularity of the notes, within 0 to 20
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradeScale {
+    pub min: f64,
+    pub max: f64,
+    pub step: ScaleStep,
+}
+
+impl Default for GradeScale {
+    fn default() -> Self {
+        Self { min: 0.0, max: MAX_NOTE, step: ScaleStep::Any }
+    }
+}
+
+impl GradeScale {
+    /// `None` unless 0 ≤ min < max ≤ 20
+    pub fn new(min: f64, max: f64, step: ScaleStep) -> Option<Self> {
+        if 0.0 <= min && min < max && max <= MAX_NOTE {
+            Some(Self { min, max, step })
+        } else {
+            None
+        }
+    }
+
+    pub fn check(&self, value: f64) -> Result<(), GradeError> {
+        if !value.is_finite() {
+            return Err(GradeError::NotANumber);
+        }
+        if value < self.min || value > self.max {
+            return Err(GradeError::OutOfRange { value, min: self.min, max: self.max });
+        }
+        if let Some(size) = self.step.size() {
+            let steps = value / size;
+            if (steps - steps.round()).abs() > 1e-9 {
+                return Err(GradeError::OffStep { value, step: self.step });
+            }
+        }
+        Ok(())
+    }
+
+    /// Bring a computed value within the bounds and onto the steps of the
+    /// scale, to the nearest hundredth when any value is allowed
+    pub fn fit(&self, value: f64) -> f64 {
+        let size = self.step.size().unwrap_or(0.01);
+        let steps_per_point = (1.0 / size).round();
+        let value = (value.clamp(self.min, self.max) * steps_per_point).round() / steps_per_point;
+        if value > self.max {
+            value - size
+        } else if value < self.min {
+            value + size
+        } else {
+            value
+        }
+    }
+}
+
+impl fmt::Display for GradeScale {
+    /// e.g. "de 0 à 20, au demi-point"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "de {} à {}, {}", self.min, self.max, self.step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_scale_accepts_zero_to_twenty() {
+        let scale = GradeScale::default();
+
+        assert!(scale.check(0.0).is_ok());
+        assert!(scale.check(13.37).is_ok());
+        assert!(scale.check(20.0).is_ok());
+        assert_eq!(scale.check(-1.0), Err(GradeError::OutOfRange { value: -1.0, min: 0.0, max: 20.0 }));
+        assert!(scale.check(250.0).is_err());
+        assert_eq!(scale.check(f64::NAN), Err(GradeError::NotANumber));
+    }
+
+    #[test]
+    fn test_step_granularity() {
+        let half = GradeScale::new(0.0, 20.0, ScaleStep::HalfPoint).unwrap();
+        assert!(half.check(12.5).is_ok());
+        assert!(matches!(half.check(12.25), Err(GradeError::OffStep { .. })));
+
+        let quarter = GradeScale::new(0.0, 20.0, ScaleStep::QuarterPoint).unwrap();
+        assert!(quarter.check(12.25).is_ok());
+        assert!(quarter.check(12.3).is_err());
+    }
+
+    #[test]
+    fn test_scale_bounds_stay_within_twenty() {
+        assert!(GradeScale::new(1.0, 20.0, ScaleStep::Any).is_some());
+        assert!(GradeScale::new(0.0, 100.0, ScaleStep::Any).is_none());
+        assert!(GradeScale::new(10.0, 10.0, ScaleStep::Any).is_none());
+        assert!(GradeScale::new(-1.0, 20.0, ScaleStep::Any).is_none());
+
+        assert_eq!(ScaleStep::parse(ScaleStep::QuarterPoint.as_str()), Some(ScaleStep::QuarterPoint));
+        assert_eq!(ScaleStep::parse("0.1"), None);
+    }
+
+    #[test]
+    fn test_fit_values_to_the_scale() {
+        let scale = GradeScale::default();
+        assert_eq!(scale.fit(23.4), 20.0);
+        assert_eq!(scale.fit(-2.0), 0.0);
+        assert_eq!(scale.fit(12.3456), 12.35);
+
+        let half = GradeScale::new(1.0, 19.8, ScaleStep::HalfPoint).unwrap();
+        assert_eq!(half.fit(12.3), 12.5);
+        assert_eq!(half.fit(20.0), 19.5);
+        assert_eq!(half.fit(0.0), 1.0);
+    }
+}
@@ -0,0 +1,391 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rusqlite::Connection;
+use crate::db::repositories::enrolment_repository::{EnrolmentRepository, NOT_DEPARTED};
+use crate::db::repositories::evaluation_repository::EvaluationRepository;
+use crate::db::repositories::note_repository::{Note, NoteRepository};
+use crate::db::repositories::period_repository::{Period, PeriodRepository};
+use crate::db::repositories::repository::Repository;
+use crate::db::repositories::rules_repository::RulesRepository;
+use crate::db::repositories::settings_repository::SettingsRepository;
+use crate::db::repositories::student_repository::{Student, StudentRepository};
+use crate::db::repositories::subject_repository::{Subject, SubjectRepository};
+use crate::grading::attendance::{AttendanceThresholds, MAX_LATENESS_SETTING, MAX_UNJUSTIFIED_HOURS_SETTING};
+use crate::grading::rules::GradingRules;
+use crate::grading::warnings::{WarningThresholds, CORE_FAILURES_SETTING, MAX_DROP_SETTING, OUTLIER_Z_SCORE_SETTING};
+use crate::grading::scale::{GradeScale, ScaleStep, SCALE_MAX_SETTING, SCALE_MIN_SETTING, SCALE_STEP_SETTING};
+use crate::grading::weights::NoteWeights;
+use crate::grading::{self, GradingPolicy, MissingNotePolicy, Scope, StudentAverage, MISSING_NOTES_SETTING};
+
+/// Computes averages from the database using the configured policy
+pub struct GradingService {
+    student_repo: StudentRepository,
+    subject_repo: SubjectRepository,
+    note_repo: NoteRepository,
+    settings_repo: SettingsRepository,
+    rules_repo: RulesRepository,
+    period_repo: PeriodRepository,
+    evaluation_repo: EvaluationRepository,
+    enrolment_repo: EnrolmentRepository,
+}
+
+/// Students enrolled in the class `?1` during the year `?2`, or currently in
+/// that class when they have no enrolment that year and are still at the
+/// school
+fn enrolled_in_class_condition() -> String {
+    format!(
+        "(matricule IN (SELECT matricule FROM enrolments
+                        WHERE class = ?1 AND academic_year_id = ?2 AND status <> 'transferred')
+          OR (class = ?1 AND matricule NOT IN (SELECT matricule FROM enrolments WHERE academic_year_id = ?2)
+              AND {}))",
+        NOT_DEPARTED
+    )
+}
+
+impl GradingService {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self {
+            student_repo: StudentRepository::new(conn.clone()),
+            subject_repo: SubjectRepository::new(conn.clone()),
+            note_repo: NoteRepository::new(conn.clone()),
+            settings_repo: SettingsRepository::new(conn.clone()),
+            rules_repo: RulesRepository::new(conn.clone()),
+            period_repo: PeriodRepository::new(conn.clone()),
+            evaluation_repo: EvaluationRepository::new(conn.clone()),
+            enrolment_repo: EnrolmentRepository::new(conn),
+        }
+    }
+
+    /// Grading policy stored in the settings
+    pub fn policy(&self) -> rusqlite::Result<GradingPolicy> {
+        let missing_notes = self
+            .settings_repo
+            .get(MISSING_NOTES_SETTING)?
+            .and_then(|value| MissingNotePolicy::parse(&value))
+            .unwrap_or(MissingNotePolicy::Exclude);
+
+        Ok(GradingPolicy {
+            missing_notes,
+            ..GradingPolicy::default()
+        })
+    }
+
+    /// Grading scale stored in the settings, the default scale if it is invalid
+    pub fn scale(&self) -> rusqlite::Result<GradeScale> {
+        let default = GradeScale::default();
+        let bound = |key: &str, default: f64| -> rusqlite::Result<f64> {
+            Ok(self.settings_repo.get(key)?.and_then(|value| value.parse().ok()).unwrap_or(default))
+        };
+        let step = self
+            .settings_repo
+            .get(SCALE_STEP_SETTING)?
+            .and_then(|value| ScaleStep::parse(&value))
+            .unwrap_or(default.step);
+
+        Ok(GradeScale::new(bound(SCALE_MIN_SETTING, default.min)?, bound(SCALE_MAX_SETTING, default.max)?, step)
+            .unwrap_or(default))
+    }
+
+    pub fn set_scale(&self, scale: &GradeScale) -> rusqlite::Result<()> {
+        self.settings_repo.set(SCALE_MIN_SETTING, &scale.min.to_string())?;
+        self.settings_repo.set(SCALE_MAX_SETTING, &scale.max.to_string())?;
+        self.settings_repo.set(SCALE_STEP_SETTING, scale.step.as_str())
+    }
+
+    /// Attendance alert thresholds stored in the settings
+    pub fn attendance_thresholds(&self) -> rusqlite::Result<AttendanceThresholds> {
+        let default = AttendanceThresholds::default();
+        let limit = |key: &str, default: u32| -> rusqlite::Result<u32> {
+            Ok(self.settings_repo.get(key)?.and_then(|value| value.parse().ok()).unwrap_or(default))
+        };
+
+        Ok(AttendanceThresholds {
+            max_unjustified_hours: limit(MAX_UNJUSTIFIED_HOURS_SETTING, default.max_unjustified_hours)?,
+            max_lateness: limit(MAX_LATENESS_SETTING, default.max_lateness)?,
+        })
+    }
+
+    pub fn set_attendance_thresholds(&self, thresholds: &AttendanceThresholds) -> rusqlite::Result<()> {
+        self.settings_repo.set(MAX_UNJUSTIFIED_HOURS_SETTING, &thresholds.max_unjustified_hours.to_string())?;
+        self.settings_repo.set(MAX_LATENESS_SETTING, &thresholds.max_lateness.to_string())
+    }
+
+    /// Early-warning thresholds stored in the settings
+    pub fn warning_thresholds(&self) -> rusqlite::Result<WarningThresholds> {
+        let default = WarningThresholds::default();
+        let number = |key: &str, default: f64| -> rusqlite::Result<f64> {
+            Ok(self.settings_repo.get(key)?.and_then(|value| value.parse().ok()).unwrap_or(default))
+        };
+        let core_failures = self
+            .settings_repo
+            .get(CORE_FAILURES_SETTING)?
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default.core_failures);
+
+        Ok(WarningThresholds {
+            max_drop: number(MAX_DROP_SETTING, default.max_drop)?,
+            core_failures,
+            outlier_z_score: number(OUTLIER_Z_SCORE_SETTING, default.outlier_z_score)?,
+        })
+    }
+
+    pub fn set_warning_thresholds(&self, thresholds: &WarningThresholds) -> rusqlite::Result<()> {
+        self.settings_repo.set(MAX_DROP_SETTING, &thresholds.max_drop.to_string())?;
+        self.settings_repo.set(CORE_FAILURES_SETTING, &thresholds.core_failures.to_string())?;
+        self.settings_repo.set(OUTLIER_Z_SCORE_SETTING, &thresholds.outlier_z_score.to_string())
+    }
+
+    /// Mentions and decision rules configured by the school
+    pub fn rules(&self) -> rusqlite::Result<GradingRules> {
+        self.rules_repo.get_grading_rules()
+    }
+
+    /// Weights of the evaluation types and evaluations
+    pub fn weights(&self) -> rusqlite::Result<NoteWeights> {
+        Ok(NoteWeights::new(&self.evaluation_repo.get_types()?, &self.evaluation_repo.get_all()?))
+    }
+
+    pub fn set_missing_notes_policy(&self, policy: MissingNotePolicy) -> rusqlite::Result<()> {
+        self.settings_repo.set(MISSING_NOTES_SETTING, policy.as_str())
+    }
+
+    /// Academic year a scope belongs to
+    fn year_of(&self, scope: Scope) -> rusqlite::Result<i64> {
+        match scope {
+            Scope::Year(year_id) => Ok(year_id),
+            Scope::Period(period_id) => self
+                .period_repo
+                .get_period(period_id)?
+                .map(|period| period.academic_year_id)
+                .ok_or(rusqlite::Error::QueryReturnedNoRows),
+        }
+    }
+
+    /// Subjects of the class a student attended during a year
+    fn subjects_of(&self, student: &Student, year_id: i64) -> rusqlite::Result<Vec<Subject>> {
+        let class = self.enrolment_repo.get_class(&student.matricule, year_id)?.unwrap_or_else(|| student.class.clone());
+        self.subject_repo.filter("class = ?1", &[&class])
+    }
+
+    /// Weighted average of a student over the subjects of their class
+    pub fn student_average(&self, student: &Student, scope: Scope) -> rusqlite::Result<StudentAverage> {
+        let subjects = self.subjects_of(student, self.year_of(scope)?)?;
+        let notes = self.note_repo.get_by_student(&student.matricule, None)?;
+
+        let mut averages = self.scoped_averages(std::slice::from_ref(student), &subjects, &notes, scope)?;
+        Ok(averages.remove(0))
+    }
+
+    /// Averages of a student in each term of an academic year
+    pub fn term_averages(&self, student: &Student, year_id: i64) -> rusqlite::Result<Vec<(Period, StudentAverage)>> {
+        let subjects = self.subjects_of(student, year_id)?;
+        let notes = self.note_repo.get_by_student(&student.matricule, None)?;
+        let policy = self.policy()?;
+        let weights = self.weights()?;
+
+        Ok(self
+            .period_repo
+            .get_periods(year_id)?
+            .into_iter()
+            .map(|period| {
+                let term_notes = notes_of_period(&notes, period.id);
+                let average = grading::student_average(&student.matricule, &subjects, &term_notes, &policy, &weights);
+                (period, average)
+            })
+            .collect())
+    }
+
+    /// Students enrolled in `class` during the year of `scope`, by name
+    pub fn enrolled_in_class(&self, class: &str, scope: Scope) -> rusqlite::Result<Vec<Student>> {
+        let year_id = self.year_of(scope)?;
+        self.student_repo.filter(
+            &format!("{} ORDER BY name, surname", enrolled_in_class_condition()),
+            &[&class, &year_id],
+        )
+    }
+
+    /// Weighted averages of every student enrolled in `class` during the
+    /// year of `scope`
+    pub fn class_averages(&self, class: &str, scope: Scope) -> rusqlite::Result<Vec<(Student, StudentAverage)>> {
+        let year_id = self.year_of(scope)?;
+        let students = self.enrolled_in_class(class, scope)?;
+        let subjects = self.subject_repo.filter("class = ?1", &[&class])?;
+        let notes = self.note_repo.filter(
+            &format!("matricule IN (SELECT matricule FROM students WHERE {})", enrolled_in_class_condition()),
+            &[&class, &year_id],
+        )?;
+
+        let averages = self.scoped_averages(&students, &subjects, &notes, scope)?;
+        Ok(students.into_iter().zip(averages).collect())
+    }
+
+    /// Averages of `students` over a term, or over a whole year from their
+    /// term averages
+    fn scoped_averages(
+        &self,
+        students: &[Student],
+        subjects: &[Subject],
+        notes: &[Note],
+        scope: Scope,
+    ) -> rusqlite::Result<Vec<StudentAverage>> {
+        let policy = self.policy()?;
+        let weights = self.weights()?;
+
+        match scope {
+            Scope::Period(period_id) => {
+                let notes = notes_of_period(notes, period_id);
+                Ok(grading::class_averages(students, subjects, &notes, &policy, &weights))
+            }
+            Scope::Year(year_id) => {
+                let terms: Vec<Vec<StudentAverage>> = self
+                    .period_repo
+                    .get_periods(year_id)?
+                    .iter()
+                    .map(|period| {
+                        let notes = notes_of_period(notes, period.id);
+                        grading::class_averages(students, subjects, &notes, &policy, &weights)
+                    })
+                    .collect();
+
+                Ok(students
+                    .iter()
+                    .enumerate()
+                    .map(|(index, student)| {
+                        let student_terms: Vec<StudentAverage> =
+                            terms.iter().map(|term| term[index].clone()).collect();
+                        grading::annual_average(&student.matricule, &student_terms, &policy)
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+fn notes_of_period(notes: &[Note], period_id: i64) -> Vec<Note> {
+    notes
+        .iter()
+        .filter(|note| note.period_id == Some(period_id))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    fn setup_service() -> GradingService {
+        setup().0
+    }
+
+    fn setup() -> (GradingService, Rc<RefCell<Connection>>) {
+        let conn = Rc::new(RefCell::new(crate::db::bootstrap::open_database(":memory:").unwrap()));
+        let schema = include_str!("../../sql/schema.sql");
+        crate::db::executor::execute_sql_script(conn.clone(), schema).unwrap();
+
+        {
+            let conn = conn.borrow();
+            conn.execute_batch(
+                "INSERT INTO classes (code, level) VALUES ('10A', '10');
+                 INSERT INTO students (matricule, name, surname, class) VALUES
+                    ('S001', 'Alice', 'Smith', '10A'),
+                    ('S002', 'Bob', 'Jones', '10A');
+                 INSERT INTO subjects (code, name, class, coefficient) VALUES
+                    ('MATH101', 'Mathematics', '10A', 3),
+                    ('PHY101', 'Physics', '10A', 1);",
+            ).unwrap();
+            let notes = [
+                ("S001", "MATH101", 12.0, 1),
+                ("S001", "MATH101", 14.0, 1),
+                ("S002", "PHY101", 16.0, 1),
+                ("S001", "MATH101", 18.0, 2),
+            ];
+            for (matricule, subject, value, period) in notes {
+                conn.execute(
+                    "INSERT INTO notes (matricule, subject_code, value, period_id) VALUES (?1, ?2, ?3, ?4)",
+                    params![matricule, subject, value, period],
+                ).unwrap();
+            }
+        }
+
+        (GradingService::new(conn.clone()), conn)
+    }
+
+    const TERM: Scope = Scope::Period(1);
+
+    fn student(matricule: &str) -> Student {
+        Student {
+            matricule: matricule.into(),
+            name: "Alice".into(),
+            surname: "Smith".into(),
+            class: "10A".into(),
+        }
+    }
+
+    #[test]
+    fn test_student_average_follows_policy() {
+        let service = setup_service();
+
+        assert_eq!(service.policy().unwrap(), GradingPolicy::default());
+        assert_eq!(service.student_average(&student("S001"), TERM).unwrap().average, Some(13.0));
+        assert_eq!(service.student_average(&student("S002"), TERM).unwrap().average, Some(16.0));
+
+        service.set_missing_notes_policy(MissingNotePolicy::CountAsZero).unwrap();
+        assert_eq!(service.student_average(&student("S001"), TERM).unwrap().average, Some(9.75));
+        assert_eq!(service.student_average(&student("S002"), TERM).unwrap().average, Some(4.0));
+    }
+
+    #[test]
+    fn test_class_averages() {
+        let service = setup_service();
+
+        let averages = service.class_averages("10A", TERM).unwrap();
+        assert_eq!(averages.len(), 2);
+        assert_eq!(averages[0].0.matricule, "S001");
+        assert_eq!(averages[0].1.average, Some(13.0));
+        assert!(service.class_averages("12C", TERM).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_class_averages_follow_enrolments() {
+        let (service, conn) = setup();
+        conn.borrow().execute_batch(
+            "INSERT INTO classes (code, level) VALUES ('11A', '11');
+             UPDATE students SET class = '11A' WHERE matricule = 'S001';",
+        ).unwrap();
+
+        // S001 attended 10A during the year, whatever their current class
+        let averages = service.class_averages("10A", TERM).unwrap();
+        assert_eq!(averages.len(), 2);
+        assert_eq!(averages[0].1.average, Some(13.0));
+        assert!(service.class_averages("11A", TERM).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_term_and_annual_averages() {
+        let service = setup_service();
+
+        let terms = service.term_averages(&student("S001"), 1).unwrap();
+        assert_eq!(terms.len(), 3);
+        assert_eq!(terms[0].1.average, Some(13.0));
+        assert_eq!(terms[1].1.average, Some(18.0));
+        assert_eq!(terms[2].1.average, None);
+
+        let annual = service.student_average(&student("S001"), Scope::Year(1)).unwrap();
+        assert_eq!(annual.average, Some(15.5));
+    }
+
+    #[test]
+    fn test_scale_settings() {
+        let service = setup_service();
+        assert_eq!(service.scale().unwrap(), GradeScale::default());
+
+        let scale = GradeScale::new(1.0, 20.0, ScaleStep::HalfPoint).unwrap();
+        service.set_scale(&scale).unwrap();
+        assert_eq!(service.scale().unwrap(), scale);
+
+        // An inconsistent scale falls back to the default one
+        service.settings_repo.set(SCALE_MIN_SETTING, "30").unwrap();
+        assert_eq!(service.scale().unwrap(), GradeScale::default());
+    }
+}
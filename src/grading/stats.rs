@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use crate::db::repositories::note_repository::Note;
+use crate::db::repositories::subject_repository::Subject;
+
+/// Number of bars in a grade distribution (buckets of 2 points on /20)
+pub const BUCKETS: usize = 10;
+
+/// Bounds of a distribution bucket, e.g. "8-10"
+pub fn bucket_label(index: usize) -> String {
+    format!("{}-{}", index * 2, index * 2 + 2)
+}
+
+/// Aggregated figures of a set of notes
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteSummary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    pub min: f64,
+    /// First and third quartiles
+    pub q1: f64,
+    pub q3: f64,
+    pub max: f64,
+    /// Share of notes of at least 10, in percent
+    pub pass_rate: f64,
+    pub buckets: [usize; BUCKETS],
+}
+
+/// Quantile `q` of sorted values, interpolating between the closest ranks
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = (sorted.len() - 1) as f64 * q;
+    let lower = position.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    sorted[lower] + (position - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+/// Summary of `values`, `None` without any value
+pub fn summarize(values: &[f64]) -> Option<NoteSummary> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let mut buckets = [0usize; BUCKETS];
+    for value in values {
+        let index = ((value / 2.0).floor().max(0.0) as usize).min(BUCKETS - 1);
+        buckets[index] += 1;
+    }
+
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+
+    Some(NoteSummary {
+        count: values.len(),
+        mean,
+        median: quantile(&sorted, 0.5),
+        std_dev: variance.sqrt(),
+        min: sorted[0],
+        q1: quantile(&sorted, 0.25),
+        q3: quantile(&sorted, 0.75),
+        max: sorted[sorted.len() - 1],
+        pass_rate: values.iter().filter(|v| **v >= 10.0).count() as f64 / count * 100.0,
+        buckets,
+    })
+}
+
+/// Summary of the notes of each subject, in the order of `subjects`
+pub fn by_subject(subjects: Vec<Subject>, notes: &[Note]) -> Vec<(Subject, Option<NoteSummary>)> {
+    let mut values: HashMap<&str, Vec<f64>> = HashMap::new();
+    for note in notes {
+        values.entry(note.subject_code.as_str()).or_default().push(note.value);
+    }
+
+    subjects
+        .into_iter()
+        .map(|subject| {
+            let summary = values.get(subject.code.as_str()).and_then(|values| summarize(values));
+            (subject, summary)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        let summary = summarize(&[8.0, 20.0, 10.0]).unwrap();
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.min, 8.0);
+        assert_eq!(summary.max, 20.0);
+        assert_eq!(summary.buckets[4], 1);
+        assert_eq!(summary.buckets[5], 1);
+        assert_eq!(summary.buckets[9], 1);
+        assert!(summarize(&[]).is_none());
+    }
+
+    #[test]
+    fn test_median_quartiles_and_deviation() {
+        let summary = summarize(&[14.0, 2.0, 8.0, 10.0, 6.0]).unwrap();
+        assert_eq!(summary.median, 8.0);
+        assert_eq!(summary.q1, 6.0);
+        assert_eq!(summary.q3, 10.0);
+        assert_eq!(summary.mean, 8.0);
+        assert!((summary.std_dev - 16.0_f64.sqrt()).abs() < 1e-9);
+
+        let even = summarize(&[12.0, 9.0, 11.0, 10.0]).unwrap();
+        assert_eq!(even.median, 10.5);
+        assert_eq!(even.q1, 9.75);
+        assert_eq!(summarize(&[13.0]).unwrap().std_dev, 0.0);
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+use crate::db::repositories::student_repository::Student;
+use crate::grading::StudentAverage;
+use crate::grading::rules::GradingRules;
+use crate::grading::stats;
+
+/// Setting keys holding the early-warning thresholds
+pub const MAX_DROP_SETTING: &str = "warnings.max_drop";
+pub const CORE_FAILURES_SETTING: &str = "warnings.core_failures";
+pub const OUTLIER_Z_SCORE_SETTING: &str = "warnings.outlier_z_score";
+
+/// When a student is flagged for the guidance counsellor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WarningThresholds {
+    /// Fall of the general average between two terms, in points, above
+    /// which a student is flagged
+    pub max_drop: f64,
+    /// Number of failed key subjects from which a student is flagged
+    pub core_failures: u32,
+    /// Number of standard deviations below the class mean from which an
+    /// average is an outlier
+    pub outlier_z_score: f64,
+}
+
+impl Default for WarningThresholds {
+    fn default() -> Self {
+        Self { max_drop: 2.0, core_failures: 2, outlier_z_score: 1.5 }
+    }
+}
+
+impl fmt::Display for WarningThresholds {
+    /// e.g. "baisse de plus de 2.00 points, 2 matières clés en échec, 1.50 écart-type sous la classe"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "baisse de plus de {:.2} points, {} matières clés en échec, {:.2} écart-type sous la classe",
+            self.max_drop, self.core_failures, self.outlier_z_score
+        )
+    }
+}
+
+/// Why a student is flagged
+#[derive(Debug, Clone, PartialEq)]
+pub enum WarningReason {
+    /// General average of the previous term and of this one
+    Drop { from: f64, to: f64 },
+    /// Failed key subjects, with the student's average in each
+    CoreFailures(Vec<(String, f64)>),
+    /// General average far below the class
+    Outlier { z_score: f64 },
+    /// Average in a subject far below the class
+    SubjectOutlier { subject_code: String, average: f64, z_score: f64 },
+}
+
+impl fmt::Display for WarningReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Drop { from, to } =>
+                write!(f, "moyenne en baisse de {:.2} points ({:.2} → {:.2})", from - to, from, to),
+            Self::CoreFailures(subjects) => {
+                let subjects: Vec<String> =
+                    subjects.iter().map(|(code, average)| format!("{} {:.2}", code, average)).collect();
+                write!(f, "{} matières clés en échec ({})", subjects.len(), subjects.join(", "))
+            }
+            Self::Outlier { z_score } =>
+                write!(f, "moyenne générale à {:.1} écarts-types de la classe", z_score),
+            Self::SubjectOutlier { subject_code, average, z_score } =>
+                write!(f, "{:.2} en {} ({:.1} écarts-types)", average, subject_code, z_score),
+        }
+    }
+}
+
+/// A student flagged for the guidance counsellor
+#[derive(Debug, Clone)]
+pub struct EarlyWarning {
+    pub student: Student,
+    pub average: Option<f64>,
+    pub reasons: Vec<WarningReason>,
+}
+
+impl EarlyWarning {
+    /// Number of distinct criteria met, from 1 to 3: a drop, failed key
+    /// subjects and outlying averages
+    pub fn priority(&self) -> usize {
+        let drop = self.reasons.iter().any(|r| matches!(r, WarningReason::Drop { .. }));
+        let core = self.reasons.iter().any(|r| matches!(r, WarningReason::CoreFailures(_)));
+        let outlier = self
+            .reasons
+            .iter()
+            .any(|r| matches!(r, WarningReason::Outlier { .. } | WarningReason::SubjectOutlier { .. }));
+        [drop, core, outlier].into_iter().filter(|met| *met).count()
+    }
+
+    /// e.g. "haute" when every criterion is met
+    pub fn priority_label(&self) -> &'static str {
+        match self.priority() {
+            3 => "haute",
+            2 => "moyenne",
+            _ => "faible",
+        }
+    }
+}
+
+/// Distance of each value to the mean of the series, in standard
+/// deviations. `None` for missing values and for series without spread.
+pub fn z_scores(values: &[Option<f64>]) -> Vec<Option<f64>> {
+    let present: Vec<f64> = values.iter().flatten().copied().collect();
+    let summary = stats::summarize(&present).filter(|summary| summary.std_dev > 0.0);
+
+    values
+        .iter()
+        .map(|value| {
+            let summary = summary.as_ref()?;
+            Some((value.as_ref()? - summary.mean) / summary.std_dev)
+        })
+        .collect()
+}
+
+/// Flag the students of a class: their general average fell by more than
+/// the allowed drop since the `previous` term, they fail several key
+/// subjects of the `rules`, or their general or subject averages are
+/// outliers within the class. The most urgent students come first.
+pub fn detect(
+    averages: &[(Student, StudentAverage)],
+    previous: Option<&[(Student, StudentAverage)]>,
+    rules: &GradingRules,
+    thresholds: &WarningThresholds,
+) -> Vec<EarlyWarning> {
+    let general = z_scores(&averages.iter().map(|(_, a)| a.average).collect::<Vec<_>>());
+    let subject_codes: Vec<&str> = averages
+        .first()
+        .map(|(_, a)| a.subjects.iter().map(|s| s.subject_code.as_str()).collect())
+        .unwrap_or_default();
+    let subject_average = |average: &StudentAverage, code: &str| {
+        average.subjects.iter().find(|s| s.subject_code == code).and_then(|s| s.average)
+    };
+    let by_subject: Vec<Vec<Option<f64>>> = subject_codes
+        .iter()
+        .map(|code| z_scores(&averages.iter().map(|(_, a)| subject_average(a, code)).collect::<Vec<_>>()))
+        .collect();
+
+    let mut warnings: Vec<EarlyWarning> = averages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (student, average))| {
+            let mut reasons = Vec::new();
+
+            let before = previous
+                .and_then(|previous| previous.iter().find(|(s, _)| s.matricule == student.matricule))
+                .and_then(|(_, a)| a.average);
+            if let (Some(from), Some(to)) = (before, average.average)
+                && from - to > thresholds.max_drop
+            {
+                reasons.push(WarningReason::Drop { from, to });
+            }
+
+            let failed: Vec<(String, f64)> = average
+                .subjects
+                .iter()
+                .filter(|s| rules.decisions.key_subjects.iter().any(|key| s.subject_code.starts_with(&key.subject_prefix)))
+                .filter_map(|s| Some((s.subject_code.clone(), s.average?)))
+                .filter(|(_, value)| *value < rules.decisions.failing_subject_average)
+                .collect();
+            if thresholds.core_failures > 0 && failed.len() >= thresholds.core_failures as usize {
+                reasons.push(WarningReason::CoreFailures(failed));
+            }
+
+            // A student far below the class overall is also below it in most
+            // subjects: those are only worth listing for the other students
+            if let Some(z_score) = general[index]
+                && z_score <= -thresholds.outlier_z_score
+            {
+                reasons.push(WarningReason::Outlier { z_score });
+            } else {
+                for (code, z_scores) in subject_codes.iter().zip(&by_subject) {
+                    if let (Some(z_score), Some(value)) = (z_scores[index], subject_average(average, code))
+                        && z_score <= -thresholds.outlier_z_score
+                    {
+                        reasons.push(WarningReason::SubjectOutlier { subject_code: code.to_string(), average: value, z_score });
+                    }
+                }
+            }
+
+            (!reasons.is_empty()).then(|| EarlyWarning { student: student.clone(), average: average.average, reasons })
+        })
+        .collect();
+
+    prioritise(&mut warnings);
+    warnings
+}
+
+/// Order warnings by priority, then from the lowest general average
+pub fn prioritise(warnings: &mut [EarlyWarning]) {
+    warnings.sort_by(|a, b| {
+        b.priority()
+            .cmp(&a.priority())
+            .then(a.average.unwrap_or(f64::MAX).total_cmp(&b.average.unwrap_or(f64::MAX)))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::SubjectAverage;
+    use crate::grading::rules::{DecisionRules, KeySubjectRule};
+
+    fn entry(matricule: &str, math: f64, french: f64, art: f64) -> (Student, StudentAverage) {
+        let subjects: Vec<SubjectAverage> = [("MATH6", math), ("FR6", french), ("ART6", art)]
+            .iter()
+            .map(|(code, value)| SubjectAverage {
+                subject_code: code.to_string(),
+                coefficient: 1,
+                average: Some(*value),
+                counted: true,
+            })
+            .collect();
+        (
+            Student {
+                matricule: matricule.into(),
+                name: matricule.into(),
+                surname: "Test".into(),
+                class: "6A".into(),
+            },
+            StudentAverage {
+                matricule: matricule.into(),
+                subjects,
+                total_coefficients: 3,
+                total_points: math + french + art,
+                average: Some((math + french + art) / 3.0),
+            },
+        )
+    }
+
+    fn rules() -> GradingRules {
+        GradingRules {
+            mentions: vec![],
+            decisions: DecisionRules {
+                key_subjects: vec![
+                    KeySubjectRule { subject_prefix: "MATH".into(), min_average: 8.0 },
+                    KeySubjectRule { subject_prefix: "FR".into(), min_average: 8.0 },
+                ],
+                ..DecisionRules::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_z_scores() {
+        let scores = z_scores(&[Some(8.0), None, Some(12.0)]);
+        assert_eq!(scores, vec![Some(-1.0), None, Some(1.0)]);
+        assert_eq!(z_scores(&[Some(10.0), Some(10.0)]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_detect() {
+        let averages = vec![
+            entry("S1", 14.0, 13.0, 12.0),
+            entry("S2", 13.0, 14.0, 15.0),
+            entry("S3", 12.0, 12.0, 14.0),
+            entry("S4", 15.0, 13.0, 13.0),
+            entry("S5", 6.0, 8.0, 13.0),
+            entry("S6", 13.0, 12.0, 3.0),
+        ];
+        let previous = vec![entry("S1", 17.0, 16.0, 15.0), entry("S5", 8.0, 9.0, 13.0)];
+
+        let warnings = detect(&averages, Some(&previous), &rules(), &WarningThresholds::default());
+        let flagged: Vec<&str> = warnings.iter().map(|w| w.student.matricule.as_str()).collect();
+        assert_eq!(flagged, vec!["S5", "S6", "S1"]);
+
+        // S5: a drop of 1 point is tolerated, but fails both key subjects far below the class
+        assert_eq!(warnings[0].priority(), 2);
+        assert!(warnings[0].reasons.contains(&WarningReason::CoreFailures(vec![
+            ("MATH6".into(), 6.0),
+            ("FR6".into(), 8.0),
+        ])));
+        assert!(matches!(&warnings[1].reasons[..], [WarningReason::SubjectOutlier { subject_code, .. }] if subject_code == "ART6"));
+        assert_eq!(warnings[2].reasons, vec![WarningReason::Drop { from: 16.0, to: 13.0 }]);
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use crate::db::repositories::evaluation_repository::{Evaluation, EvaluationType};
+use crate::db::repositories::note_repository::Note;
+
+/// How the notes of a subject are combined into its average.
+///
+/// Notes are first averaged per evaluation type, each note counting for
+/// the weight of its evaluation. The type averages are then combined
+/// using the type weights, over the types that have notes. Notes
+/// recorded outside any evaluation belong to the default type.
+///
+/// Without any type or evaluation, every note counts the same.
+#[derive(Debug, Clone, Default)]
+pub struct NoteWeights {
+    type_weights: HashMap<String, f64>,
+    /// Type code and weight of each evaluation, by id
+    evaluations: HashMap<i64, (String, f64)>,
+    default_type: Option<String>,
+}
+
+impl NoteWeights {
+    pub fn new(types: &[EvaluationType], evaluations: &[Evaluation]) -> Self {
+        Self {
+            type_weights: types.iter().map(|t| (t.code.clone(), t.weight)).collect(),
+            evaluations: evaluations
+                .iter()
+                .map(|e| (e.id, (e.type_code.clone(), e.weight)))
+                .collect(),
+            default_type: types.iter().find(|t| t.is_default).map(|t| t.code.clone()),
+        }
+    }
+
+    /// Evaluation type of a note and its weight within that type
+    fn classify(&self, note: &Note) -> (Option<&str>, f64) {
+        match note.evaluation_id.and_then(|id| self.evaluations.get(&id)) {
+            Some((type_code, weight)) => (Some(type_code.as_str()), *weight),
+            None => (self.default_type.as_deref(), 1.0),
+        }
+    }
+
+    /// Average of the notes of one subject, `None` without notes
+    pub fn subject_mean(&self, notes: &[&Note]) -> Option<f64> {
+        // (Σ weight × value, Σ weight) per type, in order of appearance
+        let mut groups: Vec<(Option<&str>, f64, f64)> = Vec::new();
+        for note in notes {
+            let (type_code, weight) = self.classify(note);
+            match groups.iter_mut().find(|(code, _, _)| *code == type_code) {
+                Some(group) => {
+                    group.1 += weight * note.value;
+                    group.2 += weight;
+                }
+                None => groups.push((type_code, weight * note.value, weight)),
+            }
+        }
+
+        let mut total = 0.0;
+        let mut total_weight = 0.0;
+        for (type_code, points, weight) in groups {
+            let type_weight = type_code
+                .and_then(|code| self.type_weights.get(code))
+                .copied()
+                .unwrap_or(1.0);
+            total += type_weight * points / weight;
+            total_weight += type_weight;
+        }
+
+        if total_weight > 0.0 {
+            Some(total / total_weight)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluation_type(code: &str, weight: f64, is_default: bool) -> EvaluationType {
+        EvaluationType { code: code.into(), label: code.into(), weight, is_default }
+    }
+
+    fn evaluation(id: i64, type_code: &str, weight: f64) -> Evaluation {
+        Evaluation {
+            id,
+            subject_code: "MATH".into(),
+            period_id: 1,
+            type_code: type_code.into(),
+            title: "Contrôle".into(),
+            date: "2025-10-01".into(),
+            max_score: 20.0,
+            weight,
+        }
+    }
+
+    fn note(value: f64, evaluation_id: Option<i64>) -> Note {
+        Note {
+            id: 0,
+            value,
+            matricule: "S001".into(),
+            subject_code: "MATH".into(),
+            period_id: Some(1),
+            evaluation_id,
+        }
+    }
+
+    fn weights() -> NoteWeights {
+        NoteWeights::new(
+            &[
+                evaluation_type("devoir", 20.0, true),
+                evaluation_type("interrogation", 20.0, false),
+                evaluation_type("composition", 60.0, false),
+            ],
+            &[evaluation(1, "devoir", 1.0), evaluation(2, "devoir", 2.0), evaluation(3, "composition", 1.0)],
+        )
+    }
+
+    #[test]
+    fn test_plain_mean_without_weights() {
+        let notes = [note(10.0, None), note(14.0, Some(3))];
+        let refs: Vec<&Note> = notes.iter().collect();
+
+        assert_eq!(NoteWeights::default().subject_mean(&refs), Some(12.0));
+        assert_eq!(NoteWeights::default().subject_mean(&[]), None);
+    }
+
+    #[test]
+    fn test_types_are_weighted() {
+        // devoir: (8 × 1 + 14 × 2) / 3 = 12, composition: 17
+        let notes = [note(8.0, Some(1)), note(14.0, Some(2)), note(17.0, Some(3))];
+        let refs: Vec<&Note> = notes.iter().collect();
+
+        let mean = weights().subject_mean(&refs).unwrap();
+        assert!((mean - (20.0 * 12.0 + 60.0 * 17.0) / 80.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_notes_without_evaluation_use_default_type() {
+        let notes = [note(10.0, None), note(16.0, Some(3))];
+        let refs: Vec<&Note> = notes.iter().collect();
+
+        // 25 % devoir, 75 % composition
+        assert_eq!(weights().subject_mean(&refs), Some(14.5));
+
+        // Only one type evaluated: plain mean of its notes
+        let notes = [note(10.0, None), note(16.0, Some(1))];
+        let refs: Vec<&Note> = notes.iter().collect();
+        assert_eq!(weights().subject_mean(&refs), Some(13.0));
+    }
+}
@@ -3,6 +3,9 @@ mod app;
 mod auth;
 mod cli;
 mod views;
+mod reports;
+mod grading;
+mod students;
 
 use std::io;
 use crate::db::init_database;
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+use crate::db::repositories::profile_repository::{Guardian, StudentProfile};
+use crate::db::repositories::student_repository::Student;
+use crate::db::repositories::subject_repository::Subject;
+use crate::db::repositories::teacher_repository::Teacher;
+use crate::grading::{Scope, StudentAverage};
+use crate::grading::attendance::AttendanceTotals;
+use crate::grading::ranking::{self, Rank};
+use crate::grading::rules::{DecisionOutcome, GradingRules};
+use crate::reports::format_value;
+use crate::reports::pdf::{Font, Page, PdfDocument, PAGE_HEIGHT, PAGE_WIDTH};
+
+/// One subject line of a report card
+#[derive(Debug, Clone)]
+pub struct BulletinLine {
+    pub subject_code: String,
+    pub subject_name: String,
+    pub teacher: Option<String>,
+    pub coefficient: u8,
+    /// Average of the student's notes in this subject, if any
+    pub average: Option<f64>,
+    /// average × coefficient
+    pub points: Option<f64>,
+}
+
+/// Report card (bulletin de notes) of a single student
+#[derive(Debug, Clone)]
+pub struct Bulletin {
+    pub student: Student,
+    /// Term or school year covered, e.g. "1er trimestre 2025-2026"
+    pub period: String,
+    pub lines: Vec<BulletinLine>,
+    pub total_coefficients: u32,
+    pub total_points: f64,
+    pub weighted_average: Option<f64>,
+    pub rank: Option<Rank>,
+    pub mention: Option<String>,
+    /// End-of-year decision, only on annual bulletins
+    pub decision: Option<DecisionOutcome>,
+    pub attendance: AttendanceTotals,
+    pub profile: Option<StudentProfile>,
+    pub guardians: Vec<Guardian>,
+}
+
+/// Build the bulletins of every student of a class.
+///
+/// `averages` are the students' averages over `scope`, computed on the
+/// `subjects` taught in the class, whose `teachers` are given by subject
+/// code, and `attendance` holds the absences of the students by matricule.
+/// Mentions and decisions follow the school's `rules`; `period` is printed
+/// on every bulletin.
+pub fn build_class_bulletins(
+    averages: &[(Student, StudentAverage)],
+    subjects: &[Subject],
+    teachers: &HashMap<String, Teacher>,
+    attendance: &HashMap<String, AttendanceTotals>,
+    rules: &GradingRules,
+    scope: Scope,
+    period: &str,
+) -> Vec<Bulletin> {
+    let scores: Vec<Option<f64>> = averages.iter().map(|(_, a)| a.average).collect();
+    let ranks = ranking::competition_ranks(&scores);
+
+    averages
+        .iter()
+        .zip(ranks)
+        .map(|((student, average), rank)| {
+            let lines = subjects
+                .iter()
+                .zip(&average.subjects)
+                .map(|(subject, subject_average)| BulletinLine {
+                    subject_code: subject.code.clone(),
+                    subject_name: subject.name.clone(),
+                    teacher: teachers.get(&subject.code).map(|t| t.to_string()),
+                    coefficient: subject.coefficient,
+                    average: subject_average.average,
+                    points: subject_average.points(),
+                })
+                .collect();
+
+            Bulletin {
+                student: student.clone(),
+                period: period.to_string(),
+                lines,
+                total_coefficients: average.total_coefficients,
+                total_points: average.total_points,
+                weighted_average: average.average,
+                rank,
+                mention: average.average.and_then(|a| rules.mention(a)).map(str::to_string),
+                decision: if scope.is_annual() { rules.decide(average) } else { None },
+                attendance: attendance.get(&student.matricule).copied().unwrap_or_default(),
+                profile: None,
+                guardians: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Fill in the profile and the guardians of the students of `bulletins`,
+/// both given by matricule
+pub fn attach_profiles(
+    bulletins: &mut [Bulletin],
+    profiles: &mut HashMap<String, StudentProfile>,
+    guardians: &mut HashMap<String, Vec<Guardian>>,
+) {
+    for bulletin in bulletins {
+        bulletin.profile = profiles.remove(&bulletin.student.matricule);
+        bulletin.guardians = guardians.remove(&bulletin.student.matricule).unwrap_or_default();
+    }
+}
+
+/// Lowest baseline of the notes table on a page
+const BOTTOM_MARGIN: f64 = 60.0;
+/// Height of the totals, the comment box and the signatures below the
+/// notes table
+const FOOTER_HEIGHT: f64 = 260.0;
+
+/// Draw a bulletin on a new page of `doc`, continued on the next pages
+/// when its notes table does not fit
+pub fn render_bulletin(doc: &mut PdfDocument, bulletin: &Bulletin) {
+    let mut page = doc.add_page();
+    let left = 50.0;
+    let right = PAGE_WIDTH - 50.0;
+    let mut y = PAGE_HEIGHT - 60.0;
+
+    page.text(left, y, 18.0, Font::Bold, "Nschool");
+    page.text(right - 170.0, y, 16.0, Font::Bold, "BULLETIN DE NOTES");
+    y -= 15.0;
+    page.line(left, y, right, y);
+
+    // ---- Identity ----
+    y -= 25.0;
+    let student = &bulletin.student;
+    page.text(left, y, 11.0, Font::Bold, "Nom :");
+    page.text(left + 70.0, y, 11.0, Font::Regular, &student.name);
+    page.text(300.0, y, 11.0, Font::Bold, "Prénom :");
+    page.text(370.0, y, 11.0, Font::Regular, &student.surname);
+    y -= 18.0;
+    page.text(left, y, 11.0, Font::Bold, "Matricule :");
+    page.text(left + 70.0, y, 11.0, Font::Regular, &student.matricule);
+    page.text(300.0, y, 11.0, Font::Bold, "Classe :");
+    page.text(370.0, y, 11.0, Font::Regular, &student.class);
+    y -= 18.0;
+    page.text(left, y, 11.0, Font::Bold, "Période :");
+    page.text(left + 70.0, y, 11.0, Font::Regular, &bulletin.period);
+    let profile = bulletin.profile.as_ref();
+    page.text(300.0, y, 11.0, Font::Bold, "Sexe :");
+    let gender = profile.and_then(|p| p.gender).map(|g| g.to_string()).unwrap_or_else(|| "-".to_string());
+    page.text(370.0, y, 11.0, Font::Regular, &gender);
+    y -= 18.0;
+    page.text(left, y, 11.0, Font::Bold, "Naissance :");
+    let birth = profile.and_then(|p| p.birth_label()).unwrap_or_else(|| "-".to_string());
+    page.text(left + 70.0, y, 11.0, Font::Regular, &birth);
+    y -= 18.0;
+    page.text(left, y, 11.0, Font::Bold, "Responsable :");
+    let guardian = bulletin
+        .guardians
+        .first()
+        .map(Guardian::label)
+        .unwrap_or_else(|| "-".to_string());
+    page.text(left + 85.0, y, 11.0, Font::Regular, &guardian);
+
+    // ---- Notes table ----
+    y -= 35.0;
+    let columns = [left, 215.0, 345.0, 410.0, 465.0, 500.0];
+    let row_height = 18.0;
+    let draw_header = |page: &mut Page, y: f64| {
+        page.fill_rect(left, y - 5.0, right - left, row_height, 0.85);
+        for (x, header) in columns.iter().zip(["Matière", "Enseignant", "Code", "Moyenne", "Coef.", "Points"]) {
+            page.text(x + 4.0, y, 10.0, Font::Bold, header);
+        }
+    };
+    draw_header(page, y);
+    let mut table_top = y + row_height - 5.0;
+
+    for line in &bulletin.lines {
+        if y - row_height < BOTTOM_MARGIN {
+            // Close the table on this page and carry on on the next one
+            y -= 5.0;
+            page.rect(left, y, right - left, table_top - y + 8.0);
+            page = doc.add_page();
+            y = PAGE_HEIGHT - 60.0;
+            let title = format!("{} {} ({}) — suite", student.name, student.surname, student.matricule);
+            page.text(left, y, 11.0, Font::Bold, &title);
+            y -= 30.0;
+            draw_header(page, y);
+            table_top = y + row_height - 5.0;
+        }
+        y -= row_height;
+        page.line(left, y + 13.0, right, y + 13.0);
+        page.text(columns[0] + 4.0, y, 10.0, Font::Regular, &line.subject_name);
+        page.text(columns[1] + 4.0, y, 10.0, Font::Regular, line.teacher.as_deref().unwrap_or("-"));
+        page.text(columns[2] + 4.0, y, 10.0, Font::Regular, &line.subject_code);
+        page.text(columns[3] + 4.0, y, 10.0, Font::Regular, &format_value(line.average));
+        page.text(columns[4] + 4.0, y, 10.0, Font::Regular, &line.coefficient.to_string());
+        page.text(columns[5] + 4.0, y, 10.0, Font::Regular, &format_value(line.points));
+    }
+    y -= 5.0;
+    page.line(left, y, right, y);
+    page.rect(left, y, right - left, table_top - y + 8.0);
+    if y - FOOTER_HEIGHT < BOTTOM_MARGIN {
+        page = doc.add_page();
+        y = PAGE_HEIGHT - 35.0;
+    }
+
+    // ---- Totals ----
+    y -= 25.0;
+    page.text(left, y, 11.0, Font::Bold, "Total des coefficients :");
+    page.text(200.0, y, 11.0, Font::Regular, &bulletin.total_coefficients.to_string());
+    page.text(300.0, y, 11.0, Font::Bold, "Total des points :");
+    page.text(420.0, y, 11.0, Font::Regular, &format!("{:.2}", bulletin.total_points));
+    y -= 18.0;
+    page.text(left, y, 11.0, Font::Bold, "Moyenne générale :");
+    page.text(200.0, y, 11.0, Font::Regular, &format_value(bulletin.weighted_average));
+    page.text(300.0, y, 11.0, Font::Bold, "Rang :");
+    let rank = bulletin
+        .rank
+        .map(|r| r.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    page.text(420.0, y, 11.0, Font::Regular, &rank);
+    y -= 18.0;
+    page.text(left, y, 11.0, Font::Bold, "Mention :");
+    page.text(200.0, y, 11.0, Font::Regular, bulletin.mention.as_deref().unwrap_or("-"));
+    page.text(300.0, y, 11.0, Font::Bold, "Décision :");
+    let decision = bulletin
+        .decision
+        .as_ref()
+        .map(|d| d.decision.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    page.text(420.0, y, 11.0, Font::Regular, &decision);
+    y -= 18.0;
+    let attendance = &bulletin.attendance;
+    page.text(left, y, 11.0, Font::Bold, "Absences :");
+    page.text(200.0, y, 11.0, Font::Regular, &format!("{} h", attendance.absence_hours));
+    page.text(300.0, y, 11.0, Font::Bold, "Non justifiées :");
+    page.text(420.0, y, 11.0, Font::Regular, &format!("{} h", attendance.unjustified_hours));
+    y -= 18.0;
+    page.text(left, y, 11.0, Font::Bold, "Retards :");
+    page.text(200.0, y, 11.0, Font::Regular, &attendance.lateness.to_string());
+
+    // ---- Teacher comment ----
+    y -= 35.0;
+    page.text(left, y, 11.0, Font::Bold, "Appréciation du professeur principal :");
+    y -= 10.0;
+    page.rect(left, y - 80.0, right - left, 80.0);
+
+    draw_signatures(page, y - 110.0);
+}
+
+fn draw_signatures(page: &mut Page, y: f64) {
+    page.text(50.0, y, 10.0, Font::Regular, "Signature des parents");
+    page.text(380.0, y, 10.0, Font::Regular, "Le chef d'établissement");
+}
+
+/// Render one or several bulletins into a single PDF document
+pub fn bulletins_to_pdf(bulletins: &[Bulletin]) -> PdfDocument {
+    let mut doc = PdfDocument::new();
+    for bulletin in bulletins {
+        render_bulletin(&mut doc, bulletin);
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repositories::note_repository::Note;
+    use crate::grading::{self, GradingPolicy};
+    use crate::grading::weights::NoteWeights;
+    use crate::grading::rules::{Decision, DecisionRules, MentionRule};
+
+    fn rules() -> GradingRules {
+        GradingRules {
+            mentions: vec![MentionRule { id: 1, label: "Assez Bien".into(), min_average: 12.0 }],
+            decisions: DecisionRules::default(),
+        }
+    }
+
+    fn build(students: &[Student], subjects: &[Subject], notes: &[Note], scope: Scope) -> Vec<Bulletin> {
+        let averages = grading::class_averages(students, subjects, notes, &GradingPolicy::default(), &NoteWeights::default());
+        let averages: Vec<_> = students.iter().cloned().zip(averages).collect();
+        build_class_bulletins(&averages, subjects, &HashMap::new(), &HashMap::new(), &rules(), scope, "Année 2025-2026")
+    }
+
+    fn student(matricule: &str) -> Student {
+        Student {
+            matricule: matricule.into(),
+            name: "Nom".into(),
+            surname: "Prénom".into(),
+            class: "10A".into(),
+        }
+    }
+
+    fn subject(code: &str, coefficient: u8) -> Subject {
+        Subject {
+            code: code.into(),
+            name: code.into(),
+            class: "10A".into(),
+            coefficient,
+        }
+    }
+
+    fn note(matricule: &str, subject_code: &str, value: f64) -> Note {
+        Note {
+            id: 0,
+            value,
+            matricule: matricule.into(),
+            subject_code: subject_code.into(),
+            period_id: Some(1),
+            evaluation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_weighted_average() {
+        let students = vec![student("S001")];
+        let subjects = vec![subject("MATH", 4), subject("ART", 1), subject("LAT", 2)];
+        let notes = vec![
+            note("S001", "MATH", 12.0),
+            note("S001", "MATH", 16.0),
+            note("S001", "ART", 9.0),
+        ];
+
+        let bulletins = build(&students, &subjects, &notes, Scope::Year(1));
+        let bulletin = &bulletins[0];
+
+        assert_eq!(bulletin.lines[0].average, Some(14.0));
+        assert_eq!(bulletin.lines[0].points, Some(56.0));
+        assert_eq!(bulletin.lines[2].average, None);
+        assert_eq!(bulletin.total_coefficients, 5);
+        assert_eq!(bulletin.weighted_average, Some(13.0));
+        assert_eq!(bulletin.rank.unwrap().position, 1);
+        assert_eq!(bulletin.mention.as_deref(), Some("Assez Bien"));
+        assert_eq!(bulletin.decision.as_ref().unwrap().decision, Decision::Admitted);
+        assert!(build(&students, &subjects, &notes, Scope::Period(1))[0].decision.is_none());
+    }
+
+    #[test]
+    fn test_ranks_with_ties() {
+        let students = vec![student("S001"), student("S002"), student("S003"), student("S004")];
+        let subjects = vec![subject("MATH", 1)];
+        let notes = vec![
+            note("S001", "MATH", 12.0),
+            note("S002", "MATH", 15.0),
+            note("S003", "MATH", 12.0),
+        ];
+
+        let bulletins = build(&students, &subjects, &notes, Scope::Period(1));
+
+        assert_eq!(bulletins[1].rank.unwrap().position, 1);
+        assert_eq!(bulletins[0].rank.unwrap().to_string(), "2e ex aequo sur 4");
+        assert_eq!(bulletins[2].rank, bulletins[0].rank);
+        assert_eq!(bulletins[3].rank, None);
+    }
+
+    #[test]
+    fn test_one_page_per_bulletin() {
+        let students = vec![student("S001"), student("S002")];
+        let bulletins = build(&students, &[subject("MATH", 1)], &[], Scope::Period(1));
+
+        let doc = bulletins_to_pdf(&bulletins);
+        assert_eq!(doc.page_count(), 2);
+    }
+
+    #[test]
+    fn test_long_bulletin_continues_on_next_page() {
+        let subjects = |count: usize| -> Vec<Subject> {
+            (0..count).map(|i| subject(&format!("SUBJ{}", i), 1)).collect()
+        };
+
+        // The totals no longer fit under the table
+        let bulletins = build(&[student("S001")], &subjects(25), &[], Scope::Period(1));
+        assert_eq!(bulletins_to_pdf(&bulletins).page_count(), 2);
+        // The table itself overflows
+        let bulletins = build(&[student("S001")], &subjects(40), &[], Scope::Period(1));
+        assert_eq!(bulletins_to_pdf(&bulletins).page_count(), 2);
+        let bulletins = build(&[student("S001")], &subjects(80), &[], Scope::Period(1));
+        assert_eq!(bulletins_to_pdf(&bulletins).page_count(), 3);
+    }
+}
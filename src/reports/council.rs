@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use crate::db::repositories::student_repository::Student;
+use crate::db::repositories::subject_repository::Subject;
+use crate::db::repositories::teacher_repository::Teacher;
+use crate::grading::{self, Scope, StudentAverage};
+use crate::grading::attendance::{AttendanceThresholds, AttendanceTotals};
+use crate::grading::ranking::{self, MeritEntry};
+use crate::grading::rules::{Decision, DecisionOutcome, GradingRules};
+
+/// Number of students listed at the top and at the bottom of the class
+pub const LISTED_STUDENTS: usize = 3;
+
+/// Class results in one subject
+#[derive(Debug, Clone)]
+pub struct CouncilSubject {
+    pub subject: Subject,
+    pub teacher: Option<String>,
+    /// Mean of the students' averages in the subject
+    pub average: Option<f64>,
+    /// Students whose average in the subject is below the failing average
+    pub failing: usize,
+}
+
+/// A student the council should discuss, with why
+#[derive(Debug, Clone)]
+pub struct StudentAtRisk {
+    pub student: Student,
+    pub average: Option<f64>,
+    /// General average of the previous term, if any
+    pub previous: Option<f64>,
+    pub reasons: Vec<String>,
+}
+
+/// Figures the class council (conseil de classe) reviews for a class and a term
+#[derive(Debug, Clone)]
+pub struct CouncilReport {
+    pub class: String,
+    /// Term or school year covered, e.g. "1er trimestre 2025-2026"
+    pub period: String,
+    pub headcount: usize,
+    /// Mean of the students' general averages
+    pub class_average: Option<f64>,
+    pub subjects: Vec<CouncilSubject>,
+    pub top: Vec<MeritEntry>,
+    pub bottom: Vec<MeritEntry>,
+    pub at_risk: Vec<StudentAtRisk>,
+    /// Absences and lateness of the whole class
+    pub attendance: AttendanceTotals,
+    pub attendance_alerts: Vec<(Student, AttendanceTotals, Vec<String>)>,
+    /// Number of students per mention, best mention first; students
+    /// without any mention are counted under "Aucune"
+    pub mentions: Vec<(String, usize)>,
+    /// Proposed decision of every student with an average, in merit order
+    pub decisions: Vec<(Student, DecisionOutcome)>,
+}
+
+impl CouncilReport {
+    /// Number of students per proposed decision
+    pub fn decision_counts(&self) -> Vec<(Decision, usize)> {
+        [Decision::Admitted, Decision::Repeat, Decision::Excluded]
+            .into_iter()
+            .map(|decision| {
+                let count = self.decisions.iter().filter(|(_, outcome)| outcome.decision == decision).count();
+                (decision, count)
+            })
+            .collect()
+    }
+}
+
+/// What a council report is built from
+pub struct CouncilData<'a> {
+    /// Averages of the students of the class over `scope`
+    pub averages: &'a [(Student, StudentAverage)],
+    /// Their averages over the previous term, to spot sharp drops
+    pub previous: Option<&'a [(Student, StudentAverage)]>,
+    /// Fall of the general average, in points, above which a drop is sharp
+    pub max_drop: f64,
+    pub scope: Scope,
+    /// Subjects of the class, whose teachers are given by subject code
+    pub subjects: &'a [Subject],
+    pub teachers: &'a HashMap<String, Teacher>,
+    /// Absences of the students over the period, by matricule
+    pub attendance: &'a HashMap<String, AttendanceTotals>,
+}
+
+fn subject_average(average: &StudentAverage, code: &str) -> Option<f64> {
+    average.subjects.iter().find(|s| s.subject_code == code)?.average
+}
+
+/// Build the council report of `class` over `period`. Students are at risk
+/// below the admission average of the `rules` or when their average fell
+/// by more than the allowed drop since the previous term.
+pub fn build_council_report(
+    class: &str,
+    period: &str,
+    data: &CouncilData,
+    rules: &GradingRules,
+    thresholds: &AttendanceThresholds,
+) -> CouncilReport {
+    let averages = data.averages;
+    let general: Vec<f64> = averages.iter().filter_map(|(_, a)| a.average).collect();
+
+    let subjects = data
+        .subjects
+        .iter()
+        .map(|subject| {
+            let values: Vec<f64> = averages.iter().filter_map(|(_, a)| subject_average(a, &subject.code)).collect();
+            CouncilSubject {
+                subject: subject.clone(),
+                teacher: data.teachers.get(&subject.code).map(|t| t.to_string()),
+                average: grading::mean(&values),
+                failing: values.iter().filter(|v| **v < rules.decisions.failing_subject_average).count(),
+            }
+        })
+        .collect();
+
+    let ranked: Vec<MeritEntry> = ranking::merit_list(averages, rules, data.scope)
+        .into_iter()
+        .filter(|entry| entry.rank.is_some())
+        .collect();
+    let top = ranked.iter().take(LISTED_STUDENTS).cloned().collect();
+    let bottom = ranked
+        .iter()
+        .skip(LISTED_STUDENTS.max(ranked.len().saturating_sub(LISTED_STUDENTS)))
+        .cloned()
+        .collect();
+
+    let mut at_risk: Vec<StudentAtRisk> = averages
+        .iter()
+        .filter_map(|(student, average)| {
+            let previous = data
+                .previous
+                .and_then(|previous| previous.iter().find(|(s, _)| s.matricule == student.matricule))
+                .and_then(|(_, a)| a.average);
+            let mut reasons = Vec::new();
+
+            if let Some(value) = average.average
+                && value < rules.decisions.admission_average
+            {
+                reasons.push(format!("moyenne générale inférieure à {:.2}", rules.decisions.admission_average));
+            }
+            if let (Some(value), Some(before)) = (average.average, previous)
+                && before - value > data.max_drop
+            {
+                reasons.push(format!("baisse de {:.2} points depuis la période précédente", before - value));
+            }
+
+            (!reasons.is_empty()).then(|| StudentAtRisk {
+                student: student.clone(),
+                average: average.average,
+                previous,
+                reasons,
+            })
+        })
+        .collect();
+    at_risk.sort_by(|a, b| a.average.unwrap_or(f64::MAX).total_cmp(&b.average.unwrap_or(f64::MAX)));
+
+    let mut attendance = AttendanceTotals::default();
+    let mut attendance_alerts = Vec::new();
+    for (student, _) in averages {
+        let totals = data.attendance.get(&student.matricule).copied().unwrap_or_default();
+        attendance.absence_hours += totals.absence_hours;
+        attendance.unjustified_hours += totals.unjustified_hours;
+        attendance.lateness += totals.lateness;
+        attendance.unjustified_lateness += totals.unjustified_lateness;
+
+        let reasons = thresholds.alerts(&totals);
+        if !reasons.is_empty() {
+            attendance_alerts.push((student.clone(), totals, reasons));
+        }
+    }
+
+    let mut mention_rules: Vec<_> = rules.mentions.iter().collect();
+    mention_rules.sort_by(|a, b| b.min_average.total_cmp(&a.min_average));
+    let mut mentions: Vec<(String, usize)> = mention_rules
+        .iter()
+        .map(|rule| {
+            let count = general.iter().filter(|v| rules.mention(**v) == Some(rule.label.as_str())).count();
+            (rule.label.clone(), count)
+        })
+        .collect();
+    let without = averages.len() - mentions.iter().map(|(_, count)| count).sum::<usize>();
+    if without > 0 {
+        mentions.push(("Aucune".to_string(), without));
+    }
+
+    let decisions = ranked
+        .iter()
+        .filter_map(|entry| {
+            let (_, average) = averages.iter().find(|(s, _)| s.matricule == entry.student.matricule)?;
+            Some((entry.student.clone(), rules.decide(average)?))
+        })
+        .collect();
+
+    CouncilReport {
+        class: class.to_string(),
+        period: period.to_string(),
+        headcount: averages.len(),
+        class_average: grading::mean(&general),
+        subjects,
+        top,
+        bottom,
+        at_risk,
+        attendance,
+        attendance_alerts,
+        mentions,
+        decisions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::SubjectAverage;
+    use crate::grading::rules::{DecisionRules, MentionRule};
+
+    fn entry(matricule: &str, average: Option<f64>) -> (Student, StudentAverage) {
+        (
+            Student {
+                matricule: matricule.into(),
+                name: matricule.into(),
+                surname: "Test".into(),
+                class: "10A".into(),
+            },
+            StudentAverage {
+                matricule: matricule.into(),
+                subjects: vec![SubjectAverage {
+                    subject_code: "MATH".into(),
+                    coefficient: 1,
+                    average,
+                    counted: true,
+                }],
+                total_coefficients: 1,
+                total_points: 0.0,
+                average,
+            },
+        )
+    }
+
+    fn rules() -> GradingRules {
+        GradingRules {
+            mentions: vec![
+                MentionRule { id: 1, label: "Passable".into(), min_average: 10.0 },
+                MentionRule { id: 2, label: "Bien".into(), min_average: 14.0 },
+            ],
+            decisions: DecisionRules::default(),
+        }
+    }
+
+    #[test]
+    fn test_council_report() {
+        let scores = [15.0, 12.0, 11.0, 10.5, 9.0, 8.0, 6.0];
+        let mut averages: Vec<_> = scores
+            .iter()
+            .enumerate()
+            .map(|(index, score)| entry(&format!("S{}", index), Some(*score)))
+            .collect();
+        averages.push(entry("S7", None));
+        let previous = vec![entry("S2", Some(14.0)), entry("S3", Some(12.0))];
+
+        let subjects = vec![Subject { code: "MATH".into(), name: "Maths".into(), class: "10A".into(), coefficient: 1 }];
+        let attendance = HashMap::from([(
+            "S5".to_string(),
+            AttendanceTotals { absence_hours: 14, unjustified_hours: 12, lateness: 1, unjustified_lateness: 0 },
+        )]);
+        let data = CouncilData {
+            averages: &averages,
+            previous: Some(&previous),
+            max_drop: 2.0,
+            scope: Scope::Period(2),
+            subjects: &subjects,
+            teachers: &HashMap::new(),
+            attendance: &attendance,
+        };
+        let report = build_council_report("10A", "2e trimestre", &data, &rules(), &AttendanceThresholds::default());
+
+        assert_eq!(report.headcount, 8);
+        assert_eq!(report.subjects[0].failing, 3);
+        assert_eq!(report.top.len(), 3);
+        assert_eq!(report.top[0].student.matricule, "S0");
+        let bottom: Vec<&str> = report.bottom.iter().map(|e| e.student.matricule.as_str()).collect();
+        assert_eq!(bottom, vec!["S4", "S5", "S6"]);
+
+        // Below the admission average, then the sharp drop of S2 (14 → 11)
+        let at_risk: Vec<&str> = report.at_risk.iter().map(|s| s.student.matricule.as_str()).collect();
+        assert_eq!(at_risk, vec!["S6", "S5", "S4", "S2"]);
+        assert_eq!(report.at_risk[3].previous, Some(14.0));
+
+        assert_eq!(report.attendance.absence_hours, 14);
+        assert_eq!(report.attendance_alerts.len(), 1);
+        assert_eq!(
+            report.mentions,
+            vec![("Bien".to_string(), 1), ("Passable".to_string(), 3), ("Aucune".to_string(), 4)]
+        );
+        assert_eq!(
+            report.decision_counts(),
+            vec![(Decision::Admitted, 4), (Decision::Repeat, 2), (Decision::Excluded, 1)]
+        );
+    }
+
+    #[test]
+    fn test_small_class_lists_each_student_once() {
+        let averages = vec![entry("S0", Some(12.0)), entry("S1", Some(11.0)), entry("S2", Some(10.0)), entry("S3", Some(9.0))];
+        let data = CouncilData {
+            averages: &averages,
+            previous: None,
+            max_drop: 2.0,
+            scope: Scope::Period(1),
+            subjects: &[],
+            teachers: &HashMap::new(),
+            attendance: &HashMap::new(),
+        };
+        let report = build_council_report("10A", "1er trimestre", &data, &rules(), &AttendanceThresholds::default());
+
+        assert_eq!(report.top.len(), 3);
+        assert_eq!(report.bottom.len(), 1);
+        assert_eq!(report.bottom[0].student.matricule, "S3");
+    }
+}
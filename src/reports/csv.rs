@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use crate::db::repositories::profile_repository::{Guardian, StudentProfile};
+use crate::db::repositories::student_repository::Student;
+use crate::db::repositories::subject_repository::Subject;
+use crate::grading::comparison::SubjectComparison;
+use crate::grading::ranking::MeritEntry;
+use crate::grading::warnings::EarlyWarning;
+use crate::grading::stats::{bucket_label, NoteSummary, BUCKETS};
+
+/// Field separator, as expected by spreadsheets in French locales
+const SEPARATOR: char = ';';
+
+/// Quote a field when it contains a separator, a quote or a line break
+pub fn escape_field(field: &str) -> String {
+    if field.contains([SEPARATOR, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Join fields into a CSV line
+pub fn line(fields: &[String]) -> String {
+    let escaped: Vec<String> = fields.iter().map(|f| escape_field(f)).collect();
+    escaped.join(&SEPARATOR.to_string()) + "\n"
+}
+
+/// Split a CSV line into its fields, unquoting them
+pub fn split_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches(['\r', '\n']).chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            SEPARATOR if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+/// Merit list as CSV: rank, student identity, average, mention and decision
+pub fn merit_list(entries: &[MeritEntry]) -> String {
+    let mut csv = line(&[
+        "Rang".into(),
+        "Matricule".into(),
+        "Nom".into(),
+        "Prénom".into(),
+        "Classe".into(),
+        "Moyenne".into(),
+        "Mention".into(),
+        "Décision".into(),
+    ]);
+
+    for entry in entries {
+        csv.push_str(&line(&[
+            entry.rank.map(|r| r.to_string()).unwrap_or_default(),
+            entry.student.matricule.clone(),
+            entry.student.name.clone(),
+            entry.student.surname.clone(),
+            entry.student.class.clone(),
+            entry.average.map(|a| format!("{:.2}", a)).unwrap_or_default(),
+            entry.mention.clone().unwrap_or_default(),
+            entry.decision.map(|d| d.to_string()).unwrap_or_default(),
+        ]));
+    }
+
+    csv
+}
+
+/// Comparison of groups of notes as CSV, one line per subject and group
+pub fn comparisons(comparisons: &[SubjectComparison], group_header: &str) -> String {
+    let mut csv = line(&[
+        "Matière".into(),
+        group_header.into(),
+        "Notes".into(),
+        "Moyenne".into(),
+        "Médiane".into(),
+        "Écart-type".into(),
+        "Réussite (%)".into(),
+        "Écart aux autres".into(),
+        "Z".into(),
+        "Notation".into(),
+    ]);
+
+    for comparison in comparisons {
+        for group in &comparison.groups {
+            csv.push_str(&line(&[
+                comparison.subject.clone(),
+                group.label.clone(),
+                group.summary.count.to_string(),
+                format!("{:.2}", group.summary.mean),
+                format!("{:.2}", group.summary.median),
+                format!("{:.2}", group.summary.std_dev),
+                format!("{:.2}", group.summary.pass_rate),
+                group.gap.map(|gap| format!("{:.2}", gap)).unwrap_or_default(),
+                group.z_score.map(|z| format!("{:.2}", z)).unwrap_or_default(),
+                group.severity.map(|s| s.to_string()).unwrap_or_default(),
+            ]));
+        }
+    }
+
+    csv
+}
+
+/// Early warnings as CSV, in priority order, the reasons separated by " ; "
+pub fn early_warnings(warnings: &[EarlyWarning]) -> String {
+    let mut csv = line(&[
+        "Priorité".into(),
+        "Matricule".into(),
+        "Nom".into(),
+        "Prénom".into(),
+        "Classe".into(),
+        "Moyenne".into(),
+        "Motifs".into(),
+    ]);
+
+    for warning in warnings {
+        let reasons: Vec<String> = warning.reasons.iter().map(|r| r.to_string()).collect();
+        csv.push_str(&line(&[
+            warning.priority_label().to_string(),
+            warning.student.matricule.clone(),
+            warning.student.name.clone(),
+            warning.student.surname.clone(),
+            warning.student.class.clone(),
+            warning.average.map(|a| format!("{:.2}", a)).unwrap_or_default(),
+            reasons.join(" ; "),
+        ]));
+    }
+
+    csv
+}
+
+/// Statistics of each subject as CSV, with one column per distribution bucket
+pub fn subject_statistics(statistics: &[(Subject, Option<NoteSummary>)]) -> String {
+    let mut header: Vec<String> = [
+        "Code", "Matière", "Classe", "Notes", "Moyenne", "Médiane", "Écart-type", "Min", "Q1", "Q3", "Max", "Réussite (%)",
+    ]
+    .map(String::from)
+    .to_vec();
+    header.extend((0..BUCKETS).map(bucket_label));
+    let mut csv = line(&header);
+
+    for (subject, summary) in statistics {
+        let mut fields = vec![subject.code.clone(), subject.name.clone(), subject.class.clone()];
+        match summary {
+            Some(summary) => {
+                fields.push(summary.count.to_string());
+                fields.extend(
+                    [
+                        summary.mean,
+                        summary.median,
+                        summary.std_dev,
+                        summary.min,
+                        summary.q1,
+                        summary.q3,
+                        summary.max,
+                        summary.pass_rate,
+                    ]
+                    .map(|value| format!("{:.2}", value)),
+                );
+                fields.extend(summary.buckets.map(|count| count.to_string()));
+            }
+            None => {
+                fields.push("0".to_string());
+                fields.resize(header.len(), String::new());
+            }
+        }
+        csv.push_str(&line(&fields));
+    }
+
+    csv
+}
+
+/// Class list as CSV: student identity, profile and guardians, the profiles
+/// and guardians being given by matricule
+pub fn class_list(
+    students: &[Student],
+    profiles: &HashMap<String, StudentProfile>,
+    guardians: &HashMap<String, Vec<Guardian>>,
+) -> String {
+    let mut csv = line(&[
+        "Matricule".into(),
+        "Nom".into(),
+        "Prénom".into(),
+        "Classe".into(),
+        "Sexe".into(),
+        "Date de naissance".into(),
+        "Lieu de naissance".into(),
+        "Adresse".into(),
+        "Photo".into(),
+        "Responsables".into(),
+    ]);
+
+    for student in students {
+        let profile = profiles.get(&student.matricule);
+        let field = |value: Option<&Option<String>>| value.cloned().flatten().unwrap_or_default();
+        let student_guardians: Vec<String> = guardians
+            .get(&student.matricule)
+            .map(|list| list.iter().map(Guardian::label).collect())
+            .unwrap_or_default();
+
+        csv.push_str(&line(&[
+            student.matricule.clone(),
+            student.name.clone(),
+            student.surname.clone(),
+            student.class.clone(),
+            profile.and_then(|p| p.gender).map(|g| g.as_str().to_string()).unwrap_or_default(),
+            field(profile.map(|p| &p.birth_date)),
+            field(profile.map(|p| &p.birth_place)),
+            field(profile.map(|p| &p.address)),
+            field(profile.map(|p| &p.photo_path)),
+            student_guardians.join(" | "),
+        ]));
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("Martin"), "Martin");
+        assert_eq!(escape_field("a;b"), "\"a;b\"");
+        assert_eq!(escape_field("dit \"Jo\""), "\"dit \"\"Jo\"\"\"");
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(line(&["1".into(), "x;y".into()]), "1;\"x;y\"\n");
+    }
+
+    #[test]
+    fn test_split_line() {
+        let fields = vec!["1".to_string(), "x;y".into(), "dit \"Jo\"".into(), String::new()];
+        assert_eq!(split_line(&line(&fields)), fields);
+        assert_eq!(split_line("a;b\r\n"), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_subject_statistics() {
+        let subject = |code: &str| Subject { code: code.into(), name: "Maths".into(), class: "10A".into(), coefficient: 4 };
+        let statistics = [
+            (subject("MATH101"), crate::grading::stats::summarize(&[8.0, 12.0])),
+            (subject("MATH102"), None),
+        ];
+
+        let csv = subject_statistics(&statistics);
+        let rows: Vec<Vec<String>> = csv.lines().map(split_line).collect();
+        assert_eq!(rows[0].len(), 22);
+        assert_eq!(rows[0][12], "0-2");
+        assert_eq!(rows[1][4..7], ["10.00", "10.00", "2.00"]);
+        assert_eq!(rows[1][11], "50.00");
+        assert_eq!(rows[1][16], "1");
+        assert_eq!(rows[2].len(), 22);
+        assert_eq!(rows[2][3], "0");
+    }
+
+    #[test]
+    fn test_class_list() {
+        let student = Student {
+            matricule: "S001".into(),
+            name: "Alice".into(),
+            surname: "Smith".into(),
+            class: "10A".into(),
+        };
+        let mut profile = StudentProfile::new("S001");
+        profile.birth_date = Some("2010-05-14".into());
+        let guardian = Guardian {
+            id: 1,
+            matricule: "S001".into(),
+            name: "Marie Smith".into(),
+            relation: "mère".into(),
+            phone: Some("699 00 11 22".into()),
+            email: None,
+        };
+        let profiles = HashMap::from([("S001".to_string(), profile)]);
+        let guardians = HashMap::from([("S001".to_string(), vec![guardian])]);
+
+        let csv = class_list(&[student], &profiles, &guardians);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], "S001;Alice;Smith;10A;;2010-05-14;;;;Marie Smith (mère) — 699 00 11 22");
+    }
+}
@@ -0,0 +1,484 @@
+use std::fmt::Write;
+use crate::db::repositories::note_repository::Note;
+use crate::db::repositories::subject_repository::Subject;
+use crate::grading::stats::{self, bucket_label, BUCKETS};
+use crate::grading::ranking::MeritEntry;
+use crate::reports::bulletin::Bulletin;
+use crate::reports::council::CouncilReport;
+use crate::reports::format_value;
+
+const STYLE: &str = "
+body { font-family: Helvetica, Arial, sans-serif; margin: 2em; color: #222; }
+h1 { border-bottom: 2px solid #2c5282; padding-bottom: .3em; }
+h2 { color: #2c5282; margin-top: 2em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: .3em .7em; text-align: left; }
+th { background: #edf2f7; }
+td.num { text-align: right; }
+nav a { margin-right: 1em; }
+.bulletin { page-break-before: always; border-top: 1px dashed #999; }
+.chart { display: inline-block; margin: 0 1em 1em 0; }
+.chart figcaption { font-size: .85em; text-align: center; }
+.comment { border: 1px solid #999; height: 5em; }
+";
+
+/// Escape text for inclusion in HTML
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Anchor id of a student's bulletin. Bytes other than ASCII letters,
+/// digits and '-' are written as '_' followed by their hex code, so two
+/// matricules never share an anchor.
+fn anchor(matricule: &str) -> String {
+    let mut id = String::from("bulletin-");
+    for byte in matricule.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' {
+            id.push(byte as char);
+        } else {
+            let _ = write!(id, "_{:02X}", byte);
+        }
+    }
+    id
+}
+
+/// Inline SVG bar chart of a grade distribution
+fn distribution_chart(buckets: &[usize; BUCKETS]) -> String {
+    let width = 240.0;
+    let height = 100.0;
+    let bar_width = width / BUCKETS as f64;
+    let highest = *buckets.iter().max().unwrap_or(&0) as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        width,
+        height + 15.0,
+        width,
+        height + 15.0
+    );
+
+    for (index, count) in buckets.iter().enumerate() {
+        let bar_height = if highest > 0.0 { *count as f64 / highest * height } else { 0.0 };
+        let x = index as f64 * bar_width;
+        let colour = if index * 2 >= 10 { "#38a169" } else { "#e53e3e" };
+        let _ = write!(
+            svg,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{} : {}</title></rect>",
+            x + 1.0,
+            height - bar_height,
+            bar_width - 2.0,
+            bar_height,
+            colour,
+            bucket_label(index),
+            count
+        );
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"9\" text-anchor=\"middle\">{}</text>",
+            x + bar_width / 2.0,
+            height + 12.0,
+            index * 2
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn decision_label(b: &Bulletin) -> String {
+    b.decision
+        .as_ref()
+        .map(|d| d.decision.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn write_student_list(html: &mut String, bulletins: &[Bulletin]) {
+    html.push_str("<h2 id=\"etudiants\">Liste des étudiants</h2>\n<table>\n");
+    html.push_str(
+        "<tr><th>Matricule</th><th>Nom</th><th>Prénom</th><th>Moyenne</th><th>Rang</th>\
+         <th>Mention</th><th>Décision</th></tr>\n",
+    );
+
+    for b in bulletins {
+        let rank = b.rank.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string());
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"#{}\">{}</a></td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td>\
+             <td>{}</td><td>{}</td></tr>",
+            anchor(&b.student.matricule),
+            escape(&b.student.matricule),
+            escape(&b.student.name),
+            escape(&b.student.surname),
+            format_value(b.weighted_average),
+            escape(&rank),
+            escape(b.mention.as_deref().unwrap_or("-")),
+            decision_label(b)
+        );
+    }
+
+    html.push_str("</table>\n");
+}
+
+fn write_subject_statistics(html: &mut String, subjects: &[Subject], notes: &[Note]) {
+    html.push_str("<h2 id=\"statistiques\">Statistiques par matière</h2>\n<table>\n");
+    html.push_str(
+        "<tr><th>Code</th><th>Matière</th><th>Coef.</th><th>Notes</th><th>Moyenne</th><th>Médiane</th>\
+         <th>Écart-type</th><th>Min</th><th>Max</th><th>Réussite (≥ 10)</th></tr>\n",
+    );
+
+    let mut charts = String::new();
+
+    for (subject, summary) in stats::by_subject(subjects.to_vec(), notes) {
+        match summary {
+            Some(summary) => {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+                     <td class=\"num\">{:.2}</td><td class=\"num\">{:.2}</td><td class=\"num\">{:.2}</td>\
+                     <td class=\"num\">{:.2}</td><td class=\"num\">{:.2}</td><td class=\"num\">{:.0} %</td></tr>",
+                    escape(&subject.code),
+                    escape(&subject.name),
+                    subject.coefficient,
+                    summary.count,
+                    summary.mean,
+                    summary.median,
+                    summary.std_dev,
+                    summary.min,
+                    summary.max,
+                    summary.pass_rate
+                );
+                let _ = writeln!(
+                    charts,
+                    "<figure class=\"chart\">{}<figcaption>{}</figcaption></figure>",
+                    distribution_chart(&summary.buckets),
+                    escape(&subject.name)
+                );
+            }
+            None => {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">0</td>\
+                     <td>-</td><td>-</td><td>-</td><td>-</td><td>-</td><td>-</td></tr>",
+                    escape(&subject.code),
+                    escape(&subject.name),
+                    subject.coefficient
+                );
+            }
+        }
+    }
+
+    html.push_str("</table>\n<h2 id=\"distributions\">Distribution des notes</h2>\n");
+    html.push_str(&charts);
+}
+
+fn write_bulletin(html: &mut String, b: &Bulletin) {
+    let student = &b.student;
+    let _ = writeln!(
+        html,
+        "<section class=\"bulletin\" id=\"{}\">\n<h2>Bulletin de {} {}</h2>",
+        anchor(&student.matricule),
+        escape(&student.name),
+        escape(&student.surname)
+    );
+    let _ = writeln!(
+        html,
+        "<p>Matricule : <strong>{}</strong> — Classe : <strong>{}</strong></p>",
+        escape(&student.matricule),
+        escape(&student.class)
+    );
+    if let Some(profile) = &b.profile {
+        let birth = profile.birth_label().unwrap_or_else(|| "-".to_string());
+        let gender = profile.gender.map(|g| g.to_string()).unwrap_or_else(|| "-".to_string());
+        let _ = writeln!(html, "<p>Naissance : {} — Sexe : {}</p>", escape(&birth), gender);
+    }
+    if !b.guardians.is_empty() {
+        let guardians: Vec<String> = b.guardians.iter().map(|g| g.label()).collect();
+        let _ = writeln!(html, "<p>Responsables : {}</p>", escape(&guardians.join(" ; ")));
+    }
+
+    html.push_str(
+        "<table>\n<tr><th>Matière</th><th>Enseignant</th><th>Code</th><th>Moyenne</th><th>Coef.</th><th>Points</th></tr>\n",
+    );
+    for line in &b.lines {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            escape(&line.subject_name),
+            escape(line.teacher.as_deref().unwrap_or("-")),
+            escape(&line.subject_code),
+            format_value(line.average),
+            line.coefficient,
+            format_value(line.points)
+        );
+    }
+    html.push_str("</table>\n");
+
+    let rank = b.rank.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string());
+    let _ = writeln!(
+        html,
+        "<p>Moyenne générale : <strong>{}</strong> — Rang : <strong>{}</strong> — Mention : <strong>{}</strong></p>",
+        format_value(b.weighted_average),
+        escape(&rank),
+        escape(b.mention.as_deref().unwrap_or("-"))
+    );
+    if let Some(outcome) = &b.decision {
+        let _ = write!(html, "<p>Décision : <strong>{}</strong>", outcome.decision);
+        if !outcome.reasons.is_empty() {
+            let _ = write!(html, " ({})", escape(&outcome.reasons.join(", ")));
+        }
+        html.push_str("</p>\n");
+    }
+    let _ = writeln!(html, "<p>Assiduité : {}</p>", escape(&b.attendance.to_string()));
+    html.push_str("<p>Appréciation du professeur principal :</p>\n<div class=\"comment\"></div>\n");
+    html.push_str("<p><a href=\"#etudiants\">Retour à la liste</a></p>\n</section>\n");
+}
+
+/// Build a self-contained HTML report for a class over `period`: student
+/// list, per-subject statistics with distribution charts and every bulletin.
+/// `notes` are the notes of the period, `bulletins` those of the students.
+pub fn class_report(
+    class: &str,
+    period: &str,
+    bulletins: &[Bulletin],
+    subjects: &[Subject],
+    notes: &[Note],
+) -> String {
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"fr\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Nschool - Classe {}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        escape(class),
+        STYLE
+    );
+    let _ = writeln!(html, "<h1>Classe {} — {}</h1>", escape(class), escape(period));
+    let _ = writeln!(
+        html,
+        "<p>{} étudiants — {} matières — {} notes</p>",
+        bulletins.len(),
+        subjects.len(),
+        notes.len()
+    );
+    html.push_str(
+        "<nav><a href=\"#etudiants\">Étudiants</a><a href=\"#statistiques\">Statistiques</a>\
+         <a href=\"#distributions\">Distributions</a></nav>\n",
+    );
+
+    write_student_list(&mut html, bulletins);
+    write_subject_statistics(&mut html, subjects, notes);
+    for b in bulletins {
+        write_bulletin(&mut html, b);
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn write_merit_entries(html: &mut String, title: &str, entries: &[MeritEntry]) {
+    let _ = writeln!(html, "<h2>{}</h2>\n<table>", escape(title));
+    html.push_str("<tr><th>Rang</th><th>Matricule</th><th>Nom</th><th>Prénom</th><th>Moyenne</th><th>Mention</th></tr>\n");
+    for entry in entries {
+        let rank = entry.rank.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string());
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            escape(&rank),
+            escape(&entry.student.matricule),
+            escape(&entry.student.name),
+            escape(&entry.student.surname),
+            format_value(entry.average),
+            escape(entry.mention.as_deref().unwrap_or("-"))
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+/// Build a self-contained HTML document of a class council report, to be
+/// printed or shared before the council
+pub fn council_report(report: &CouncilReport) -> String {
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"fr\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Nschool - Conseil de classe {}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        escape(&report.class),
+        STYLE
+    );
+    let _ = writeln!(
+        html,
+        "<h1>Conseil de classe — {} — {}</h1>",
+        escape(&report.class),
+        escape(&report.period)
+    );
+    let _ = writeln!(
+        html,
+        "<p>{} étudiants — moyenne de la classe : <strong>{}</strong></p>",
+        report.headcount,
+        format_value(report.class_average)
+    );
+
+    html.push_str("<h2>Moyennes par matière</h2>\n<table>\n");
+    html.push_str("<tr><th>Matière</th><th>Code</th><th>Enseignant</th><th>Coef.</th><th>Moyenne</th><th>En échec</th></tr>\n");
+    for line in &report.subjects {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            escape(&line.subject.name),
+            escape(&line.subject.code),
+            escape(line.teacher.as_deref().unwrap_or("-")),
+            line.subject.coefficient,
+            format_value(line.average),
+            line.failing
+        );
+    }
+    html.push_str("</table>\n");
+
+    write_merit_entries(&mut html, "Tête de classe", &report.top);
+    write_merit_entries(&mut html, "Fin de classe", &report.bottom);
+
+    html.push_str("<h2>Étudiants en difficulté</h2>\n");
+    if report.at_risk.is_empty() {
+        html.push_str("<p>Aucun étudiant en difficulté.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Matricule</th><th>Nom</th><th>Prénom</th><th>Moyenne</th><th>Période précédente</th><th>Motifs</th></tr>\n");
+        for line in &report.at_risk {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                escape(&line.student.matricule),
+                escape(&line.student.name),
+                escape(&line.student.surname),
+                format_value(line.average),
+                format_value(line.previous),
+                escape(&line.reasons.join(", "))
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Assiduité</h2>\n");
+    let _ = writeln!(html, "<p>Classe : {}</p>", escape(&report.attendance.to_string()));
+    if !report.attendance_alerts.is_empty() {
+        html.push_str("<table>\n<tr><th>Matricule</th><th>Nom</th><th>Prénom</th><th>Absences</th><th>Retards</th><th>Alertes</th></tr>\n");
+        for (student, totals, reasons) in &report.attendance_alerts {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{} h</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                escape(&student.matricule),
+                escape(&student.name),
+                escape(&student.surname),
+                totals.absence_hours,
+                totals.lateness,
+                escape(&reasons.join(", "))
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Mentions</h2>\n<table>\n<tr><th>Mention</th><th>Étudiants</th></tr>\n");
+    for (mention, count) in &report.mentions {
+        let _ = writeln!(html, "<tr><td>{}</td><td class=\"num\">{}</td></tr>", escape(mention), count);
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Décisions proposées</h2>\n<p>");
+    let counts: Vec<String> = report
+        .decision_counts()
+        .iter()
+        .map(|(decision, count)| format!("{} : {}", decision, count))
+        .collect();
+    html.push_str(&counts.join(" — "));
+    html.push_str("</p>\n<table>\n<tr><th>Matricule</th><th>Nom</th><th>Prénom</th><th>Décision</th><th>Motifs</th></tr>\n");
+    for (student, outcome) in &report.decisions {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&student.matricule),
+            escape(&student.name),
+            escape(&student.surname),
+            outcome.decision,
+            escape(&outcome.reasons.join(", "))
+        );
+    }
+    html.push_str("</table>\n<h2>Observations du conseil</h2>\n<div class=\"comment\"></div>\n");
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::db::repositories::student_repository::Student;
+    use crate::grading::{self, GradingPolicy, Scope};
+    use crate::grading::weights::NoteWeights;
+    use crate::grading::rules::{DecisionRules, GradingRules};
+    use crate::reports::bulletin;
+
+    fn sample() -> (Vec<Student>, Vec<Subject>, Vec<Note>) {
+        let students = vec![Student {
+            matricule: "S 001".into(),
+            name: "Alice <b>".into(),
+            surname: "Smith".into(),
+            class: "10A".into(),
+        }];
+        let subjects = vec![Subject {
+            code: "MATH101".into(),
+            name: "Mathematics".into(),
+            class: "10A".into(),
+            coefficient: 4,
+        }];
+        let notes = vec![
+            Note { id: 1, value: 8.0, matricule: "S 001".into(), subject_code: "MATH101".into(), period_id: Some(1), evaluation_id: None },
+            Note { id: 2, value: 20.0, matricule: "S 001".into(), subject_code: "MATH101".into(), period_id: Some(1), evaluation_id: None },
+        ];
+        (students, subjects, notes)
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("<a href=\"x\">&'"), "&lt;a href=&quot;x&quot;&gt;&amp;&#39;");
+    }
+
+    #[test]
+    fn test_anchors_do_not_collide() {
+        assert_eq!(anchor("STU-6-001"), "bulletin-STU-6-001");
+        assert_ne!(anchor("S 001"), anchor("S_001"));
+        assert_ne!(anchor("S_20001"), anchor("S 001"));
+        assert_eq!(anchor("É1"), "bulletin-_C3_891");
+    }
+
+    #[test]
+    fn test_class_report_is_self_contained() {
+        let (students, subjects, notes) = sample();
+        let rules = GradingRules { mentions: vec![], decisions: DecisionRules::default() };
+        let averages = grading::class_averages(&students, &subjects, &notes, &GradingPolicy::default(), &NoteWeights::default());
+        let averages: Vec<_> = students.into_iter().zip(averages).collect();
+        let period = "1er trimestre 2025-2026";
+        let bulletins = bulletin::build_class_bulletins(&averages, &subjects, &HashMap::new(), &HashMap::new(), &rules, Scope::Period(1), period);
+        let html = class_report("10A", period, &bulletins, &subjects, &notes);
+
+        assert!(html.contains("id=\"bulletin-S_20001\""));
+        assert!(html.contains("href=\"#bulletin-S_20001\""));
+        assert!(html.contains("Alice &lt;b&gt;"));
+        assert!(html.contains("1er trimestre 2025-2026"));
+        assert!(html.contains("<svg"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("src=\"http"));
+    }
+}
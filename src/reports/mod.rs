@@ -0,0 +1,41 @@
+pub mod pdf;
+pub mod bulletin;
+pub mod html;
+pub mod csv;
+pub mod council;
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Directory where generated reports are written
+pub const EXPORT_DIR: &str = "exports";
+
+/// Build the path of an export file, creating the export directory if needed
+pub fn export_path(file_name: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(EXPORT_DIR)?;
+    Ok(PathBuf::from(EXPORT_DIR).join(sanitize_file_name(file_name)))
+}
+
+/// Value with two decimals, "-" when there is none
+pub fn format_value(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string())
+}
+
+/// Replace characters that are unsafe in file names
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("bulletins_6ème.pdf"), "bulletins_6ème.pdf");
+        assert_eq!(sanitize_file_name("../a b/c.pdf"), ".._a_b_c.pdf");
+    }
+}
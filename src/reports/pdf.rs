@@ -0,0 +1,256 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A4 page size in PDF points
+pub const PAGE_WIDTH: f64 = 595.0;
+pub const PAGE_HEIGHT: f64 = 842.0;
+
+/// Built-in PDF fonts (no embedding required)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// A single page, stored as a PDF content stream
+#[derive(Debug, Default)]
+pub struct Page {
+    content: Vec<u8>,
+}
+
+impl Page {
+    /// Write `text` with its baseline starting at (x, y)
+    pub fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        self.content.extend_from_slice(
+            format!("BT /{} {} Tf {:.2} {:.2} Td (", font.resource_name(), size, x, y).as_bytes(),
+        );
+        self.content.extend_from_slice(&encode_text(text));
+        self.content.extend_from_slice(b") Tj ET\n");
+    }
+
+    /// Draw a straight line from (x1, y1) to (x2, y2)
+    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.content.extend_from_slice(
+            format!("0.5 w {:.2} {:.2} m {:.2} {:.2} l S\n", x1, y1, x2, y2).as_bytes(),
+        );
+    }
+
+    /// Draw the outline of a rectangle whose lower-left corner is (x, y)
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.content.extend_from_slice(
+            format!("0.5 w {:.2} {:.2} {:.2} {:.2} re S\n", x, y, width, height).as_bytes(),
+        );
+    }
+
+    /// Fill a rectangle with a grey level (0 = black, 1 = white)
+    pub fn fill_rect(&mut self, x: f64, y: f64, width: f64, height: f64, grey: f64) {
+        self.content.extend_from_slice(
+            format!("q {:.2} g {:.2} {:.2} {:.2} {:.2} re f Q\n", grey, x, y, width, height).as_bytes(),
+        );
+    }
+}
+
+/// Minimal PDF writer: A4 pages, Helvetica text, lines and rectangles
+#[derive(Debug, Default)]
+pub struct PdfDocument {
+    pages: Vec<Page>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a blank page and return it for drawing
+    pub fn add_page(&mut self) -> &mut Page {
+        self.pages.push(Page::default());
+        self.pages.last_mut().unwrap()
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Serialize the document to PDF 1.4 bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Object layout:
+        // 1 = catalog, 2 = page tree, 3/4 = fonts,
+        // then for each page: page object, content stream
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 5 + i * 2).collect();
+
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+
+        let kids = page_ids
+            .iter()
+            .map(|id| format!("{} 0 R", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        objects.push(
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids, self.pages.len()).into_bytes(),
+        );
+
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+
+        for (page, page_id) in self.pages.iter().zip(&page_ids) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    page_id + 1
+                )
+                .into_bytes(),
+            );
+
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.content.len()).into_bytes();
+            stream.extend_from_slice(&page.content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+        out.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+
+        out
+    }
+
+    /// Write the document to `path`
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+}
+
+/// Encode text for a PDF string literal using WinAnsiEncoding.
+/// Characters outside the encoding are replaced by '?'.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '\u{A0}'..='\u{FF}' => c as u32 as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            'ƒ' => 0x83,
+            '„' => 0x84,
+            '…' => 0x85,
+            '†' => 0x86,
+            '‡' => 0x87,
+            'ˆ' => 0x88,
+            '‰' => 0x89,
+            'Š' => 0x8A,
+            '‹' => 0x8B,
+            'Œ' => 0x8C,
+            'Ž' => 0x8E,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '˜' => 0x98,
+            '™' => 0x99,
+            'š' => 0x9A,
+            '›' => 0x9B,
+            'œ' => 0x9C,
+            'ž' => 0x9E,
+            'Ÿ' => 0x9F,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let mut doc = PdfDocument::new();
+        doc.add_page().text(50.0, 800.0, 12.0, Font::Regular, "Bulletin");
+        doc.add_page().text(50.0, 800.0, 12.0, Font::Bold, "Page 2");
+
+        let bytes = doc.to_bytes();
+        assert!(bytes.starts_with(b"%PDF-1.4"));
+        assert!(bytes.ends_with(b"%%EOF\n"));
+        assert!(find(&bytes, b"/Count 2").is_some());
+        assert_eq!(doc.page_count(), 2);
+    }
+
+    #[test]
+    fn test_xref_offsets_point_to_objects() {
+        let mut doc = PdfDocument::new();
+        doc.add_page().rect(10.0, 10.0, 100.0, 50.0);
+
+        let bytes = doc.to_bytes();
+        let xref = find(&bytes, b"xref\n").unwrap();
+        let table = String::from_utf8_lossy(&bytes[xref..]).to_string();
+
+        for (index, line) in table.lines().skip(3).take(6).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            let header = format!("{} 0 obj", index + 1);
+            assert!(bytes[offset..].starts_with(header.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_text_encoding() {
+        assert_eq!(encode_text("é"), vec![0xE9]);
+        assert_eq!(encode_text("a(b)"), b"a\\(b\\)".to_vec());
+        assert_eq!(encode_text("‰"), vec![0x89]);
+        assert_eq!(encode_text("✓"), b"?".to_vec());
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+use crate::db::repositories::student_repository::Student;
+use crate::reports::csv;
+
+/// A student read from an import file, with the line they come from
+#[derive(Debug, Clone)]
+pub struct ImportRow {
+    pub line: usize,
+    pub student: Student,
+}
+
+/// Why an import file cannot be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportError {
+    Empty,
+    MissingColumn(&'static str),
+    FieldCount { line: usize, expected: usize, found: usize },
+    MissingValue { line: usize, column: &'static str },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty =>
+                write!(f, "le fichier est vide"),
+            Self::MissingColumn(column) =>
+                write!(f, "colonne « {column} » absente de la première ligne"),
+            Self::FieldCount { line, expected, found } =>
+                write!(f, "ligne {line} : {found} champs au lieu de {expected}"),
+            Self::MissingValue { line, column } =>
+                write!(f, "ligne {line} : « {column} » est vide"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Position of `column` in the header, accents and case ignored
+fn column_index(header: &[String], column: &'static str) -> Option<usize> {
+    let normalize = |name: &str| name.trim().to_lowercase().replace('é', "e");
+    header.iter().position(|name| normalize(name) == normalize(column))
+}
+
+/// Read students from a CSV file whose first line names the columns
+/// "Matricule", "Nom", "Prénom" and "Classe", in any order. The matricule
+/// column is optional and an empty matricule is to be generated.
+pub fn parse_students(content: &str) -> Result<Vec<ImportRow>, ImportError> {
+    let mut lines = content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines.next().ok_or(ImportError::Empty)?;
+    let header = csv::split_line(header);
+    let required = |column| column_index(&header, column).ok_or(ImportError::MissingColumn(column));
+    let matricule = column_index(&header, "Matricule");
+    let name = required("Nom")?;
+    let surname = required("Prénom")?;
+    let class = required("Classe")?;
+
+    lines
+        .map(|(line, text)| {
+            let fields = csv::split_line(text);
+            if fields.len() != header.len() {
+                return Err(ImportError::FieldCount { line, expected: header.len(), found: fields.len() });
+            }
+            let value = |index: usize, column| match fields[index].trim() {
+                "" => Err(ImportError::MissingValue { line, column }),
+                value => Ok(value.to_string()),
+            };
+
+            Ok(ImportRow {
+                line,
+                student: Student {
+                    matricule: matricule.map(|index| fields[index].trim().to_string()).unwrap_or_default(),
+                    name: value(name, "Nom")?,
+                    surname: value(surname, "Prénom")?,
+                    class: value(class, "Classe")?,
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_students() {
+        let content = "\u{feff}Nom;Prenom;Classe;Matricule\r\nMartin;Lucas;6A;\r\n\r\n\"Le Goff\";Anne;6A;STU-6-010\r\n";
+        let rows = parse_students(content).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].student.matricule, "");
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(rows[1].student.name, "Le Goff");
+        assert_eq!(rows[1].student.matricule, "STU-6-010");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_students("").unwrap_err(), ImportError::Empty);
+        assert_eq!(parse_students("Nom;Classe\n").unwrap_err(), ImportError::MissingColumn("Prénom"));
+        assert_eq!(
+            parse_students("Nom;Prénom;Classe\nMartin;Lucas\n").unwrap_err(),
+            ImportError::FieldCount { line: 2, expected: 3, found: 2 }
+        );
+        assert_eq!(
+            parse_students("Nom;Prénom;Classe\nMartin; ;6A\n").unwrap_err(),
+            ImportError::MissingValue { line: 2, column: "Prénom" }
+        );
+    }
+}
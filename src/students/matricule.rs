@@ -0,0 +1,251 @@
+use std::fmt;
+
+/// Setting key holding the pattern of the generated matricules
+pub const MATRICULE_PATTERN_SETTING: &str = "students.matricule_pattern";
+
+/// Pattern of the matricules of the seeded students, e.g. "STU-6-001"
+pub const DEFAULT_PATTERN: &str = "STU-{LEVEL}-{SEQ:3}";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    /// `{YEAR}`: first year of the current school year, e.g. "2025"
+    Year,
+    /// `{YY}`: its last two digits, e.g. "25"
+    ShortYear,
+    /// `{LEVEL}`: level of the student's class, see [`level_code`]
+    Level,
+    /// `{SEQ:n}`: sequence number padded with zeros to `n` digits
+    Sequence(usize),
+    /// `{CHECK}`: check digit of the digits before it
+    Check,
+}
+
+/// Why a matricule pattern is rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternError {
+    Unclosed,
+    UnknownToken(String),
+    /// The pattern needs exactly one `{SEQ:n}`
+    Sequence,
+    CheckBeforeSequence,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unclosed =>
+                write!(f, "accolade non fermée"),
+            Self::UnknownToken(token) =>
+                write!(f, "élément {{{token}}} inconnu (YEAR, YY, LEVEL, SEQ:n ou CHECK)"),
+            Self::Sequence =>
+                write!(f, "le format doit contenir un et un seul {{SEQ:n}}, n entre 1 et 9"),
+            Self::CheckBeforeSequence =>
+                write!(f, "{{CHECK}} doit suivre {{SEQ:n}}"),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Code of a class level in a matricule: its digits ("6ème" gives "6"),
+/// or its letters in capitals when it has none
+pub fn level_code(level: &str) -> String {
+    let digits: String = level.chars().filter(char::is_ascii_digit).collect();
+    if !digits.is_empty() {
+        return digits;
+    }
+    level
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Luhn check digit of the digits of `text`
+pub fn check_digit(text: &str) -> char {
+    let sum: u32 = text
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(index, digit)| match (index % 2, digit * 2) {
+            (0, doubled) if doubled > 9 => doubled - 9,
+            (0, doubled) => doubled,
+            _ => digit,
+        })
+        .sum();
+    char::from_digit((10 - sum % 10) % 10, 10).expect("a single digit")
+}
+
+/// Length of the run of leading characters of `text` satisfying `accept`
+fn run_length(text: &str, accept: impl Fn(u8) -> bool) -> usize {
+    text.bytes().take_while(|&b| accept(b)).count()
+}
+
+/// Pattern of the generated matricules, e.g. `{YEAR}{LEVEL}{SEQ:4}{CHECK}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatriculePattern {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+impl MatriculePattern {
+    pub fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let mut segments = Vec::new();
+        let mut rest = pattern;
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('{') {
+                let end = after.find('}').ok_or(PatternError::Unclosed)?;
+                let token = &after[..end];
+                segments.push(match token {
+                    "YEAR" => Segment::Year,
+                    "YY" => Segment::ShortYear,
+                    "LEVEL" => Segment::Level,
+                    "CHECK" => Segment::Check,
+                    _ => match token.strip_prefix("SEQ:").map(str::parse::<usize>) {
+                        Some(Ok(width @ 1..=9)) => Segment::Sequence(width),
+                        Some(_) => return Err(PatternError::Sequence),
+                        None => return Err(PatternError::UnknownToken(token.to_string())),
+                    },
+                });
+                rest = &after[end + 1..];
+            } else {
+                let end = rest.find('{').unwrap_or(rest.len());
+                segments.push(Segment::Literal(rest[..end].to_string()));
+                rest = &rest[end..];
+            }
+        }
+
+        let sequences: Vec<usize> = segments
+            .iter()
+            .enumerate()
+            .filter(|(_, segment)| matches!(segment, Segment::Sequence(_)))
+            .map(|(index, _)| index)
+            .collect();
+        let [sequence] = sequences[..] else {
+            return Err(PatternError::Sequence);
+        };
+        if segments[..sequence].contains(&Segment::Check) {
+            return Err(PatternError::CheckBeforeSequence);
+        }
+
+        Ok(Self { pattern: pattern.to_string(), segments })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    fn render(&self, year: &str, level: &str, sequence: Option<u32>) -> String {
+        let mut matricule = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => matricule.push_str(text),
+                Segment::Year => matricule.push_str(year),
+                Segment::ShortYear => matricule.push_str(&year[year.len().saturating_sub(2)..]),
+                Segment::Level => matricule.push_str(&level_code(level)),
+                Segment::Sequence(width) => match sequence {
+                    Some(sequence) => matricule.push_str(&format!("{:0width$}", sequence, width = width)),
+                    None => matricule.push_str("{SEQ}"),
+                },
+                Segment::Check if sequence.is_some() => matricule.push(check_digit(&matricule)),
+                Segment::Check => {}
+            }
+        }
+        matricule
+    }
+
+    /// Matricule number `sequence` of a student of `level` enrolled in the
+    /// school year starting in `year`
+    pub fn format(&self, year: &str, level: &str, sequence: u32) -> String {
+        self.render(year, level, Some(sequence))
+    }
+
+    /// Matricules with the same year and level share their sequence: the
+    /// pattern with those filled in identifies it
+    pub fn scope(&self, year: &str, level: &str) -> String {
+        self.render(year, level, None)
+    }
+
+    /// Whether `matricule` could have been generated by this pattern,
+    /// including its check digit
+    pub fn matches(&self, matricule: &str) -> bool {
+        self.matches_from(0, matricule, 0)
+    }
+
+    fn matches_from(&self, index: usize, input: &str, position: usize) -> bool {
+        let Some(segment) = self.segments.get(index) else {
+            return position == input.len();
+        };
+        let rest = &input[position..];
+        let digits = run_length(rest, |b| b.is_ascii_digit());
+        let next = |length: usize| self.matches_from(index + 1, input, position + length);
+
+        match segment {
+            Segment::Literal(text) => rest.starts_with(text.as_str()) && next(text.len()),
+            Segment::Year => digits >= 4 && next(4),
+            Segment::ShortYear => digits >= 2 && next(2),
+            Segment::Level => {
+                let length = run_length(rest, |b| b.is_ascii_uppercase() || b.is_ascii_digit());
+                (0..=length).any(next)
+            }
+            Segment::Sequence(width) => (*width..=digits).any(next),
+            Segment::Check => {
+                digits >= 1 && rest.as_bytes()[0] as char == check_digit(&input[..position]) && next(1)
+            }
+        }
+    }
+}
+
+impl Default for MatriculePattern {
+    fn default() -> Self {
+        Self::parse(DEFAULT_PATTERN).expect("the default pattern is valid")
+    }
+}
+
+impl fmt::Display for MatriculePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(MatriculePattern::parse("{YEAR}{LEVEL}"), Err(PatternError::Sequence));
+        assert_eq!(MatriculePattern::parse("{SEQ:2}{SEQ:3}"), Err(PatternError::Sequence));
+        assert_eq!(MatriculePattern::parse("{SEQ:0}"), Err(PatternError::Sequence));
+        assert_eq!(MatriculePattern::parse("{CHECK}{SEQ:4}"), Err(PatternError::CheckBeforeSequence));
+        assert_eq!(MatriculePattern::parse("{SEQ:4"), Err(PatternError::Unclosed));
+        assert_eq!(MatriculePattern::parse("{MONTH}{SEQ:4}"), Err(PatternError::UnknownToken("MONTH".into())));
+    }
+
+    #[test]
+    fn test_format_and_match() {
+        let pattern = MatriculePattern::parse("{YEAR}{LEVEL}{SEQ:4}{CHECK}").unwrap();
+        assert_eq!(check_digit("7992739871"), '3');
+        assert_eq!(pattern.format("2025", "6ème", 12), format!("202560012{}", check_digit("202560012")));
+        assert_eq!(pattern.scope("2025", "6ème"), "20256{SEQ}");
+
+        let matricule = pattern.format("2025", "Terminale", 7);
+        assert!(matricule.starts_with("2025TERMINALE0007"));
+        assert!(pattern.matches(&matricule));
+        assert!(pattern.matches(&pattern.format("2025", "10", 12345)));
+
+        let mut wrong_check = matricule.clone();
+        let last = wrong_check.pop().unwrap();
+        wrong_check.push(if last == '0' { '1' } else { '0' });
+        assert!(!pattern.matches(&wrong_check));
+        assert!(!pattern.matches("2025TERMINALE07"));
+
+        let default = MatriculePattern::default();
+        assert!(default.matches("STU-6-001"));
+        assert!(!default.matches("STU-6-01"));
+        assert!(!default.matches("ETU-6-001"));
+    }
+}
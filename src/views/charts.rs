@@ -0,0 +1,150 @@
+use std::env;
+use crate::grading::stats::NoteSummary;
+
+/// Characters the charts are drawn with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Charset {
+    Unicode,
+    Ascii,
+}
+
+impl Charset {
+    /// Unicode when the locale of the terminal uses UTF-8, ASCII otherwise
+    pub fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|key| env::var(key).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if locale.contains("utf-8") || locale.contains("utf8") {
+            Self::Unicode
+        } else {
+            Self::Ascii
+        }
+    }
+}
+
+/// Partial blocks, from one eighth to a full block
+const EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const ASCII_SPARKS: [char; 8] = ['_', '.', '-', '~', '=', '+', '*', '#'];
+
+/// Share of `value` between `min` and `max`, within 0 and 1
+fn ratio(value: f64, min: f64, max: f64) -> f64 {
+    if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 }
+}
+
+/// Horizontal bar of `value` out of `max`, at most `width` characters long.
+/// A value above zero always shows.
+pub fn bar(value: f64, max: f64, width: usize, charset: Charset) -> String {
+    let eighths = (ratio(value, 0.0, max) * (width * 8) as f64).round() as usize;
+    if value <= 0.0 {
+        return String::new();
+    }
+
+    match charset {
+        Charset::Unicode => {
+            let eighths = eighths.max(1);
+            let mut bar = "█".repeat(eighths / 8);
+            if let Some(partial) = (eighths % 8).checked_sub(1) {
+                bar.push(EIGHTHS[partial]);
+            }
+            bar
+        }
+        Charset::Ascii => "#".repeat(eighths.div_ceil(8).max(1)),
+    }
+}
+
+/// Labelled horizontal bars, one line per `(label, value, shown value)`
+pub fn bar_chart(rows: &[(String, f64, String)], max: f64, width: usize, charset: Charset) -> String {
+    let label_width = rows.iter().map(|(label, _, _)| label.chars().count()).max().unwrap_or(0);
+    let separator = match charset {
+        Charset::Unicode => '│',
+        Charset::Ascii => '|',
+    };
+
+    rows.iter()
+        .map(|(label, value, shown)| {
+            let bar = bar(*value, max, width, charset);
+            format!("{:>label_width$} {} {:<width$} {}\n", label, separator, bar, shown)
+        })
+        .collect()
+}
+
+/// One character per value, as high as the value between `min` and `max`;
+/// missing values are left blank
+pub fn sparkline(values: &[Option<f64>], min: f64, max: f64, charset: Charset) -> String {
+    let levels = match charset {
+        Charset::Unicode => SPARKS,
+        Charset::Ascii => ASCII_SPARKS,
+    };
+
+    values
+        .iter()
+        .map(|value| match value {
+            Some(value) => levels[(ratio(*value, min, max) * 7.0).round() as usize],
+            None => ' ',
+        })
+        .collect()
+}
+
+/// Box plot of a series between `min` and `max`, `width` characters wide:
+/// whiskers from the lowest to the highest value, a box from the first to
+/// the third quartile and a mark on the median
+pub fn box_plot(summary: &NoteSummary, min: f64, max: f64, width: usize, charset: Charset) -> String {
+    let (end, whisker, fill, median) = match charset {
+        Charset::Unicode => ('┃', '─', '▒', '█'),
+        Charset::Ascii => ('|', '-', '=', 'M'),
+    };
+    let position = |value: f64| (ratio(value, min, max) * (width - 1) as f64).round() as usize;
+
+    let mut line = vec![' '; width];
+    let (low, q1, q2, q3, high) = (
+        position(summary.min),
+        position(summary.q1),
+        position(summary.median),
+        position(summary.q3),
+        position(summary.max),
+    );
+    line[low..=high].fill(whisker);
+    line[q1..=q3].fill(fill);
+    line[q1] = '[';
+    line[q3] = ']';
+    line[low] = end;
+    line[high] = end;
+    line[q2] = median;
+
+    line.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grading::stats::summarize;
+
+    #[test]
+    fn test_bars() {
+        assert_eq!(bar(10.0, 20.0, 4, Charset::Unicode), "██");
+        assert_eq!(bar(11.0, 20.0, 4, Charset::Unicode), "██▎");
+        assert_eq!(bar(0.1, 20.0, 4, Charset::Unicode), "▏");
+        assert_eq!(bar(0.1, 20.0, 4, Charset::Ascii), "#");
+        assert_eq!(bar(0.0, 20.0, 4, Charset::Ascii), "");
+        assert_eq!(bar(30.0, 20.0, 4, Charset::Ascii), "####");
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let values = [Some(0.0), Some(10.0), None, Some(20.0)];
+        assert_eq!(sparkline(&values, 0.0, 20.0, Charset::Unicode), "▁▅ █");
+        assert_eq!(sparkline(&values, 0.0, 20.0, Charset::Ascii), "_= #");
+    }
+
+    #[test]
+    fn test_box_plot() {
+        let summary = summarize(&[2.0, 6.0, 8.0, 10.0, 18.0]).unwrap();
+        assert_eq!(box_plot(&summary, 0.0, 20.0, 21, Charset::Ascii), "  |---[=M=]-------|  ");
+        assert_eq!(box_plot(&summary, 0.0, 20.0, 21, Charset::Unicode).chars().count(), 21);
+    }
+}
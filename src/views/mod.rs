@@ -1,7 +1,33 @@
+use std::collections::HashMap;
 use comfy_table::Table;
 use crate::db::repositories::student_repository::Student;
 use crate::db::repositories::subject_repository::Subject;
-use crate::db::repositories::note_repository::Note;
+use crate::db::repositories::note_repository::{MisplacedNote, Note, NoteVersion};
+use crate::db::repositories::period_repository::Period;
+use crate::db::repositories::evaluation_repository::{Evaluation, EvaluationType};
+use crate::db::repositories::class_repository::ClassSummary;
+use crate::db::repositories::teacher_repository::Teacher;
+use crate::db::repositories::enrolment_repository::EnrolmentRecord;
+use crate::db::repositories::absence_repository::Absence;
+use crate::db::repositories::profile_repository::{Guardian, StudentProfile};
+use crate::db::repositories::moderation_repository::ModerationRecord;
+use crate::db::repositories::lock_repository::PeriodLock;
+use crate::db::repositories::audit_repository::AuditEntry;
+use crate::db::repositories::archive_repository::ArchivedRecord;
+use crate::grading::StudentAverage;
+use crate::grading::attendance::AttendanceTotals;
+use crate::grading::comparison::SubjectComparison;
+use crate::grading::promotion::PromotionMove;
+use crate::grading::ranking::MeritEntry;
+use crate::grading::rules::GradingRules;
+use crate::grading::scale::GradeError;
+use crate::grading::warnings::EarlyWarning;
+use crate::grading::stats::{bucket_label, NoteSummary, BUCKETS};
+use crate::reports::council::CouncilReport;
+
+pub mod charts;
+
+use charts::Charset;
 
 /// Display students in a table
 pub fn display_students(students: &[Student]) {
@@ -21,16 +47,172 @@ pub fn display_students(students: &[Student]) {
 }
 
 /// Display subjects in a table
-pub fn display_subjects(subjects: &[Subject]) {
+pub fn display_subjects(subjects: &[Subject], teachers: &HashMap<String, Teacher>) {
     let mut table = Table::new();
-    table.set_header(vec!["Code", "Nom", "Classe", "Coefficient"]);
+    table.set_header(vec!["Code", "Nom", "Classe", "Coefficient", "Enseignant"]);
 
     for subject in subjects {
         table.add_row(vec![
-            &subject.code,
-            &subject.name,
-            &subject.class,
-            &subject.coefficient.to_string(),
+            subject.code.clone(),
+            subject.name.clone(),
+            subject.class.clone(),
+            subject.coefficient.to_string(),
+            teachers.get(&subject.code).map(|t| t.to_string()).unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display teachers with the subjects they teach
+pub fn display_teachers(teachers: &[Teacher], assignments: &HashMap<String, Teacher>) {
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Nom", "Prénom", "Email", "Téléphone", "Matières"]);
+
+    for teacher in teachers {
+        let mut subjects: Vec<&str> = assignments
+            .iter()
+            .filter(|(_, assigned)| assigned.id == teacher.id)
+            .map(|(code, _)| code.as_str())
+            .collect();
+        subjects.sort();
+
+        table.add_row(vec![
+            teacher.id.to_string(),
+            teacher.name.clone(),
+            teacher.surname.clone(),
+            teacher.email.clone().unwrap_or_else(|| "-".to_string()),
+            teacher.phone.clone().unwrap_or_else(|| "-".to_string()),
+            if subjects.is_empty() { "-".to_string() } else { subjects.join(", ") },
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display grading statistics of each subject
+pub fn display_subject_statistics(statistics: &[(Subject, Option<NoteSummary>)]) {
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Matière", "Classe", "Notes", "Moyenne", "Médiane", "Écart-type", "Min", "Q1", "Q3", "Max", "Réussite",
+    ]);
+
+    for (subject, summary) in statistics {
+        let figures = match summary {
+            Some(summary) => vec![
+                summary.count.to_string(),
+                format!("{:.2}", summary.mean),
+                format!("{:.2}", summary.median),
+                format!("{:.2}", summary.std_dev),
+                format!("{:.2}", summary.min),
+                format!("{:.2}", summary.q1),
+                format!("{:.2}", summary.q3),
+                format!("{:.2}", summary.max),
+                format!("{:.0}%", summary.pass_rate),
+            ],
+            None => {
+                let mut empty = vec!["0".to_string()];
+                empty.resize(9, "-".to_string());
+                empty
+            }
+        };
+
+        let mut row = vec![subject.code.clone(), subject.class.clone()];
+        row.extend(figures);
+        table.add_row(row);
+    }
+
+    println!("{}", table);
+}
+
+/// Display a grade distribution as horizontal bars
+pub fn display_distribution(buckets: &[usize; BUCKETS]) {
+    let highest = buckets.iter().copied().max().unwrap_or(0).max(1);
+    let rows: Vec<(String, f64, String)> = buckets
+        .iter()
+        .enumerate()
+        .map(|(index, count)| (bucket_label(index), *count as f64, count.to_string()))
+        .collect();
+
+    print!("{}", charts::bar_chart(&rows, highest as f64, 30, Charset::detect()));
+}
+
+/// Display the spread of the notes of each subject as box plots on /20
+pub fn display_box_plots(statistics: &[(Subject, Option<NoteSummary>)]) {
+    let charset = Charset::detect();
+    let width = statistics.iter().map(|(subject, _)| subject.code.chars().count()).max().unwrap_or(0);
+
+    println!("{:width$}   0{:>40}", "", "20");
+    for (subject, summary) in statistics {
+        let plot = match summary {
+            Some(summary) => charts::box_plot(summary, 0.0, 20.0, 41, charset),
+            None => "-".to_string(),
+        };
+        println!("{:>width$}   {}", subject.code, plot);
+    }
+}
+
+/// Display groups of notes (classes or school years) side by side, subject
+/// by subject, with their gap to the other groups
+pub fn display_comparisons(comparisons: &[SubjectComparison], group_header: &str) {
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Matière", group_header, "Notes", "Moyenne", "Médiane", "Écart-type", "Réussite", "Écart aux autres", "Notation",
+    ]);
+
+    for comparison in comparisons {
+        for (index, group) in comparison.groups.iter().enumerate() {
+            let subject = if index == 0 { comparison.subject.clone() } else { String::new() };
+            table.add_row(vec![
+                subject,
+                group.label.clone(),
+                group.summary.count.to_string(),
+                format!("{:.2}", group.summary.mean),
+                format!("{:.2}", group.summary.median),
+                format!("{:.2}", group.summary.std_dev),
+                format!("{:.0}%", group.summary.pass_rate),
+                group.gap.map(|gap| format!("{:+.2}", gap)).unwrap_or_else(|| "-".to_string()),
+                group.severity.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()),
+            ]);
+        }
+    }
+
+    println!("{}", table);
+}
+
+/// Display the class average of each subject as bars on /20
+pub fn display_subject_average_bars(averages: &[(Subject, Option<f64>)]) {
+    let rows: Vec<(String, f64, String)> = averages
+        .iter()
+        .map(|(subject, average)| match average {
+            Some(average) => (subject.code.clone(), *average, format!("{:.2}", average)),
+            None => (subject.code.clone(), 0.0, "-".to_string()),
+        })
+        .collect();
+
+    print!("{}", charts::bar_chart(&rows, 20.0, 30, Charset::detect()));
+}
+
+/// Display classes with their headcount
+pub fn display_classes(summaries: &[ClassSummary]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Code", "Niveau", "Section", "Année", "Professeur principal", "Effectif"]);
+
+    for summary in summaries {
+        let class = &summary.class;
+        let headcount = match class.capacity {
+            Some(capacity) if summary.is_full() => format!("{}/{} (complète)", summary.headcount, capacity),
+            Some(capacity) => format!("{}/{}", summary.headcount, capacity),
+            None => summary.headcount.to_string(),
+        };
+
+        table.add_row(vec![
+            class.code.clone(),
+            class.level.clone(),
+            class.section.clone().unwrap_or_else(|| "-".to_string()),
+            summary.year_label.clone().unwrap_or_else(|| "-".to_string()),
+            class.head_teacher.clone().unwrap_or_else(|| "-".to_string()),
+            headcount,
         ]);
     }
 
@@ -54,6 +236,62 @@ pub fn display_notes(notes: &[Note]) {
     println!("{}", table);
 }
 
+/// Display the versions of a note, oldest first
+pub fn display_note_history(versions: &[NoteVersion]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Version", "Date", "Par", "Avant", "Après", "Motif"]);
+
+    for version in versions {
+        table.add_row(vec![
+            version.version.to_string(),
+            version.changed_at.clone().unwrap_or_else(|| "inconnue".to_string()),
+            version.changed_by.clone().unwrap_or_else(|| "-".to_string()),
+            version.previous_value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+            format!("{:.2}", version.value),
+            version.reason.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the notes given in a subject of another class than the student's
+pub fn display_misplaced_notes(notes: &[MisplacedNote]) {
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Matricule", "Classe", "Matière", "Classe de la matière", "Note"]);
+
+    for misplaced in notes {
+        table.add_row(vec![
+            &misplaced.note.id.to_string(),
+            &misplaced.note.matricule,
+            &misplaced.student_class,
+            &misplaced.note.subject_code,
+            &misplaced.subject_class,
+            &format!("{:.2}", misplaced.note.value),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the notes whose value does not fit the grading scale
+pub fn display_invalid_notes(notes: &[(Note, GradeError)]) {
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Matricule", "Matière", "Note", "Problème"]);
+
+    for (note, error) in notes {
+        table.add_row(vec![
+            note.id.to_string(),
+            note.matricule.clone(),
+            note.subject_code.clone(),
+            format!("{:.2}", note.value),
+            error.to_string(),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
 /// Display student notes with subject names (requires joining data)
 pub fn display_student_notes_with_details(
     notes: &[Note],
@@ -84,3 +322,483 @@ pub fn display_student_notes_with_details(
     println!("{}", table);
 }
 
+
+/// Display the subject averages and the weighted general average of a student
+pub fn display_student_average(average: &StudentAverage, subjects: &[Subject]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Matière", "Code", "Moyenne", "Coefficient", "Points"]);
+
+    for subject_average in &average.subjects {
+        let subject_name = subjects
+            .iter()
+            .find(|s| s.code == subject_average.subject_code)
+            .map(|s| s.name.as_str())
+            .unwrap_or("Inconnu");
+
+        table.add_row(vec![
+            subject_name,
+            &subject_average.subject_code,
+            &subject_average.average.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+            &subject_average.coefficient.to_string(),
+            &subject_average.points().map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("\nMoyennes par matière:");
+    println!("{}", table);
+
+    match average.average {
+        Some(value) => println!(
+            "Moyenne générale pondérée: {:.2}/20 (coefficients: {})",
+            value, average.total_coefficients
+        ),
+        None => println!("Moyenne générale pondérée: -"),
+    }
+}
+
+/// Display the general average of a student in each term and over the year
+pub fn display_term_averages(terms: &[(Period, StudentAverage)], annual: &StudentAverage) {
+    let mut table = Table::new();
+    table.set_header(vec!["Période", "Moyenne"]);
+
+    for (period, average) in terms {
+        table.add_row(vec![
+            period.to_string(),
+            average.average.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+    table.add_row(vec![
+        "Moyenne annuelle".to_string(),
+        annual.average.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+    ]);
+
+    let values: Vec<Option<f64>> = terms.iter().map(|(_, average)| average.average).collect();
+
+    println!("\nMoyennes par période:");
+    println!("{}", table);
+    println!("Évolution : {}", charts::sparkline(&values, 0.0, 20.0, Charset::detect()));
+}
+
+/// Display the periods of an academic year
+pub fn display_periods(periods: &[Period]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Période", "Début", "Fin"]);
+
+    for period in periods {
+        table.add_row(vec![period.to_string(), period.start_date.clone(), period.end_date.clone()]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the evaluation types and their share of a subject average
+pub fn display_evaluation_types(types: &[EvaluationType]) {
+    let total: f64 = types.iter().map(|t| t.weight).sum();
+    let mut table = Table::new();
+    table.set_header(vec!["Code", "Libellé", "Poids", "Part si tous évalués", "Hors évaluation"]);
+
+    for evaluation_type in types {
+        table.add_row(vec![
+            evaluation_type.code.clone(),
+            evaluation_type.label.clone(),
+            format!("{}", evaluation_type.weight),
+            format!("{:.0} %", evaluation_type.weight * 100.0 / total),
+            if evaluation_type.is_default { "oui" } else { "" }.to_string(),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display evaluations in a table
+pub fn display_evaluations(evaluations: &[Evaluation]) {
+    let mut table = Table::new();
+    table.set_header(vec!["N°", "Date", "Type", "Intitulé", "Barème", "Poids"]);
+
+    for evaluation in evaluations {
+        table.add_row(vec![
+            evaluation.id.to_string(),
+            evaluation.date.clone(),
+            evaluation.type_code.clone(),
+            evaluation.title.clone(),
+            format!("/{}", evaluation.max_score),
+            format!("{}", evaluation.weight),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the notes before and after a moderation, then their means
+pub fn display_moderation_preview(notes: &[Note], moderated: &[f64]) {
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Matricule", "Avant", "Après", "Écart"]);
+
+    for (note, value) in notes.iter().zip(moderated) {
+        table.add_row(vec![
+            note.id.to_string(),
+            note.matricule.clone(),
+            format!("{:.2}", note.value),
+            format!("{:.2}", value),
+            format!("{:+.2}", value - note.value),
+        ]);
+    }
+
+    println!("{}", table);
+    let before: Vec<f64> = notes.iter().map(|note| note.value).collect();
+    if let (Some(before), Some(after)) = (crate::grading::mean(&before), crate::grading::mean(moderated)) {
+        println!("Moyenne : {:.2} → {:.2}", before, after);
+    }
+}
+
+/// Display the moderations of a subject
+pub fn display_moderations(records: &[ModerationRecord]) {
+    let mut table = Table::new();
+    table.set_header(vec!["N°", "Appliquée le", "Par", "Évaluation", "Période", "Modulation", "Notes", "Annulée le"]);
+
+    for record in records {
+        table.add_row(vec![
+            record.id.to_string(),
+            record.applied_at.clone(),
+            record.applied_by.clone().unwrap_or_else(|| "-".to_string()),
+            record.evaluation_id.map(|id| id.to_string()).unwrap_or_else(|| "-".to_string()),
+            record.period_id.map(|id| id.to_string()).unwrap_or_else(|| "toutes".to_string()),
+            record.description.clone(),
+            record.notes.to_string(),
+            record.reverted_at.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the locks of the periods they belong to
+pub fn display_locks(locks: &[(Period, PeriodLock)]) {
+    let mut table = Table::new();
+    table.set_header(vec!["N°", "Période", "Notes verrouillées", "Par", "Le"]);
+
+    for (period, lock) in locks {
+        table.add_row(vec![
+            lock.id.to_string(),
+            period.to_string(),
+            lock.scope.to_string(),
+            lock.locked_by.clone().unwrap_or_else(|| "-".to_string()),
+            lock.locked_at.clone(),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the audit log, latest first
+pub fn display_audit_log(entries: &[AuditEntry]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Date", "Par", "Action", "Détails", "Justification"]);
+
+    for entry in entries {
+        table.add_row(vec![
+            entry.performed_at.clone(),
+            entry.performed_by.clone().unwrap_or_else(|| "-".to_string()),
+            entry.action.to_string(),
+            entry.details.clone(),
+            entry.justification.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the archived records, numbered to be picked
+pub fn display_archives(records: &[ArchivedRecord]) {
+    let mut table = Table::new();
+    table.set_header(vec!["N°", "Élément", "Détail", "Archivé le", "Notes"]);
+
+    for (number, archived) in records.iter().enumerate() {
+        table.add_row(vec![
+            (number + 1).to_string(),
+            archived.record.to_string(),
+            archived.label.clone(),
+            archived.archived_at.clone(),
+            archived.notes.to_string(),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display a merit list (class or subject ranking)
+pub fn display_merit_list(entries: &[MeritEntry]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Rang", "Matricule", "Nom", "Prénom", "Moyenne", "Mention", "Décision"]);
+
+    for entry in entries {
+        table.add_row(vec![
+            &entry.rank.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string()),
+            &entry.student.matricule,
+            &entry.student.name,
+            &entry.student.surname,
+            &entry.average.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+            entry.mention.as_deref().unwrap_or("-"),
+            &entry.decision.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+pub fn display_grading_rules(rules: &GradingRules) {
+    let mut mentions = Table::new();
+    mentions.set_header(vec!["Mention", "Moyenne minimale"]);
+    for rule in &rules.mentions {
+        mentions.add_row(vec![rule.label.clone(), format!("{:.2}", rule.min_average)]);
+    }
+    println!("{}", mentions);
+
+    let decisions = &rules.decisions;
+    let mut thresholds = Table::new();
+    thresholds.set_header(vec!["Règle de passage", "Valeur"]);
+    thresholds.add_row(vec!["Moyenne d'admission".to_string(), format!("{:.2}", decisions.admission_average)]);
+    thresholds.add_row(vec!["Moyenne d'exclusion".to_string(), format!("{:.2}", decisions.exclusion_average)]);
+    thresholds.add_row(vec!["Matière en échec sous".to_string(), format!("{:.2}", decisions.failing_subject_average)]);
+    thresholds.add_row(vec!["Matières en échec tolérées".to_string(), decisions.max_failed_subjects.to_string()]);
+    for key in &decisions.key_subjects {
+        thresholds.add_row(vec![format!("Minimum en {}*", key.subject_prefix), format!("{:.2}", key.min_average)]);
+    }
+    println!("{}", thresholds);
+}
+
+/// Display the enrolments of a student, year by year
+pub fn display_enrolments(records: &[EnrolmentRecord]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Année", "Classe", "Statut", "Décision", "Notes"]);
+
+    for record in records {
+        let enrolment = &record.enrolment;
+        table.add_row(vec![
+            record.year_label.clone(),
+            enrolment.class.clone(),
+            enrolment.status.to_string(),
+            enrolment.decision.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string()),
+            record.note_count.to_string(),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the year-end outcome of each student
+pub fn display_promotion(moves: &[PromotionMove]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Matricule", "Nom", "Prénom", "Décision", "Passage"]);
+
+    for change in moves {
+        table.add_row(vec![
+            change.student.matricule.clone(),
+            change.student.name.clone(),
+            change.student.surname.clone(),
+            change.decision.map(|d| d.to_string()).unwrap_or_else(|| "-".to_string()),
+            change.outcome.to_string(),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display absences and lateness in a table
+pub fn display_absences(absences: &[Absence]) {
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Matricule", "Date", "Type", "Matière", "Durée", "Justifiée", "Motif"]);
+
+    for absence in absences {
+        table.add_row(vec![
+            absence.id.to_string(),
+            absence.matricule.clone(),
+            absence.date.clone(),
+            absence.kind.to_string(),
+            absence.subject_code.clone().unwrap_or_else(|| "-".to_string()),
+            absence.duration_label(),
+            if absence.justified { "oui" } else { "non" }.to_string(),
+            absence.reason.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the students exceeding the attendance thresholds
+pub fn display_attendance_alerts(alerts: &[(Student, AttendanceTotals, Vec<String>)]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Matricule", "Nom", "Prénom", "Absences", "Non justifiées", "Retards", "Alertes"]);
+
+    for (student, totals, reasons) in alerts {
+        table.add_row(vec![
+            student.matricule.clone(),
+            student.name.clone(),
+            student.surname.clone(),
+            format!("{} h", totals.absence_hours),
+            format!("{} h", totals.unjustified_hours),
+            totals.lateness.to_string(),
+            reasons.join(", "),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the students flagged by the early-warning detection
+pub fn display_early_warnings(warnings: &[EarlyWarning]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Priorité", "Matricule", "Nom", "Prénom", "Classe", "Moyenne", "Motifs"]);
+
+    for warning in warnings {
+        let reasons: Vec<String> = warning.reasons.iter().map(|r| r.to_string()).collect();
+        table.add_row(vec![
+            warning.priority_label().to_string(),
+            warning.student.matricule.clone(),
+            warning.student.name.clone(),
+            warning.student.surname.clone(),
+            warning.student.class.clone(),
+            warning.average.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+            reasons.join("\n"),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the profile of a student, `None` if it was never filled in
+pub fn display_profile(profile: Option<&StudentProfile>) {
+    let field = |value: Option<&Option<String>>| value.cloned().flatten().unwrap_or_else(|| "-".to_string());
+
+    let mut table = Table::new();
+    table.set_header(vec!["Sexe", "Date de naissance", "Lieu de naissance", "Adresse", "Photo"]);
+    table.add_row(vec![
+        profile.and_then(|p| p.gender).map(|g| g.to_string()).unwrap_or_else(|| "-".to_string()),
+        field(profile.map(|p| &p.birth_date)),
+        field(profile.map(|p| &p.birth_place)),
+        field(profile.map(|p| &p.address)),
+        field(profile.map(|p| &p.photo_path)),
+    ]);
+
+    println!("{}", table);
+}
+
+/// Display the guardians of a student
+pub fn display_guardians(guardians: &[Guardian]) {
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Nom", "Lien", "Téléphone", "Email"]);
+
+    for guardian in guardians {
+        table.add_row(vec![
+            guardian.id.to_string(),
+            guardian.name.clone(),
+            guardian.relation.clone(),
+            guardian.phone.clone().unwrap_or_else(|| "-".to_string()),
+            guardian.email.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display the students found through one of their guardians
+pub fn display_guardian_matches(matches: &[(Student, Guardian)]) {
+    let mut table = Table::new();
+    table.set_header(vec!["Matricule", "Nom", "Prénom", "Classe", "Responsable", "Téléphone"]);
+
+    for (student, guardian) in matches {
+        table.add_row(vec![
+            student.matricule.clone(),
+            student.name.clone(),
+            student.surname.clone(),
+            student.class.clone(),
+            guardian.to_string(),
+            guardian.phone.clone().unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
+/// Display a class council report, section by section
+pub fn display_council_report(report: &CouncilReport) {
+    let value = |v: Option<f64>| v.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string());
+
+    println!(
+        "\n{} étudiants — moyenne de la classe : {}",
+        report.headcount,
+        value(report.class_average)
+    );
+
+    println!("\nMoyennes par matière :");
+    let mut subjects = Table::new();
+    subjects.set_header(vec!["Matière", "Code", "Enseignant", "Coef.", "Moyenne", "En échec"]);
+    for line in &report.subjects {
+        subjects.add_row(vec![
+            line.subject.name.clone(),
+            line.subject.code.clone(),
+            line.teacher.clone().unwrap_or_else(|| "-".to_string()),
+            line.subject.coefficient.to_string(),
+            value(line.average),
+            line.failing.to_string(),
+        ]);
+    }
+    println!("{}", subjects);
+
+    println!("\nTête de classe :");
+    display_merit_list(&report.top);
+    println!("\nFin de classe :");
+    display_merit_list(&report.bottom);
+
+    if report.at_risk.is_empty() {
+        println!("\nAucun étudiant en difficulté.");
+    } else {
+        println!("\nÉtudiants en difficulté :");
+        let mut at_risk = Table::new();
+        at_risk.set_header(vec!["Matricule", "Nom", "Prénom", "Moyenne", "Période précédente", "Motifs"]);
+        for line in &report.at_risk {
+            at_risk.add_row(vec![
+                line.student.matricule.clone(),
+                line.student.name.clone(),
+                line.student.surname.clone(),
+                value(line.average),
+                value(line.previous),
+                line.reasons.join(", "),
+            ]);
+        }
+        println!("{}", at_risk);
+    }
+
+    println!("\nAssiduité de la classe : {}", report.attendance);
+    if !report.attendance_alerts.is_empty() {
+        display_attendance_alerts(&report.attendance_alerts);
+    }
+
+    println!("\nMentions :");
+    let mut mentions = Table::new();
+    mentions.set_header(vec!["Mention", "Étudiants"]);
+    for (mention, count) in &report.mentions {
+        mentions.add_row(vec![mention.clone(), count.to_string()]);
+    }
+    println!("{}", mentions);
+
+    println!("\nDécisions proposées :");
+    let counts: Vec<String> = report
+        .decision_counts()
+        .iter()
+        .map(|(decision, count)| format!("{} : {}", decision, count))
+        .collect();
+    println!("{}", counts.join(" — "));
+    let mut decisions = Table::new();
+    decisions.set_header(vec!["Matricule", "Nom", "Prénom", "Décision", "Motifs"]);
+    for (student, outcome) in &report.decisions {
+        decisions.add_row(vec![
+            student.matricule.clone(),
+            student.name.clone(),
+            student.surname.clone(),
+            outcome.decision.to_string(),
+            outcome.reasons.join(", "),
+        ]);
+    }
+    println!("{}", decisions);
+}